@@ -0,0 +1,276 @@
+use crate::hyper_adapter::HyperAdapter;
+use easyhttpmock::{
+    config::EasyHttpMockConfig,
+    errors::{EasyHttpMockError, ServerError},
+    journal::Journal,
+    mock::MockState,
+    server::ServerAdapter,
+    EasyHttpMock, HttpMockResult,
+};
+use std::{marker::PhantomData, thread};
+use tokio::sync::{mpsc, oneshot};
+
+/// Command sent to the thread running the server.
+enum Command {
+    Register(MockState, oneshot::Sender<HttpMockResult<String>>),
+    Stop(oneshot::Sender<HttpMockResult<()>>),
+}
+
+/// Mock server usable from synchronous tests
+///
+/// The server runs on a background thread with its own Tokio runtime, so tests for blocking
+/// clients do not need a runtime to set up mocks. The server is stopped when the facade is
+/// dropped. Its methods block the calling thread and must not be called from async code.
+///
+/// # Examples
+///
+/// ```rust,no_run
+/// use easyhttpmock_hyper::{
+///     blocking::BlockingEasyHttpMock,
+///     config::EasyHttpMockConfig,
+///     hyper_adapter::HyperAdapter,
+///     matchers::path,
+///     mock::{given, Mock, StatusCodeExt},
+/// };
+/// use http::StatusCode;
+///
+/// let config = EasyHttpMockConfig::<HyperAdapter>::default();
+/// let mut server = BlockingEasyHttpMock::new(config).unwrap();
+/// server
+///     .register(Mock::of(given(path("/test")).will_return(StatusCode::OK.respond().empty())))
+///     .unwrap();
+///
+/// // ... call server.url("/test") with a blocking client ...
+///
+/// server.verify().unwrap();
+/// ```
+pub struct BlockingEasyHttpMock<S = HyperAdapter>
+where
+    S: ServerAdapter,
+{
+    commands: Option<mpsc::UnboundedSender<Command>>,
+    thread: Option<thread::JoinHandle<()>>,
+    base_url: String,
+    journal: Journal,
+    server: PhantomData<fn() -> S>,
+}
+
+impl<S> BlockingEasyHttpMock<S>
+where
+    S: ServerAdapter + 'static,
+    EasyHttpMockConfig<S>: Send,
+{
+    /// Creates the mock server on a background thread.
+    ///
+    /// # Arguments
+    /// * `config` - The configuration for the mock server.
+    ///
+    /// # Returns
+    /// The facade, or an error if the runtime or the server could not be created.
+    pub fn new(config: EasyHttpMockConfig<S>) -> HttpMockResult<Self> {
+        let (commands, mut receiver) = mpsc::unbounded_channel();
+        let (created, creation) = std::sync::mpsc::channel();
+
+        let thread = thread::Builder::new()
+            .name("easyhttpmock".to_string())
+            .spawn(move || {
+                let runtime = match tokio::runtime::Builder::new_current_thread()
+                    .enable_all()
+                    .build()
+                {
+                    Ok(runtime) => runtime,
+                    Err(e) => {
+                        let _ = created.send(Err(creation_error(e.to_string())));
+                        return;
+                    }
+                };
+
+                runtime.block_on(async move {
+                    let mut server = match EasyHttpMock::new(config) {
+                        Ok(server) => server,
+                        Err(e) => {
+                            let _ = created.send(Err(e));
+                            return;
+                        }
+                    };
+                    let _ = created.send(Ok((
+                        server.url(""),
+                        server
+                            .journal()
+                            .clone(),
+                    )));
+
+                    while let Some(command) = receiver
+                        .recv()
+                        .await
+                    {
+                        match command {
+                            Command::Register(mock, reply) => {
+                                let registered = server
+                                    .register_mock(mock)
+                                    .await
+                                    .map(|_| server.url(""));
+                                let _ = reply.send(registered);
+                            }
+                            Command::Stop(reply) => {
+                                let _ = reply.send(server.stop().await);
+                                return;
+                            }
+                        }
+                    }
+
+                    // The facade was dropped without being stopped
+                    let _ = server.stop().await;
+                });
+            })
+            .map_err(|e| creation_error(e.to_string()))?;
+
+        let (base_url, journal) = creation
+            .recv()
+            .map_err(|e| creation_error(e.to_string()))??;
+
+        Ok(Self {
+            commands: Some(commands),
+            thread: Some(thread),
+            base_url,
+            journal,
+            server: PhantomData,
+        })
+    }
+}
+
+impl<S: ServerAdapter> BlockingEasyHttpMock<S> {
+    /// Registers a mock and starts the server.
+    ///
+    /// # Arguments
+    /// * `mock` - The mock to handle incoming requests.
+    ///
+    /// # Returns
+    /// A result indicating whether the server started successfully.
+    pub fn register(&mut self, mock: MockState) -> HttpMockResult<()> {
+        let (reply, registered) = oneshot::channel();
+        self.send(Command::Register(mock, reply))?;
+
+        self.base_url = registered
+            .blocking_recv()
+            .map_err(|_| not_running())??;
+
+        Ok(())
+    }
+
+    /// Checks every request received by the server matched the mock.
+    ///
+    /// # Returns
+    /// An error describing the first request that did not match.
+    pub fn verify(&self) -> HttpMockResult<()> {
+        Ok(self
+            .journal
+            .verify()?)
+    }
+
+    /// Stops the server and waits for its thread to finish.
+    ///
+    /// # Returns
+    /// A result indicating whether the server stopped successfully.
+    pub fn stop(&mut self) -> HttpMockResult<()> {
+        let (reply, stopped) = oneshot::channel();
+        self.send(Command::Stop(reply))?;
+
+        let stopped = stopped
+            .blocking_recv()
+            .map_err(|_| not_running())?;
+        self.join()?;
+
+        stopped
+    }
+
+    /// Returns the full URL for a given path.
+    ///
+    /// # Arguments
+    /// * `path` - The path to append to the base URL.
+    ///
+    /// # Returns
+    /// The full URL for the given path.
+    pub fn url(&self, path: &str) -> String {
+        format!("{}{}", self.base_url, path)
+    }
+
+    /// Returns the base URL of the server, known once a mock is registered if the system picks
+    /// the port.
+    ///
+    /// # Returns
+    /// The base URL of the server.
+    pub fn base_url(&self) -> &str {
+        &self.base_url
+    }
+
+    /// Returns the journal of the requests received by the server.
+    ///
+    /// # Returns
+    /// The journal of the server.
+    pub fn journal(&self) -> &Journal {
+        &self.journal
+    }
+
+    /// Sends a command to the thread running the server.
+    ///
+    /// # Arguments
+    /// * `command` - The command to send.
+    ///
+    /// # Returns
+    /// An error if the server is no longer running.
+    fn send(&self, command: Command) -> HttpMockResult<()> {
+        self.commands
+            .as_ref()
+            .and_then(|commands| {
+                commands
+                    .send(command)
+                    .ok()
+            })
+            .ok_or_else(not_running)
+    }
+
+    /// Closes the command channel and waits for the thread running the server to finish.
+    ///
+    /// # Returns
+    /// An error if the thread panicked.
+    fn join(&mut self) -> HttpMockResult<()> {
+        self.commands.take();
+
+        match self.thread.take() {
+            Some(thread) => thread
+                .join()
+                .map_err(|_| {
+                    EasyHttpMockError::Server(ServerError::Stop(
+                        "Mock server thread panicked".to_string(),
+                    ))
+                }),
+            None => Ok(()),
+        }
+    }
+}
+
+impl<S: ServerAdapter> Drop for BlockingEasyHttpMock<S> {
+    fn drop(&mut self) {
+        let _ = self.join();
+    }
+}
+
+/// Builds the error returned when the server could not be created.
+///
+/// # Arguments
+/// * `message` - The cause of the error.
+///
+/// # Returns
+/// The creation error.
+fn creation_error(message: String) -> EasyHttpMockError {
+    EasyHttpMockError::Server(ServerError::Creation(message))
+}
+
+/// Builds the error returned when the server is no longer running.
+///
+/// # Returns
+/// The error.
+fn not_running() -> EasyHttpMockError {
+    EasyHttpMockError::Server(ServerError::Stop("Mock server is not running".to_string()))
+}
@@ -0,0 +1,86 @@
+use bytes::Bytes;
+use std::{
+    net::SocketAddr,
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+
+/// What the server does with an accepted connection, used to inject faults at the transport level
+///
+/// # Examples
+///
+/// ```rust
+/// use easyhttpmock_hyper::{connection::ConnectionAction, hyper_adapter::HyperAdapterConfig};
+///
+/// let config = HyperAdapterConfig::builder()
+///     .on_connection(|_peer| ConnectionAction::Close)
+///     .build();
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ConnectionAction {
+    /// Serve the connection normally
+    Serve,
+    /// Close the connection before reading anything from it
+    Close,
+    /// Wait before serving the connection
+    Delay(Duration),
+    /// Write the given bytes as is, then close the connection without reading the request
+    Raw(Bytes),
+}
+
+/// Event on a connection accepted by the server
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ConnectionEvent {
+    /// A connection was accepted from the peer
+    Accepted(SocketAddr),
+    /// The connection with the peer was closed
+    Closed(SocketAddr),
+}
+
+/// Hook deciding what the server does with each accepted connection
+pub type ConnectionHook = Arc<dyn Fn(SocketAddr) -> ConnectionAction + Send + Sync + 'static>;
+
+/// Log of the connection events of a server
+///
+/// Cloning the log is cheap, every clone records into the same events.
+#[derive(Clone, Debug, Default)]
+pub struct ConnectionEvents {
+    events: Arc<Mutex<Vec<ConnectionEvent>>>,
+}
+
+impl ConnectionEvents {
+    /// Creates an empty log
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Records a new event in the log
+    ///
+    /// # Arguments
+    ///
+    /// * `event` - The event to record
+    pub fn record(&self, event: ConnectionEvent) {
+        if let Ok(mut events) = self.events.lock() {
+            events.push(event);
+        }
+    }
+
+    /// Returns a snapshot of the recorded events, in the order they happened
+    ///
+    /// # Returns
+    ///
+    /// * `Vec<ConnectionEvent>` - The recorded events
+    pub fn events(&self) -> Vec<ConnectionEvent> {
+        self.events
+            .lock()
+            .map(|events| events.clone())
+            .unwrap_or_default()
+    }
+
+    /// Removes all recorded events
+    pub fn clear(&self) {
+        if let Ok(mut events) = self.events.lock() {
+            events.clear();
+        }
+    }
+}
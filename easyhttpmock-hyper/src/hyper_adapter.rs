@@ -0,0 +1,940 @@
+use crate::{
+    connection::{ConnectionAction, ConnectionEvent, ConnectionEvents, ConnectionHook},
+    service::MockService,
+};
+#[cfg(feature = "rust-tls")]
+use easyhttpmock::tls::ClientAuth;
+use easyhttpmock::{
+    errors::{EasyHttpMockError, MockError, ServerError},
+    journal::Journal,
+    mock::Mock,
+    server::{
+        generate_randon_port, OversizedBody, PortGenerator, ServerAdapter, DEFAULT_DRAIN_TIMEOUT,
+    },
+    tls::ClientCertificate,
+    HttpMockResult,
+};
+use http::Version;
+use hyper_util::{
+    rt::{TokioExecutor, TokioIo},
+    server::{
+        conn::auto,
+        graceful::{GracefulShutdown, Watcher},
+    },
+};
+#[cfg(unix)]
+use std::path::{Path, PathBuf};
+use std::{io, net::SocketAddr, sync::Arc, time::Duration};
+#[cfg(unix)]
+use tokio::net::UnixListener;
+use tokio::{
+    io::{AsyncRead, AsyncWrite, AsyncWriteExt},
+    net::{TcpListener, TcpStream},
+    sync::oneshot,
+    task::{JoinHandle, JoinSet},
+};
+
+/// Builder for HyperAdapterConfig
+pub struct HyperAdapterConfigBuilder {
+    hostname: String,
+    interface: String,
+    protocol_version: Version,
+    port: u16,
+    #[cfg(feature = "rust-tls")]
+    cert: Option<Vec<u8>>,
+    #[cfg(feature = "rust-tls")]
+    key: Option<Vec<u8>>,
+    #[cfg(feature = "rust-tls")]
+    ca: Option<Vec<u8>>,
+    #[cfg(feature = "rust-tls")]
+    client_auth: ClientAuth,
+    on_connection: Option<ConnectionHook>,
+    #[cfg(unix)]
+    unix_socket: Option<PathBuf>,
+    drain_timeout: Duration,
+    max_body_size: Option<usize>,
+    oversized_body: OversizedBody,
+    max_journal_body_size: Option<usize>,
+}
+
+impl HyperAdapterConfigBuilder {
+    /// Sets the hostname for the server.
+    ///
+    /// # Arguments
+    /// * `hostname` - The hostname to set.
+    ///
+    /// # Returns
+    /// A new `HyperAdapterConfigBuilder` instance with the hostname set.
+    pub fn hostname(mut self, hostname: &str) -> Self {
+        self.hostname = hostname.to_string();
+        self
+    }
+
+    /// Sets the interface for the server.
+    ///
+    /// # Arguments
+    /// * `interface` - The interface to set.
+    ///
+    /// # Returns
+    /// A new `HyperAdapterConfigBuilder` instance with the interface set.
+    pub fn interface(mut self, interface: &str) -> Self {
+        self.interface = interface.to_string();
+        self
+    }
+
+    /// Sets the protocol version for the server.
+    ///
+    /// `HTTP_11` serves HTTP/1.1, `HTTP_2` serves h2c over plain HTTP and h2 negotiated with ALPN
+    /// over TLS. Other versions are rejected when the server starts.
+    ///
+    /// # Arguments
+    /// * `protocol_version` - The protocol version to set.
+    ///
+    /// # Returns
+    /// A new `HyperAdapterConfigBuilder` instance with the protocol version set.
+    pub fn protocol_version(mut self, protocol_version: Version) -> Self {
+        self.protocol_version = protocol_version;
+        self
+    }
+
+    /// Sets the port for the server, `0` lets the system pick a free port when the server starts.
+    ///
+    /// # Arguments
+    /// * `port` - The port to set.
+    ///
+    /// # Returns
+    /// A new `HyperAdapterConfigBuilder` instance with the port set.
+    pub fn port(mut self, port: u16) -> Self {
+        self.port = port;
+        self
+    }
+
+    /// Sets the certificate for the server.
+    ///
+    /// # Arguments
+    /// * `cert` - The DER encoded certificate to set.
+    ///
+    /// # Returns
+    /// A new `HyperAdapterConfigBuilder` instance with the certificate set.
+    #[cfg(feature = "rust-tls")]
+    pub fn cert(mut self, cert: Vec<u8>) -> Self {
+        self.cert = Some(cert);
+        self
+    }
+
+    /// Sets the key for the server.
+    ///
+    /// # Arguments
+    /// * `key` - The DER encoded private key to set.
+    ///
+    /// # Returns
+    /// A new `HyperAdapterConfigBuilder` instance with the key set.
+    #[cfg(feature = "rust-tls")]
+    pub fn key(mut self, key: Vec<u8>) -> Self {
+        self.key = Some(key);
+        self
+    }
+
+    /// Sets the CA certificate client certificates are verified against.
+    ///
+    /// # Arguments
+    /// * `ca` - The DER encoded CA certificate to set.
+    ///
+    /// # Returns
+    /// A new `HyperAdapterConfigBuilder` instance with the CA certificate set.
+    #[cfg(feature = "rust-tls")]
+    pub fn ca(mut self, ca: Vec<u8>) -> Self {
+        self.ca = Some(ca);
+        self
+    }
+
+    /// Sets whether the server asks clients for a certificate.
+    ///
+    /// The certificate presented by the client is recorded on the request and in the journal.
+    ///
+    /// # Arguments
+    /// * `client_auth` - The client authentication mode to set.
+    ///
+    /// # Returns
+    /// A new `HyperAdapterConfigBuilder` instance with the client authentication mode set.
+    #[cfg(feature = "rust-tls")]
+    pub fn client_auth(mut self, client_auth: ClientAuth) -> Self {
+        self.client_auth = client_auth;
+        self
+    }
+
+    /// Sets the path of a Unix domain socket the server listens on, instead of the interface and
+    /// the port.
+    ///
+    /// The socket file is created when the server starts and removed when it stops or is dropped.
+    ///
+    /// # Arguments
+    /// * `path` - The path of the socket file, which must not exist yet.
+    ///
+    /// # Returns
+    /// A new `HyperAdapterConfigBuilder` instance with the socket path set.
+    #[cfg(unix)]
+    pub fn unix_socket(mut self, path: impl Into<PathBuf>) -> Self {
+        self.unix_socket = Some(path.into());
+        self
+    }
+
+    /// Sets the time in-flight requests have to complete when the server stops.
+    ///
+    /// # Arguments
+    /// * `drain_timeout` - The drain timeout to set.
+    ///
+    /// # Returns
+    /// A new `HyperAdapterConfigBuilder` instance with the drain timeout set.
+    pub fn drain_timeout(mut self, drain_timeout: Duration) -> Self {
+        self.drain_timeout = drain_timeout;
+        self
+    }
+
+    /// Sets the maximum size of request bodies, larger requests are not matched.
+    ///
+    /// Encoded bodies must fit the limit both as received and once decoded.
+    ///
+    /// # Arguments
+    /// * `max_body_size` - The maximum size of request bodies, in bytes.
+    ///
+    /// # Returns
+    /// A new `HyperAdapterConfigBuilder` instance with the maximum body size set.
+    pub fn max_body_size(mut self, max_body_size: usize) -> Self {
+        self.max_body_size = Some(max_body_size);
+        self
+    }
+
+    /// Sets what the server does with requests whose body exceeds the maximum size.
+    ///
+    /// # Arguments
+    /// * `oversized_body` - Whether to answer `413 Payload Too Large` or reset the connection.
+    ///
+    /// # Returns
+    /// A new `HyperAdapterConfigBuilder` instance with the oversized body action set.
+    pub fn oversized_body(mut self, oversized_body: OversizedBody) -> Self {
+        self.oversized_body = oversized_body;
+        self
+    }
+
+    /// Sets the size the bodies recorded in the journal are truncated to.
+    ///
+    /// # Arguments
+    /// * `max_journal_body_size` - The maximum size of recorded bodies, in bytes.
+    ///
+    /// # Returns
+    /// A new `HyperAdapterConfigBuilder` instance with the maximum journal body size set.
+    pub fn max_journal_body_size(mut self, max_journal_body_size: usize) -> Self {
+        self.max_journal_body_size = Some(max_journal_body_size);
+        self
+    }
+
+    /// Sets the hook deciding what the server does with each accepted connection.
+    ///
+    /// Connections on a Unix domain socket have no peer address, the hook and the connection events
+    /// get `0.0.0.0:0` for them.
+    ///
+    /// # Arguments
+    /// * `hook` - The hook, called with the address of the peer.
+    ///
+    /// # Returns
+    /// A new `HyperAdapterConfigBuilder` instance with the connection hook set.
+    pub fn on_connection<F>(mut self, hook: F) -> Self
+    where
+        F: Fn(SocketAddr) -> ConnectionAction + Send + Sync + 'static,
+    {
+        self.on_connection = Some(Arc::new(hook));
+        self
+    }
+
+    /// Builds the HyperAdapterConfig from the builder.
+    ///
+    /// # Returns
+    /// A new `HyperAdapterConfig` instance.
+    pub fn build(self) -> HyperAdapterConfig {
+        HyperAdapterConfig {
+            hostname: self.hostname,
+            interface: self.interface,
+            protocol_version: self.protocol_version,
+            port: self.port,
+            #[cfg(feature = "rust-tls")]
+            cert: self.cert,
+            #[cfg(feature = "rust-tls")]
+            key: self.key,
+            #[cfg(feature = "rust-tls")]
+            ca: self.ca,
+            #[cfg(feature = "rust-tls")]
+            client_auth: self.client_auth,
+            on_connection: self.on_connection,
+            #[cfg(unix)]
+            unix_socket: self.unix_socket,
+            drain_timeout: self.drain_timeout,
+            max_body_size: self.max_body_size,
+            oversized_body: self.oversized_body,
+            max_journal_body_size: self.max_journal_body_size,
+        }
+    }
+}
+
+/// Configuration for the hyper adapter.
+#[derive(Clone)]
+pub struct HyperAdapterConfig {
+    hostname: String,
+    interface: String,
+    protocol_version: Version,
+    port: u16,
+    #[cfg(feature = "rust-tls")]
+    cert: Option<Vec<u8>>,
+    #[cfg(feature = "rust-tls")]
+    key: Option<Vec<u8>>,
+    #[cfg(feature = "rust-tls")]
+    ca: Option<Vec<u8>>,
+    #[cfg(feature = "rust-tls")]
+    client_auth: ClientAuth,
+    on_connection: Option<ConnectionHook>,
+    #[cfg(unix)]
+    unix_socket: Option<PathBuf>,
+    drain_timeout: Duration,
+    max_body_size: Option<usize>,
+    oversized_body: OversizedBody,
+    max_journal_body_size: Option<usize>,
+}
+
+impl Default for HyperAdapterConfig {
+    /// Creates a default configuration for the hyper adapter.
+    ///
+    /// This function sets up a basic server configuration with:
+    /// - Hostname: "localhost"
+    /// - Interface: "0.0.0.0"
+    /// - Port: random port between 9000 and 65535
+    /// - No TLS certificates (HTTP only)
+    /// - Drain timeout: 5 seconds
+    /// - No limit on the size of request bodies
+    ///
+    /// # Returns
+    /// A default `HyperAdapterConfig` instance.
+    fn default() -> Self {
+        Self::builder().build()
+    }
+}
+
+impl HyperAdapterConfig {
+    /// Creates a new builder for the hyper adapter configuration.
+    ///
+    /// This function sets up a basic server configuration with:
+    /// - Hostname: "localhost"
+    /// - Interface: "0.0.0.0"
+    /// - Port: random port between 9000 and 65535
+    /// - No TLS certificates (HTTP only)
+    /// - Drain timeout: 5 seconds
+    /// - No limit on the size of request bodies
+    ///
+    /// # Returns
+    /// A new `HyperAdapterConfigBuilder` instance.
+    pub fn builder() -> HyperAdapterConfigBuilder {
+        HyperAdapterConfigBuilder {
+            hostname: "localhost".into(),
+            interface: "0.0.0.0".into(),
+            protocol_version: Version::HTTP_11,
+            port: generate_randon_port(),
+            #[cfg(feature = "rust-tls")]
+            cert: None,
+            #[cfg(feature = "rust-tls")]
+            key: None,
+            #[cfg(feature = "rust-tls")]
+            ca: None,
+            #[cfg(feature = "rust-tls")]
+            client_auth: ClientAuth::None,
+            on_connection: None,
+            #[cfg(unix)]
+            unix_socket: None,
+            drain_timeout: DEFAULT_DRAIN_TIMEOUT,
+            max_body_size: None,
+            oversized_body: OversizedBody::Reject,
+            max_journal_body_size: None,
+        }
+    }
+
+    /// Returns the hostname of the server.
+    ///
+    /// # Returns
+    /// The hostname of the server.
+    pub fn hostname(&self) -> &String {
+        &self.hostname
+    }
+
+    /// Returns the interface of the server.
+    ///
+    /// # Returns
+    /// The interface of the server.
+    pub fn interface(&self) -> &str {
+        &self.interface
+    }
+
+    /// Returns the protocol version of the server.
+    ///
+    /// # Returns
+    /// The protocol version of the server.
+    pub fn protocol_version(&self) -> Version {
+        self.protocol_version
+    }
+
+    /// Returns the port of the server, the one picked by the system once started if it was `0`.
+    ///
+    /// # Returns
+    /// The port of the server.
+    pub fn port(&self) -> u16 {
+        self.port
+    }
+
+    /// Returns the certificate of the server.
+    ///
+    /// # Returns
+    /// The certificate of the server.
+    #[cfg(feature = "rust-tls")]
+    pub fn cert(&self) -> &Option<Vec<u8>> {
+        &self.cert
+    }
+
+    /// Returns the key of the server.
+    ///
+    /// # Returns
+    /// The key of the server.
+    #[cfg(feature = "rust-tls")]
+    pub fn key(&self) -> &Option<Vec<u8>> {
+        &self.key
+    }
+
+    /// Returns the CA certificate of the server.
+    ///
+    /// # Returns
+    /// The CA certificate of the server.
+    #[cfg(feature = "rust-tls")]
+    pub fn ca(&self) -> &Option<Vec<u8>> {
+        &self.ca
+    }
+
+    /// Returns the client authentication mode of the server.
+    ///
+    /// # Returns
+    /// The client authentication mode of the server.
+    #[cfg(feature = "rust-tls")]
+    pub fn client_auth(&self) -> ClientAuth {
+        self.client_auth
+    }
+
+    /// Returns the path of the Unix domain socket the server listens on.
+    ///
+    /// # Returns
+    /// The path of the socket file, if the server listens on a Unix domain socket.
+    #[cfg(unix)]
+    pub fn unix_socket(&self) -> Option<&Path> {
+        self.unix_socket
+            .as_deref()
+    }
+
+    /// Returns the time in-flight requests have to complete when the server stops.
+    ///
+    /// # Returns
+    /// The drain timeout of the server.
+    pub fn drain_timeout(&self) -> Duration {
+        self.drain_timeout
+    }
+
+    /// Returns the maximum size of request bodies.
+    ///
+    /// # Returns
+    /// The maximum size of request bodies, if they are limited.
+    pub fn max_body_size(&self) -> Option<usize> {
+        self.max_body_size
+    }
+
+    /// Returns what the server does with requests whose body exceeds the maximum size.
+    ///
+    /// # Returns
+    /// The oversized body action of the server.
+    pub fn oversized_body(&self) -> OversizedBody {
+        self.oversized_body
+    }
+
+    /// Returns the size the bodies recorded in the journal are truncated to.
+    ///
+    /// # Returns
+    /// The maximum size of recorded bodies, if they are truncated.
+    pub fn max_journal_body_size(&self) -> Option<usize> {
+        self.max_journal_body_size
+    }
+
+    /// Returns whether the server serves HTTPS.
+    ///
+    /// # Returns
+    /// `true` if a certificate and a key are set.
+    pub fn is_tls(&self) -> bool {
+        #[cfg(feature = "rust-tls")]
+        return self.cert.is_some() && self.key.is_some();
+        #[cfg(not(feature = "rust-tls"))]
+        return false;
+    }
+}
+
+impl PortGenerator<HyperAdapter> for HyperAdapterConfigBuilder {
+    fn with_random_port(self) -> Self {
+        self.port(generate_randon_port())
+    }
+}
+
+#[derive(Default)]
+/// Hyper adapter implementation
+pub struct HyperAdapter {
+    config: HyperAdapterConfig,
+    service: MockService,
+    connection_events: ConnectionEvents,
+    shutdown: Option<oneshot::Sender<()>>,
+    task: Option<JoinHandle<()>>,
+    #[cfg(unix)]
+    socket_file: Option<SocketFile>,
+}
+
+impl HyperAdapter {
+    /// Returns the log of the connection events of the server.
+    ///
+    /// # Returns
+    /// The connection events of the server.
+    pub fn connection_events(&self) -> &ConnectionEvents {
+        &self.connection_events
+    }
+
+    /// Binds the listener of the server, updating the port if the system picked it.
+    ///
+    /// # Returns
+    /// The listener, on the Unix domain socket if one is configured, on the interface and the port
+    /// otherwise.
+    async fn bind(&mut self) -> io::Result<Listener> {
+        #[cfg(unix)]
+        if let Some(path) = self
+            .config
+            .unix_socket()
+        {
+            let listener = UnixListener::bind(path)?;
+            self.socket_file = Some(SocketFile(path.to_path_buf()));
+            return Ok(Listener::Unix(listener));
+        }
+
+        let listener = TcpListener::bind((
+            self.config
+                .interface(),
+            self.config.port(),
+        ))
+        .await?;
+        self.config.port = listener
+            .local_addr()?
+            .port();
+
+        Ok(Listener::Tcp(listener))
+    }
+}
+
+impl ServerAdapter for HyperAdapter {
+    /// The configuration type for the adapter.
+    type Config = HyperAdapterConfig;
+
+    /// Creates a new HyperAdapter instance.
+    ///
+    /// # Arguments
+    /// * `config` - The configuration for the adapter.
+    ///
+    /// # Returns
+    /// A new `HyperAdapter` instance.
+    fn new(config: Self::Config) -> Result<Self, EasyHttpMockError> {
+        let service = MockService::new(Journal::with_body_limit(config.max_journal_body_size()))
+            .with_body_limit(config.max_body_size(), config.oversized_body());
+
+        Ok(Self { config, service, ..Default::default() })
+    }
+
+    /// Returns the hostname of the server.
+    ///
+    /// # Returns
+    /// The hostname of the server.
+    fn hostname(&self) -> String {
+        self.config
+            .hostname()
+            .clone()
+    }
+
+    /// Returns the base URL of the server.
+    ///
+    /// Servers listening on a Unix domain socket use the `http+unix` scheme, with the percent
+    /// encoded socket path as host, like Docker clients do.
+    ///
+    /// # Returns
+    /// The base URL of the server.
+    fn base_url(&self) -> String {
+        let scheme = if self.config.is_tls() { "https" } else { "http" };
+
+        #[cfg(unix)]
+        if let Some(path) = self
+            .config
+            .unix_socket()
+        {
+            return format!("{}+unix://{}", scheme, encode_socket_path(path));
+        }
+
+        format!("{}://{}:{}", scheme, self.hostname(), self.config.port())
+    }
+
+    /// Returns the configuration of the server.
+    ///
+    /// # Returns
+    /// The configuration of the server.
+    fn config(&self) -> &Self::Config {
+        &self.config
+    }
+
+    /// Returns a mutable reference to the configuration of the server.
+    ///
+    /// # Returns
+    /// A mutable reference to the configuration of the server.
+    fn config_mut(&mut self) -> &mut Self::Config {
+        &mut self.config
+    }
+
+    /// Returns the journal of the requests received by the server.
+    ///
+    /// # Returns
+    /// The journal of the server.
+    fn journal(&self) -> &Journal {
+        self.service
+            .journal()
+    }
+
+    /// Sets the mock to handle incoming requests.
+    ///
+    /// # Arguments
+    /// * `mock` - The mock to handle incoming requests.
+    fn register_mock(&mut self, mock: Arc<Mock>) {
+        self.service
+            .register_mock(mock);
+    }
+
+    /// Binds the listener and starts accepting connections in the background.
+    ///
+    /// The listener is bound before this returns, so connections succeed as soon as it resolves.
+    ///
+    /// # Returns
+    /// A result indicating whether the server started successfully or a `EasyHttpMockError` if it
+    /// failed.
+    async fn start(&mut self) -> HttpMockResult<()> {
+        if !self
+            .service
+            .has_mock()
+        {
+            return Err(MockError::Notfound.into());
+        }
+
+        let http2 = match self
+            .config
+            .protocol_version()
+        {
+            Version::HTTP_11 | Version::HTTP_10 => false,
+            Version::HTTP_2 => true,
+            version => {
+                return Err(EasyHttpMockError::Server(ServerError::Config(format!(
+                    "Unsupported protocol version {:?}",
+                    version
+                ))));
+            }
+        };
+
+        let context = Arc::new(ConnectionContext {
+            service: self.service.clone(),
+            events: self
+                .connection_events
+                .clone(),
+            on_connection: self
+                .config
+                .on_connection
+                .clone(),
+            http2,
+            #[cfg(feature = "rust-tls")]
+            acceptor: crate::tls_acceptor::acceptor(&self.config)?,
+        });
+
+        let listener = self
+            .bind()
+            .await
+            .map_err(|e| EasyHttpMockError::Server(ServerError::Start(e.to_string())))?;
+
+        let drain_timeout = self
+            .config
+            .drain_timeout();
+        let (shutdown, mut signal) = oneshot::channel();
+        let task = tokio::spawn(async move {
+            let graceful = GracefulShutdown::new();
+            let mut connections = JoinSet::new();
+            loop {
+                tokio::select! {
+                    _ = &mut signal => break,
+                    accepted = listener.accept() => match accepted {
+                        Ok(Accepted::Tcp(stream, peer)) => {
+                            connections.spawn(serve_connection(
+                                context.clone(),
+                                stream,
+                                peer,
+                                graceful.watcher(),
+                            ));
+                        }
+                        #[cfg(unix)]
+                        Ok(Accepted::Unix(stream)) => {
+                            connections.spawn(serve_connection(
+                                context.clone(),
+                                stream,
+                                UNIX_PEER,
+                                graceful.watcher(),
+                            ));
+                        }
+                        Err(_) => {}
+                    }
+                }
+            }
+
+            // Stop accepting, let the requests being handled complete, then abort the
+            // connections still open by dropping the set
+            drop(listener);
+            let _ = tokio::time::timeout(drain_timeout, graceful.shutdown()).await;
+        });
+
+        self.shutdown = Some(shutdown);
+        self.task = Some(task);
+
+        Ok(())
+    }
+
+    /// Stops the server, closing the listener and every open connection once the requests being
+    /// handled completed or the drain timeout elapsed.
+    ///
+    /// The socket file of a server listening on a Unix domain socket is removed.
+    ///
+    /// # Returns
+    /// A result indicating whether the server stopped successfully.
+    async fn stop(&mut self) -> HttpMockResult<()> {
+        if let Some(shutdown) = self.shutdown.take() {
+            let _ = shutdown.send(());
+        }
+
+        let stopped = match self.task.take() {
+            Some(task) => task
+                .await
+                .map_err(|e| EasyHttpMockError::Server(ServerError::Stop(e.to_string()))),
+            None => Ok(()),
+        };
+
+        #[cfg(unix)]
+        self.socket_file
+            .take();
+
+        stopped
+    }
+
+    /// Signals the server to stop without waiting for it, when the mock is dropped without being
+    /// stopped.
+    fn shutdown(&mut self) {
+        if let Some(shutdown) = self.shutdown.take() {
+            let _ = shutdown.send(());
+        }
+
+        // The accept loop drains the connections in the background
+        self.task.take();
+
+        #[cfg(unix)]
+        self.socket_file
+            .take();
+    }
+}
+
+/// Address reported to the connection hook and events for connections on a Unix domain socket,
+/// which have no peer address.
+#[cfg(unix)]
+const UNIX_PEER: SocketAddr =
+    SocketAddr::V4(std::net::SocketAddrV4::new(std::net::Ipv4Addr::UNSPECIFIED, 0));
+
+/// Listener of a server.
+enum Listener {
+    Tcp(TcpListener),
+    #[cfg(unix)]
+    Unix(UnixListener),
+}
+
+/// Connection accepted by a listener.
+enum Accepted {
+    Tcp(TcpStream, SocketAddr),
+    #[cfg(unix)]
+    Unix(tokio::net::UnixStream),
+}
+
+impl Listener {
+    /// Accepts the next connection.
+    ///
+    /// # Returns
+    /// The accepted connection.
+    async fn accept(&self) -> io::Result<Accepted> {
+        match self {
+            Listener::Tcp(listener) => listener
+                .accept()
+                .await
+                .map(|(stream, peer)| Accepted::Tcp(stream, peer)),
+            #[cfg(unix)]
+            Listener::Unix(listener) => listener
+                .accept()
+                .await
+                .map(|(stream, _)| Accepted::Unix(stream)),
+        }
+    }
+}
+
+/// Socket file of a server listening on a Unix domain socket, removed when dropped.
+#[cfg(unix)]
+struct SocketFile(PathBuf);
+
+#[cfg(unix)]
+impl Drop for SocketFile {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_file(&self.0);
+    }
+}
+
+/// Percent encodes the path of a Unix domain socket, so it can be used as the host of a URL.
+///
+/// # Arguments
+/// * `path` - The path of the socket file.
+///
+/// # Returns
+/// The encoded path.
+#[cfg(unix)]
+fn encode_socket_path(path: &Path) -> String {
+    path.to_string_lossy()
+        .bytes()
+        .map(|byte| match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'.' | b'_' | b'~' => {
+                (byte as char).to_string()
+            }
+            _ => format!("%{:02X}", byte),
+        })
+        .collect()
+}
+
+/// State shared by the connections of a server.
+struct ConnectionContext {
+    service: MockService,
+    events: ConnectionEvents,
+    on_connection: Option<ConnectionHook>,
+    http2: bool,
+    #[cfg(feature = "rust-tls")]
+    acceptor: Option<tokio_rustls::TlsAcceptor>,
+}
+
+/// Serves an accepted connection, applying the action of the connection hook first.
+///
+/// # Arguments
+/// * `context` - The state shared by the connections of the server.
+/// * `stream` - The accepted connection.
+/// * `peer` - The address of the peer.
+/// * `watcher` - The watcher shutting the connection down gracefully when the server stops.
+async fn serve_connection<S>(
+    context: Arc<ConnectionContext>,
+    mut stream: S,
+    peer: SocketAddr,
+    watcher: Watcher,
+) where
+    S: AsyncRead + AsyncWrite + Unpin + Send + 'static,
+{
+    context
+        .events
+        .record(ConnectionEvent::Accepted(peer));
+
+    let action = context
+        .on_connection
+        .as_ref()
+        .map_or(ConnectionAction::Serve, |hook| hook(peer));
+
+    match action {
+        ConnectionAction::Serve => serve_stream(context.clone(), stream, watcher).await,
+        ConnectionAction::Close => drop(stream),
+        ConnectionAction::Delay(delay) => {
+            tokio::time::sleep(delay).await;
+            serve_stream(context.clone(), stream, watcher).await;
+        }
+        ConnectionAction::Raw(bytes) => {
+            let _ = stream
+                .write_all(&bytes)
+                .await;
+            let _ = stream
+                .shutdown()
+                .await;
+        }
+    }
+
+    context
+        .events
+        .record(ConnectionEvent::Closed(peer));
+}
+
+/// Serves HTTP on a connection, after the TLS handshake if the server serves HTTPS.
+///
+/// # Arguments
+/// * `context` - The state shared by the connections of the server.
+/// * `stream` - The accepted connection.
+/// * `watcher` - The watcher shutting the connection down gracefully when the server stops.
+async fn serve_stream<S>(context: Arc<ConnectionContext>, stream: S, watcher: Watcher)
+where
+    S: AsyncRead + AsyncWrite + Unpin + Send + 'static,
+{
+    #[cfg(feature = "rust-tls")]
+    if let Some(acceptor) = &context.acceptor {
+        let Ok(stream) = acceptor
+            .accept(stream)
+            .await
+        else {
+            return;
+        };
+
+        let certificate = stream
+            .get_ref()
+            .1
+            .peer_certificates()
+            .and_then(|certificates| certificates.first())
+            .map(|certificate| ClientCertificate::new(certificate.to_vec()));
+
+        return serve_http(context.clone(), TokioIo::new(stream), certificate, watcher).await;
+    }
+
+    serve_http(context, TokioIo::new(stream), None, watcher).await
+}
+
+/// Serves HTTP requests on a connection until it is closed.
+///
+/// # Arguments
+/// * `context` - The state shared by the connections of the server.
+/// * `io` - The connection.
+/// * `certificate` - The certificate presented by the client, recorded on every request.
+/// * `watcher` - The watcher shutting the connection down gracefully when the server stops.
+async fn serve_http<I>(
+    context: Arc<ConnectionContext>,
+    io: I,
+    certificate: Option<ClientCertificate>,
+    watcher: Watcher,
+) where
+    I: hyper::rt::Read + hyper::rt::Write + Unpin + Send + 'static,
+{
+    let service = context
+        .service
+        .clone()
+        .with_client_certificate(certificate);
+
+    let builder = auto::Builder::new(TokioExecutor::new());
+    let builder = if context.http2 { builder.http2_only() } else { builder.http1_only() };
+
+    let _ = watcher
+        .watch(builder.serve_connection(io, service))
+        .await;
+}
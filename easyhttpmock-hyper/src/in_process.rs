@@ -0,0 +1,273 @@
+use crate::service::MockService;
+use easyhttpmock::{
+    errors::{EasyHttpMockError, MockError},
+    journal::Journal,
+    mock::Mock,
+    server::ServerAdapter,
+    HttpMockResult,
+};
+use http::Uri;
+use hyper_util::{
+    client::legacy::connect::{Connected, Connection},
+    rt::{TokioExecutor, TokioIo},
+    server::conn::auto,
+};
+use std::{
+    future::{ready, Ready},
+    io,
+    pin::Pin,
+    sync::Arc,
+    task::{Context, Poll},
+};
+use tokio::io::DuplexStream;
+
+/// Size of the in-memory buffer of each direction of a connection.
+const BUFFER_SIZE: usize = 64 * 1024;
+
+/// Builder for InProcessAdapterConfig
+pub struct InProcessAdapterConfigBuilder {
+    hostname: String,
+}
+
+impl InProcessAdapterConfigBuilder {
+    /// Sets the hostname used in the URLs of the server.
+    ///
+    /// # Arguments
+    /// * `hostname` - The hostname to set.
+    ///
+    /// # Returns
+    /// A new `InProcessAdapterConfigBuilder` instance with the hostname set.
+    pub fn hostname(mut self, hostname: &str) -> Self {
+        self.hostname = hostname.to_string();
+        self
+    }
+
+    /// Builds the InProcessAdapterConfig instance.
+    ///
+    /// # Returns
+    /// A new `InProcessAdapterConfig` instance.
+    pub fn build(self) -> InProcessAdapterConfig {
+        InProcessAdapterConfig { hostname: self.hostname }
+    }
+}
+
+/// In-process adapter configuration
+#[derive(Clone)]
+pub struct InProcessAdapterConfig {
+    hostname: String,
+}
+
+impl Default for InProcessAdapterConfig {
+    fn default() -> Self {
+        Self::builder().build()
+    }
+}
+
+impl InProcessAdapterConfig {
+    /// Creates a new InProcessAdapterConfigBuilder instance.
+    ///
+    /// # Returns
+    /// A new `InProcessAdapterConfigBuilder` instance.
+    pub fn builder() -> InProcessAdapterConfigBuilder {
+        InProcessAdapterConfigBuilder { hostname: "localhost".to_string() }
+    }
+
+    /// Returns the hostname used in the URLs of the server.
+    ///
+    /// # Returns
+    /// The hostname of the server.
+    pub fn hostname(&self) -> &String {
+        &self.hostname
+    }
+}
+
+#[derive(Default)]
+/// Adapter answering requests in memory, without binding any socket
+///
+/// Requests go through the same mock and matchers as the socket adapters, either by calling
+/// `service` directly or by plugging `connector` into a hyper client.
+pub struct InProcessAdapter {
+    config: InProcessAdapterConfig,
+    service: MockService,
+}
+
+impl InProcessAdapter {
+    /// Returns the service answering the requests with the mock.
+    ///
+    /// # Returns
+    /// The service, sharing the mock and the journal of the server.
+    pub fn service(&self) -> MockService {
+        self.service.clone()
+    }
+
+    /// Returns a connector opening in-memory connections to the server.
+    ///
+    /// # Returns
+    /// The connector, to use with `hyper_util::client::legacy::Client::builder`.
+    pub fn connector(&self) -> InProcessConnector {
+        InProcessConnector { service: self.service() }
+    }
+}
+
+impl ServerAdapter for InProcessAdapter {
+    /// The configuration type for the adapter.
+    type Config = InProcessAdapterConfig;
+
+    /// Creates a new InProcessAdapter instance.
+    ///
+    /// # Arguments
+    /// * `config` - The configuration for the adapter.
+    ///
+    /// # Returns
+    /// A new `InProcessAdapter` instance.
+    fn new(config: Self::Config) -> Result<Self, EasyHttpMockError> {
+        Ok(Self { config, ..Default::default() })
+    }
+
+    /// Returns the hostname of the server.
+    ///
+    /// # Returns
+    /// The hostname of the server.
+    fn hostname(&self) -> String {
+        self.config
+            .hostname()
+            .clone()
+    }
+
+    /// Returns the base URL of the server, only meaningful to the in-process connector.
+    ///
+    /// # Returns
+    /// The base URL of the server.
+    fn base_url(&self) -> String {
+        format!("http://{}", self.hostname())
+    }
+
+    /// Returns the configuration of the server.
+    ///
+    /// # Returns
+    /// The configuration of the server.
+    fn config(&self) -> &Self::Config {
+        &self.config
+    }
+
+    /// Returns a mutable reference to the configuration of the server.
+    ///
+    /// # Returns
+    /// A mutable reference to the configuration of the server.
+    fn config_mut(&mut self) -> &mut Self::Config {
+        &mut self.config
+    }
+
+    /// Returns the journal of the requests received by the server.
+    ///
+    /// # Returns
+    /// The journal of the server.
+    fn journal(&self) -> &Journal {
+        self.service
+            .journal()
+    }
+
+    /// Sets the mock to handle incoming requests.
+    ///
+    /// # Arguments
+    /// * `mock` - The mock to handle incoming requests.
+    fn register_mock(&mut self, mock: Arc<Mock>) {
+        self.service
+            .register_mock(mock);
+    }
+
+    /// Starts the server, there is nothing to bind so it only checks a mock is registered.
+    ///
+    /// # Returns
+    /// A result indicating whether the server started successfully or a `EasyHttpMockError` if it
+    /// failed.
+    async fn start(&mut self) -> HttpMockResult<()> {
+        if !self
+            .service
+            .has_mock()
+        {
+            return Err(MockError::Notfound.into());
+        }
+
+        Ok(())
+    }
+
+    /// Stops the server, connections already open keep being served until the client drops them.
+    ///
+    /// # Returns
+    /// A result indicating whether the server stopped successfully.
+    async fn stop(&mut self) -> HttpMockResult<()> {
+        Ok(())
+    }
+}
+
+/// Connector opening in-memory connections to an `InProcessAdapter`
+///
+/// Every connection is served by hyper on a background task, with HTTP/1.1 or HTTP/2 depending on
+/// what the client speaks, so the requests go through the whole HTTP stack without any socket.
+#[derive(Clone)]
+pub struct InProcessConnector {
+    service: MockService,
+}
+
+impl tower_service::Service<Uri> for InProcessConnector {
+    type Response = InProcessStream;
+    type Error = io::Error;
+    type Future = Ready<Result<InProcessStream, io::Error>>;
+
+    fn poll_ready(&mut self, _cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn call(&mut self, _uri: Uri) -> Self::Future {
+        let (client, server) = tokio::io::duplex(BUFFER_SIZE);
+        let service = self.service.clone();
+
+        tokio::spawn(async move {
+            let _ = auto::Builder::new(TokioExecutor::new())
+                .serve_connection(TokioIo::new(server), service)
+                .await;
+        });
+
+        ready(Ok(InProcessStream { io: TokioIo::new(client) }))
+    }
+}
+
+/// Client side of an in-memory connection opened by `InProcessConnector`
+pub struct InProcessStream {
+    io: TokioIo<DuplexStream>,
+}
+
+impl hyper::rt::Read for InProcessStream {
+    fn poll_read(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: hyper::rt::ReadBufCursor<'_>,
+    ) -> Poll<io::Result<()>> {
+        hyper::rt::Read::poll_read(Pin::new(&mut self.io), cx, buf)
+    }
+}
+
+impl hyper::rt::Write for InProcessStream {
+    fn poll_write(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &[u8],
+    ) -> Poll<io::Result<usize>> {
+        hyper::rt::Write::poll_write(Pin::new(&mut self.io), cx, buf)
+    }
+
+    fn poll_flush(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        hyper::rt::Write::poll_flush(Pin::new(&mut self.io), cx)
+    }
+
+    fn poll_shutdown(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        hyper::rt::Write::poll_shutdown(Pin::new(&mut self.io), cx)
+    }
+}
+
+impl Connection for InProcessStream {
+    fn connected(&self) -> Connected {
+        Connected::new()
+    }
+}
@@ -0,0 +1,20 @@
+#![doc = include_str!("../README.md")]
+#![deny(missing_docs)]
+/// Blocking facade module
+pub mod blocking;
+/// Connection events and fault injection module
+pub mod connection;
+/// Hyper adapter module
+pub mod hyper_adapter;
+/// In-process adapter module
+#[cfg(feature = "in-process")]
+pub mod in_process;
+/// Shared mock server pool module
+pub mod pool;
+/// Transport independent mock service module
+pub mod service;
+#[cfg(feature = "rust-tls")]
+mod tls_acceptor;
+pub use easyhttpmock::*;
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,108 @@
+use crate::{
+    blocking::BlockingEasyHttpMock,
+    hyper_adapter::{HyperAdapter, HyperAdapterConfig},
+};
+use easyhttpmock::{
+    config::EasyHttpMockConfig,
+    errors::{EasyHttpMockError, ServerError},
+    namespace::{NamespaceHandle, Namespaces},
+    server::PortGenerator,
+    HttpMockResult,
+};
+use std::{
+    sync::{Mutex, PoisonError},
+    thread,
+};
+
+/// Server shared by every test of the process.
+struct SharedServer {
+    server: BlockingEasyHttpMock<HyperAdapter>,
+    namespaces: Namespaces,
+}
+
+/// Shared server, started by the first test asking for a namespace.
+static SHARED: Mutex<Option<SharedServer>> = Mutex::new(None);
+
+/// Returns a new namespace on the mock server shared by the tests of the process
+///
+/// The server is started on a random port, taken from the same bookkeeping as the per-test
+/// servers, the first time a namespace is requested and lives until the process exits. Each
+/// namespace has its own path prefix, mock and journal, and is removed from the server when its
+/// handle is dropped. It can be called from synchronous and asynchronous tests.
+///
+/// The shared server always runs on the hyper adapter, whatever the adapter of the other tests,
+/// since it is served by the blocking facade of this crate.
+///
+/// # Examples
+///
+/// ```rust,no_run
+/// use easyhttpmock_hyper::{
+///     matchers::path,
+///     mock::{given, Mock, StatusCodeExt},
+///     pool,
+/// };
+/// use http::StatusCode;
+///
+/// let namespace = pool::namespace().unwrap();
+/// namespace.register_mock(Mock::of(
+///     given(path("/test")).will_return(StatusCode::OK.respond().empty()),
+/// ));
+///
+/// // ... call namespace.url("/test") with any client ...
+///
+/// namespace.journal().verify().unwrap();
+/// ```
+///
+/// # Returns
+/// The handle of the namespace, or an error if the shared server could not be started.
+pub fn namespace() -> HttpMockResult<NamespaceHandle> {
+    let mut shared = SHARED
+        .lock()
+        .unwrap_or_else(PoisonError::into_inner);
+
+    let server = match shared.take() {
+        Some(server) => server,
+        None => start()?,
+    };
+    let shared = shared.insert(server);
+
+    Ok(shared
+        .namespaces
+        .create(
+            shared
+                .server
+                .base_url(),
+        ))
+}
+
+/// Starts the shared server with the mock routing requests to the namespaces.
+///
+/// The blocking facade is created on its own thread, so it does not block a runtime the caller
+/// may be running on.
+///
+/// # Returns
+/// The shared server, or an error if it could not be started.
+fn start() -> HttpMockResult<SharedServer> {
+    thread::spawn(|| {
+        let namespaces = Namespaces::new();
+        let config = EasyHttpMockConfig::<HyperAdapter>::builder()
+            .server_config(
+                HyperAdapterConfig::builder()
+                    .interface("127.0.0.1")
+                    .with_random_port()
+                    .build(),
+            )
+            .build();
+
+        let mut server = BlockingEasyHttpMock::new(config)?;
+        server.register(namespaces.mock())?;
+
+        Ok(SharedServer { server, namespaces })
+    })
+    .join()
+    .map_err(|_| {
+        EasyHttpMockError::Server(ServerError::Creation(
+            "Shared mock server thread panicked".to_string(),
+        ))
+    })?
+}
@@ -0,0 +1,292 @@
+use bytes::Bytes;
+use easyhttpmock::{
+    errors::{MockError, RequestError},
+    journal::Journal,
+    mock::{Mock, Request, Respond},
+    server::{collect_body, handle_request, payload_too_large, OversizedBody},
+    tls::ClientCertificate,
+};
+use http::{HeaderName, HeaderValue, StatusCode};
+use http_body_util::Full;
+use hyper::body::Body;
+use std::{
+    future::Future,
+    pin::Pin,
+    sync::{Arc, RwLock},
+};
+
+/// Future returned by [`MockService`]
+///
+/// It fails only when the body of the request exceeds the maximum size and the service resets
+/// the connection of such requests.
+pub type MockResponseFuture =
+    Pin<Box<dyn Future<Output = Result<http::Response<Full<Bytes>>, RequestError>> + Send>>;
+
+/// Service answering requests with a mock, independently of the transport
+///
+/// It implements both the hyper and the tower `Service` traits, so it can be served on a
+/// connection or called directly. Cloning the service is cheap, every clone shares the mock and
+/// the journal.
+///
+/// # Examples
+///
+/// ```rust
+/// use easyhttpmock_hyper::{journal::Journal, service::MockService};
+///
+/// let service = MockService::new(Journal::new());
+/// ```
+#[derive(Clone, Default)]
+pub struct MockService {
+    mock: Arc<RwLock<Option<Arc<Mock>>>>,
+    journal: Journal,
+    certificate: Option<ClientCertificate>,
+    max_body_size: Option<usize>,
+    oversized_body: OversizedBody,
+}
+
+impl MockService {
+    /// Creates a service recording the requests it receives in the given journal
+    ///
+    /// # Arguments
+    ///
+    /// * `journal` - The journal of the requests
+    pub fn new(journal: Journal) -> Self {
+        Self { journal, ..Default::default() }
+    }
+
+    /// Sets the mock answering the requests, for this service and all its clones
+    ///
+    /// # Arguments
+    ///
+    /// * `mock` - The mock answering the requests
+    pub fn register_mock(&self, mock: Arc<Mock>) {
+        if let Ok(mut current) = self.mock.write() {
+            *current = Some(mock);
+        }
+    }
+
+    /// Returns whether a mock was registered
+    pub fn has_mock(&self) -> bool {
+        self.mock
+            .read()
+            .map(|mock| mock.is_some())
+            .unwrap_or_default()
+    }
+
+    /// Records the given client certificate on every request handled by the service
+    ///
+    /// # Arguments
+    ///
+    /// * `certificate` - The certificate presented by the client, if any
+    ///
+    /// # Returns
+    ///
+    /// * `MockService` - The service with the client certificate set
+    pub fn with_client_certificate(mut self, certificate: Option<ClientCertificate>) -> Self {
+        self.certificate = certificate;
+        self
+    }
+
+    /// Limits the size of the bodies of the requests handled by the service
+    ///
+    /// # Arguments
+    ///
+    /// * `max_body_size` - The maximum size of request bodies, `None` for no limit
+    /// * `oversized_body` - Whether larger requests are answered `413 Payload Too Large` or have
+    ///   their connection reset
+    ///
+    /// # Returns
+    ///
+    /// * `MockService` - The service with the body limit set
+    pub fn with_body_limit(
+        mut self,
+        max_body_size: Option<usize>,
+        oversized_body: OversizedBody,
+    ) -> Self {
+        self.max_body_size = max_body_size;
+        self.oversized_body = oversized_body;
+        self
+    }
+
+    #[inline]
+    /// Get the journal of the requests handled by the service
+    pub fn journal(&self) -> &Journal {
+        &self.journal
+    }
+
+    /// Answers a request with the registered mock
+    ///
+    /// # Arguments
+    ///
+    /// * `request` - The request to answer
+    ///
+    /// # Returns
+    ///
+    /// * `http::Response<Full<Bytes>>` - The mocked response, or an error response if the body
+    ///   could not be read, exceeds the maximum size or no respond is available
+    pub async fn respond<B>(&self, request: http::Request<B>) -> http::Response<Full<Bytes>>
+    where
+        B: Body,
+        B::Error: Into<Box<dyn std::error::Error + Send + Sync>>,
+    {
+        match self
+            .serve(request)
+            .await
+        {
+            Ok(response) => response,
+            Err(e) => error_response(StatusCode::PAYLOAD_TOO_LARGE, e.to_string()),
+        }
+    }
+
+    /// Answers a request with the registered mock, failing when its connection must be reset
+    ///
+    /// # Arguments
+    ///
+    /// * `request` - The request to answer
+    ///
+    /// # Returns
+    ///
+    /// * `Result<http::Response<Full<Bytes>>, RequestError>` - The response, or
+    ///   `RequestError::BodyTooLarge` when the body exceeds the maximum size and the connection
+    ///   must be reset
+    async fn serve<B>(
+        &self,
+        request: http::Request<B>,
+    ) -> Result<http::Response<Full<Bytes>>, RequestError>
+    where
+        B: Body,
+        B::Error: Into<Box<dyn std::error::Error + Send + Sync>>,
+    {
+        let (mut parts, body) = request.into_parts();
+
+        if let Some(certificate) = &self.certificate {
+            parts
+                .extensions
+                .insert(certificate.clone());
+        }
+
+        // Encoded bodies are limited once decoded too, small bodies may expand to huge ones
+        let request = match collect_body(body, self.max_body_size)
+            .await
+            .and_then(|body| Request::from_parts(parts).with_limited_body(body, self.max_body_size))
+        {
+            Ok(request) => request,
+            Err(RequestError::BodyTooLarge(limit)) => {
+                return match self.oversized_body {
+                    OversizedBody::Reject => Ok(into_response(&payload_too_large(limit))),
+                    OversizedBody::Reset => Err(RequestError::BodyTooLarge(limit)),
+                };
+            }
+            Err(_) => {
+                return Ok(error_response(
+                    StatusCode::BAD_REQUEST,
+                    "Failed to collect body".to_string(),
+                ));
+            }
+        };
+
+        let mock = self
+            .mock
+            .read()
+            .ok()
+            .and_then(|mock| mock.clone());
+        let Some(mock) = mock else {
+            return Ok(error_response(
+                StatusCode::INTERNAL_SERVER_ERROR,
+                MockError::Notfound.to_string(),
+            ));
+        };
+
+        Ok(match handle_request(&mock, &self.journal, request) {
+            Some(respond) => into_response(&respond),
+            None => error_response(
+                StatusCode::INTERNAL_SERVER_ERROR,
+                "Missing respond mock".to_string(),
+            ),
+        })
+    }
+}
+
+impl<B> hyper::service::Service<http::Request<B>> for MockService
+where
+    B: Body + Send + 'static,
+    B::Data: Send,
+    B::Error: Into<Box<dyn std::error::Error + Send + Sync>>,
+{
+    type Response = http::Response<Full<Bytes>>;
+    type Error = RequestError;
+    type Future = MockResponseFuture;
+
+    fn call(&self, request: http::Request<B>) -> Self::Future {
+        let service = self.clone();
+        Box::pin(async move {
+            service
+                .serve(request)
+                .await
+        })
+    }
+}
+
+#[cfg(feature = "in-process")]
+impl<B> tower_service::Service<http::Request<B>> for MockService
+where
+    B: Body + Send + 'static,
+    B::Data: Send,
+    B::Error: Into<Box<dyn std::error::Error + Send + Sync>>,
+{
+    type Response = http::Response<Full<Bytes>>;
+    type Error = RequestError;
+    type Future = MockResponseFuture;
+
+    fn poll_ready(
+        &mut self,
+        _cx: &mut std::task::Context<'_>,
+    ) -> std::task::Poll<Result<(), Self::Error>> {
+        std::task::Poll::Ready(Ok(()))
+    }
+
+    fn call(&mut self, request: http::Request<B>) -> Self::Future {
+        hyper::service::Service::call(self, request)
+    }
+}
+
+/// Builds the response sent to the client from a mocked respond
+///
+/// # Arguments
+///
+/// * `respond` - The mocked respond
+///
+/// # Returns
+///
+/// * `http::Response<Full<Bytes>>` - The response with the status, headers and body of the respond
+fn into_response(respond: &Respond) -> http::Response<Full<Bytes>> {
+    let mut response = http::Response::new(Full::new(respond.body()));
+    *response.status_mut() = respond.status_code();
+
+    for (name, value) in respond
+        .headers()
+        .iter()
+    {
+        if let (Ok(name), Ok(value)) =
+            (HeaderName::from_bytes(name.as_bytes()), HeaderValue::from_str(value))
+        {
+            response
+                .headers_mut()
+                .append(name, value);
+        }
+    }
+
+    response
+}
+
+/// Builds the response sent to the client when the request could not be handled
+///
+/// # Arguments
+///
+/// * `status` - The status of the response
+/// * `message` - The body of the response
+fn error_response(status: StatusCode, message: String) -> http::Response<Full<Bytes>> {
+    let mut response = http::Response::new(Full::new(Bytes::from(message)));
+    *response.status_mut() = status;
+    response
+}
@@ -0,0 +1,105 @@
+use crate::{
+    blocking::BlockingEasyHttpMock,
+    hyper_adapter::{HyperAdapter, HyperAdapterConfig},
+};
+use easyhttpmock::{
+    config::EasyHttpMockConfig,
+    matchers::path,
+    mock::{given, Mock, StatusCodeExt},
+};
+use http::StatusCode;
+use std::{
+    error::Error,
+    io::{Read, Write},
+    net::TcpStream,
+};
+
+fn start() -> Result<BlockingEasyHttpMock, Box<dyn Error>> {
+    let config = EasyHttpMockConfig::<HyperAdapter>::builder()
+        .server_config(
+            HyperAdapterConfig::builder()
+                .port(0)
+                .build(),
+        )
+        .build();
+
+    let mut server = BlockingEasyHttpMock::new(config)?;
+    server.register(Mock::of(
+        given(path("/test")).will_return(
+            StatusCode::OK
+                .respond()
+                .with_body(b"teste"),
+        ),
+    ))?;
+
+    Ok(server)
+}
+
+fn address(server: &BlockingEasyHttpMock) -> String {
+    server
+        .base_url()
+        .trim_start_matches("http://")
+        .to_string()
+}
+
+fn get(address: &str, path: &str) -> Result<String, Box<dyn Error>> {
+    let mut stream = TcpStream::connect(address)?;
+    write!(stream, "GET {} HTTP/1.1\r\nhost: localhost\r\nconnection: close\r\n\r\n", path)?;
+
+    let mut response = String::new();
+    stream.read_to_string(&mut response)?;
+
+    Ok(response)
+}
+
+#[test]
+fn test_blocking_mock_request() -> Result<(), Box<dyn Error>> {
+    let mut server = start()?;
+    assert!(!server
+        .base_url()
+        .ends_with(":0"));
+
+    let response = get(&address(&server), "/test")?;
+    assert!(response.starts_with("HTTP/1.1 200 OK"));
+    assert!(response.ends_with("teste"));
+
+    server.verify()?;
+    assert_eq!(
+        server
+            .journal()
+            .len(),
+        1
+    );
+
+    server.stop()?;
+    assert!(server
+        .stop()
+        .is_err());
+
+    Ok(())
+}
+
+#[test]
+fn test_blocking_verify_reports_unmatched_request() -> Result<(), Box<dyn Error>> {
+    let server = start()?;
+
+    let _ = get(&address(&server), "/other");
+
+    assert!(server
+        .verify()
+        .is_err());
+
+    Ok(())
+}
+
+#[test]
+fn test_blocking_server_stops_on_drop() -> Result<(), Box<dyn Error>> {
+    let server = start()?;
+    let address = address(&server);
+
+    drop(server);
+
+    assert!(TcpStream::connect(address).is_err());
+
+    Ok(())
+}
@@ -0,0 +1,175 @@
+use crate::{
+    hyper_adapter::{HyperAdapter, HyperAdapterConfig},
+    in_process::InProcessAdapter,
+};
+use bytes::Bytes;
+use easyhttpmock::{
+    config::EasyHttpMockConfig,
+    matchers::{method, path, version},
+    mock::{given, AsyncMatcherExt, Mock, StatusCodeExt},
+    server::{PortGenerator, ServerAdapter},
+    EasyHttpMock,
+};
+use http::StatusCode;
+use http_body_util::{BodyExt, Empty};
+use hyper_util::{client::legacy::Client, rt::TokioExecutor};
+use std::error::Error;
+
+async fn register_mocks<S: ServerAdapter>(
+    server: &mut EasyHttpMock<S>,
+) -> Result<(), Box<dyn Error>> {
+    Mock::of(
+        given(path("/test").and(method("GET"))).will_return(
+            StatusCode::OK
+                .respond()
+                .with_header("x-mock", "hyper")
+                .with_body(b"teste"),
+        ),
+    )
+    .use_on(server)
+    .await?;
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_same_mocks_over_loopback_and_in_process() -> Result<(), Box<dyn Error>> {
+    let config = EasyHttpMockConfig::<HyperAdapter>::builder()
+        .server_config(
+            HyperAdapterConfig::builder()
+                .with_random_port()
+                .build(),
+        )
+        .build();
+    let mut loopback = EasyHttpMock::new(config)?;
+    register_mocks(&mut loopback).await?;
+
+    let mut in_process = EasyHttpMock::new(EasyHttpMockConfig::<InProcessAdapter>::default())?;
+    register_mocks(&mut in_process).await?;
+
+    let loopback_client = Client::builder(TokioExecutor::new()).build_http::<Empty<Bytes>>();
+    let in_process_client =
+        Client::builder(TokioExecutor::new()).build::<_, Empty<Bytes>>(in_process.connector());
+
+    let responses = [
+        loopback_client
+            .get(
+                loopback
+                    .url("/test")
+                    .parse()?,
+            )
+            .await?,
+        in_process_client
+            .get(
+                in_process
+                    .url("/test")
+                    .parse()?,
+            )
+            .await?,
+    ];
+
+    for response in responses {
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(response.headers()["x-mock"], "hyper");
+        assert_eq!(
+            response
+                .into_body()
+                .collect()
+                .await?
+                .to_bytes(),
+            "teste"
+        );
+    }
+
+    assert_eq!(
+        loopback
+            .journal()
+            .len(),
+        1
+    );
+    assert_eq!(
+        in_process
+            .journal()
+            .len(),
+        1
+    );
+
+    loopback
+        .stop()
+        .await?;
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_h2c_request_in_process() -> Result<(), Box<dyn Error>> {
+    let mut server = EasyHttpMock::new(EasyHttpMockConfig::<InProcessAdapter>::default())?;
+
+    Mock::of(
+        given(path("/test").and(version("h2c"))).will_return(
+            StatusCode::OK
+                .respond()
+                .empty(),
+        ),
+    )
+    .use_on(&mut server)
+    .await?;
+
+    let client = Client::builder(TokioExecutor::new())
+        .http2_only(true)
+        .build::<_, Empty<Bytes>>(server.connector());
+
+    let response = client
+        .get(
+            server
+                .url("/test")
+                .parse()?,
+        )
+        .await?;
+    assert_eq!(response.status(), StatusCode::OK);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_call_service_directly() -> Result<(), Box<dyn Error>> {
+    let mut server = EasyHttpMock::new(EasyHttpMockConfig::<InProcessAdapter>::default())?;
+    register_mocks(&mut server).await?;
+
+    let mut service = server.service();
+    std::future::poll_fn(|cx| {
+        tower_service::Service::<http::Request<Empty<Bytes>>>::poll_ready(&mut service, cx)
+    })
+    .await?;
+
+    let request = http::Request::get("/test").body(Empty::<Bytes>::new())?;
+    let response = tower_service::Service::call(&mut service, request).await?;
+
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(
+        response
+            .into_body()
+            .collect()
+            .await?
+            .to_bytes(),
+        "teste"
+    );
+    assert_eq!(
+        server
+            .journal()
+            .len(),
+        1
+    );
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_start_without_mock_fails() {
+    let mut adapter = InProcessAdapter::default();
+
+    assert!(adapter
+        .start()
+        .await
+        .is_err());
+}
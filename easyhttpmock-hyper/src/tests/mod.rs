@@ -0,0 +1,550 @@
+mod blocking;
+#[cfg(feature = "in-process")]
+mod in_process;
+mod pool;
+
+use crate::{
+    connection::{ConnectionAction, ConnectionEvent},
+    hyper_adapter::{HyperAdapter, HyperAdapterConfig},
+};
+use bytes::Bytes;
+use easyhttpmock::{
+    config::EasyHttpMockConfig,
+    matchers::{body, method, path, version},
+    mock::{given, AsyncMatcherExt, Mock, StatusCodeExt},
+    server::{OversizedBody, PortGenerator, ServerAdapter},
+    EasyHttpMock,
+};
+use http::{StatusCode, Version};
+use http_body_util::{BodyExt, Empty, Full};
+use hyper::body::Incoming;
+use hyper_util::rt::{TokioExecutor, TokioIo};
+use std::error::Error;
+use tokio::net::TcpStream;
+
+#[cfg(feature = "rust-tls")]
+const CA_CERT: &[u8] = include_bytes!("../../../certs/ca.der");
+#[cfg(feature = "rust-tls")]
+const CLIENT_CERT: &[u8] = include_bytes!("../../../certs/client.der");
+#[cfg(feature = "rust-tls")]
+const CLIENT_KEY: &[u8] = include_bytes!("../../../certs/client.key.der");
+#[cfg(feature = "rust-tls")]
+const SERVER_CERT: &[u8] = include_bytes!("../../../certs/server.der");
+#[cfg(feature = "rust-tls")]
+const SERVER_KEY: &[u8] = include_bytes!("../../../certs/server.key.der");
+
+async fn start(
+    hyper_adapter_config: HyperAdapterConfig,
+) -> Result<EasyHttpMock<HyperAdapter>, Box<dyn Error>> {
+    let config = EasyHttpMockConfig::<HyperAdapter>::builder()
+        .server_config(hyper_adapter_config)
+        .build();
+
+    let Ok(mut server) = EasyHttpMock::new(config) else {
+        panic!("Failed to create mock server");
+    };
+
+    Mock::of(
+        given(path("/test").and(method("GET"))).will_return(
+            StatusCode::OK
+                .respond()
+                .with_header("x-mock", "hyper")
+                .with_body(b"teste"),
+        ),
+    )
+    .use_on(&mut server)
+    .await?;
+
+    Ok(server)
+}
+
+async fn send<I>(io: I, http2: bool) -> Result<hyper::Response<Incoming>, Box<dyn Error>>
+where
+    I: hyper::rt::Read + hyper::rt::Write + Unpin + Send + 'static,
+{
+    let request = hyper::Request::get("/test")
+        .header("host", "localhost")
+        .body(Empty::<Bytes>::new())?;
+
+    if http2 {
+        let (mut sender, connection) =
+            hyper::client::conn::http2::handshake(TokioExecutor::new(), io).await?;
+        tokio::spawn(connection);
+        Ok(sender
+            .send_request(request)
+            .await?)
+    } else {
+        let (mut sender, connection) = hyper::client::conn::http1::handshake(io).await?;
+        tokio::spawn(connection);
+        Ok(sender
+            .send_request(request)
+            .await?)
+    }
+}
+
+async fn connect(server: &EasyHttpMock<HyperAdapter>) -> Result<TcpStream, Box<dyn Error>> {
+    Ok(TcpStream::connect((
+        "localhost",
+        server
+            .config()
+            .port(),
+    ))
+    .await?)
+}
+
+#[tokio::test]
+async fn test_mock_request() -> Result<(), Box<dyn Error>> {
+    let mut server = start(
+        HyperAdapterConfig::builder()
+            .with_random_port()
+            .build(),
+    )
+    .await?;
+
+    let response = send(TokioIo::new(connect(&server).await?), false).await?;
+
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(response.headers()["x-mock"], "hyper");
+    assert_eq!(
+        response
+            .into_body()
+            .collect()
+            .await?
+            .to_bytes(),
+        "teste"
+    );
+    assert_eq!(
+        server
+            .journal()
+            .len(),
+        1
+    );
+
+    server
+        .stop()
+        .await?;
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_h2c_request() -> Result<(), Box<dyn Error>> {
+    let config = EasyHttpMockConfig::<HyperAdapter>::builder()
+        .server_config(
+            HyperAdapterConfig::builder()
+                .protocol_version(Version::HTTP_2)
+                .port(0)
+                .build(),
+        )
+        .build();
+
+    let Ok(mut server) = EasyHttpMock::new(config) else {
+        panic!("Failed to create mock server");
+    };
+
+    Mock::of(
+        given(path("/test").and(version("h2c"))).will_return(
+            StatusCode::OK
+                .respond()
+                .empty(),
+        ),
+    )
+    .use_on(&mut server)
+    .await?;
+
+    assert_ne!(
+        server
+            .config()
+            .port(),
+        0
+    );
+
+    let response = send(TokioIo::new(connect(&server).await?), true).await?;
+    assert_eq!(response.status(), StatusCode::OK);
+
+    server
+        .stop()
+        .await?;
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_idle_connection_does_not_delay_stop() -> Result<(), Box<dyn Error>> {
+    let mut server = start(
+        HyperAdapterConfig::builder()
+            .with_random_port()
+            .build(),
+    )
+    .await?;
+
+    let (mut sender, connection) =
+        hyper::client::conn::http1::handshake(TokioIo::new(connect(&server).await?)).await?;
+    let connection = tokio::spawn(connection);
+
+    let response = sender
+        .send_request(
+            hyper::Request::get("/test")
+                .header("host", "localhost")
+                .body(Empty::<Bytes>::new())?,
+        )
+        .await?;
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let stopping = std::time::Instant::now();
+    server
+        .stop()
+        .await?;
+
+    assert!(stopping.elapsed() < std::time::Duration::from_secs(1));
+    assert!(connection
+        .await?
+        .is_ok());
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_port_released_on_drop() -> Result<(), Box<dyn Error>> {
+    let server = start(
+        HyperAdapterConfig::builder()
+            .with_random_port()
+            .build(),
+    )
+    .await?;
+    let port = server
+        .config()
+        .port();
+
+    drop(server);
+
+    let mut released = false;
+    for _ in 0..50 {
+        if tokio::net::TcpListener::bind(("0.0.0.0", port))
+            .await
+            .is_ok()
+        {
+            released = true;
+            break;
+        }
+        tokio::time::sleep(std::time::Duration::from_millis(10)).await;
+    }
+    assert!(released);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_close_connection_fault() -> Result<(), Box<dyn Error>> {
+    let mut server = start(
+        HyperAdapterConfig::builder()
+            .with_random_port()
+            .on_connection(|_| ConnectionAction::Close)
+            .build(),
+    )
+    .await?;
+
+    assert!(send(TokioIo::new(connect(&server).await?), false)
+        .await
+        .is_err());
+
+    let events = server
+        .connection_events()
+        .events();
+    assert!(matches!(events.first(), Some(ConnectionEvent::Accepted(_))));
+    assert!(server
+        .journal()
+        .is_empty());
+
+    server
+        .stop()
+        .await?;
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_raw_response_fault() -> Result<(), Box<dyn Error>> {
+    let mut server = start(
+        HyperAdapterConfig::builder()
+            .with_random_port()
+            .on_connection(|_| {
+                ConnectionAction::Raw(Bytes::from_static(
+                    b"HTTP/1.1 502 Bad Gateway\r\ncontent-length: 0\r\n\r\n",
+                ))
+            })
+            .build(),
+    )
+    .await?;
+
+    let response = send(TokioIo::new(connect(&server).await?), false).await?;
+
+    assert_eq!(response.status(), StatusCode::BAD_GATEWAY);
+    assert!(server
+        .journal()
+        .is_empty());
+
+    server
+        .stop()
+        .await?;
+
+    Ok(())
+}
+
+async fn upload(
+    server: &EasyHttpMock<HyperAdapter>,
+    body: &'static str,
+) -> Result<hyper::Response<Incoming>, Box<dyn Error>> {
+    let (mut sender, connection) =
+        hyper::client::conn::http1::handshake(TokioIo::new(connect(server).await?)).await?;
+    tokio::spawn(connection);
+
+    Ok(sender
+        .send_request(
+            hyper::Request::post("/upload")
+                .header("host", "localhost")
+                .body(Full::new(Bytes::from_static(body.as_bytes())))?,
+        )
+        .await?)
+}
+
+#[tokio::test]
+async fn test_oversized_body_rejected() -> Result<(), Box<dyn Error>> {
+    let mut server = start(
+        HyperAdapterConfig::builder()
+            .with_random_port()
+            .max_body_size(4)
+            .build(),
+    )
+    .await?;
+
+    let response = upload(&server, "0123456789").await?;
+
+    assert_eq!(response.status(), StatusCode::PAYLOAD_TOO_LARGE);
+    assert!(server
+        .journal()
+        .is_empty());
+
+    server
+        .stop()
+        .await?;
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_oversized_body_resets_connection() -> Result<(), Box<dyn Error>> {
+    let mut server = start(
+        HyperAdapterConfig::builder()
+            .with_random_port()
+            .max_body_size(4)
+            .oversized_body(OversizedBody::Reset)
+            .build(),
+    )
+    .await?;
+
+    assert!(upload(&server, "0123456789")
+        .await
+        .is_err());
+    assert!(server
+        .journal()
+        .is_empty());
+
+    server
+        .stop()
+        .await?;
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_journal_body_truncated() -> Result<(), Box<dyn Error>> {
+    let config = EasyHttpMockConfig::<HyperAdapter>::builder()
+        .server_config(
+            HyperAdapterConfig::builder()
+                .with_random_port()
+                .max_journal_body_size(4)
+                .build(),
+        )
+        .build();
+    let mut server = EasyHttpMock::new(config)?;
+
+    Mock::of(
+        given(path("/upload").and(body("^0123456789$"))).will_return(
+            StatusCode::OK
+                .respond()
+                .empty(),
+        ),
+    )
+    .use_on(&mut server)
+    .await?;
+
+    let response = upload(&server, "0123456789").await?;
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let entries = server
+        .journal()
+        .entries();
+    assert!(entries[0].is_matched());
+    assert_eq!(entries[0].truncated_bytes(), 6);
+
+    server
+        .stop()
+        .await?;
+
+    Ok(())
+}
+
+#[cfg(feature = "rust-tls")]
+async fn connect_tls(
+    server: &EasyHttpMock<HyperAdapter>,
+    client_certificate: bool,
+) -> Result<tokio_rustls::client::TlsStream<TcpStream>, Box<dyn Error>> {
+    use std::sync::Arc;
+    use tokio_rustls::{
+        rustls::{
+            pki_types::{CertificateDer, PrivateKeyDer, ServerName},
+            ClientConfig, RootCertStore,
+        },
+        TlsConnector,
+    };
+
+    let mut roots = RootCertStore::empty();
+    roots.add(CertificateDer::from(CA_CERT))?;
+
+    let builder = ClientConfig::builder().with_root_certificates(roots);
+    let config = if client_certificate {
+        builder.with_client_auth_cert(
+            vec![CertificateDer::from(CLIENT_CERT.to_vec())],
+            PrivateKeyDer::try_from(CLIENT_KEY.to_vec())?,
+        )?
+    } else {
+        builder.with_no_client_auth()
+    };
+
+    let connector = TlsConnector::from(Arc::new(config));
+    Ok(connector
+        .connect(ServerName::try_from("localhost")?, connect(server).await?)
+        .await?)
+}
+
+#[cfg(feature = "rust-tls")]
+#[tokio::test]
+async fn test_mock_request_over_tls() -> Result<(), Box<dyn Error>> {
+    let mut server = start(
+        HyperAdapterConfig::builder()
+            .with_random_port()
+            .cert(SERVER_CERT.to_vec())
+            .key(SERVER_KEY.to_vec())
+            .build(),
+    )
+    .await?;
+
+    assert!(server
+        .url("/test")
+        .starts_with("https://"));
+
+    let response = send(TokioIo::new(connect_tls(&server, false).await?), false).await?;
+    assert_eq!(response.status(), StatusCode::OK);
+
+    server
+        .stop()
+        .await?;
+
+    Ok(())
+}
+
+#[cfg(feature = "rust-tls")]
+#[tokio::test]
+async fn test_client_certificate_is_recorded() -> Result<(), Box<dyn Error>> {
+    use easyhttpmock::tls::ClientAuth;
+
+    let mut server = start(
+        HyperAdapterConfig::builder()
+            .with_random_port()
+            .cert(SERVER_CERT.to_vec())
+            .key(SERVER_KEY.to_vec())
+            .ca(CA_CERT.to_vec())
+            .client_auth(ClientAuth::Required)
+            .build(),
+    )
+    .await?;
+
+    let response = send(TokioIo::new(connect_tls(&server, true).await?), false).await?;
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let entries = server
+        .journal()
+        .entries();
+    assert_eq!(
+        entries[0]
+            .client_certificate()
+            .map(|certificate| certificate
+                .der()
+                .to_vec()),
+        Some(CLIENT_CERT.to_vec())
+    );
+
+    server
+        .stop()
+        .await?;
+
+    Ok(())
+}
+
+#[cfg(unix)]
+#[tokio::test]
+async fn test_mock_request_over_unix_socket() -> Result<(), Box<dyn Error>> {
+    let socket = std::env::temp_dir().join(format!("easyhttpmock-{}.sock", std::process::id()));
+
+    let mut server = start(
+        HyperAdapterConfig::builder()
+            .unix_socket(&socket)
+            .build(),
+    )
+    .await?;
+
+    assert!(server
+        .url("/test")
+        .starts_with("http+unix://%2F"));
+    assert!(socket.exists());
+
+    let stream = tokio::net::UnixStream::connect(&socket).await?;
+    let response = send(TokioIo::new(stream), false).await?;
+
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(response.headers()["x-mock"], "hyper");
+    assert_eq!(
+        server
+            .journal()
+            .len(),
+        1
+    );
+
+    server
+        .stop()
+        .await?;
+    assert!(!socket.exists());
+
+    Ok(())
+}
+
+#[cfg(unix)]
+#[tokio::test]
+async fn test_unix_socket_is_removed_on_drop() -> Result<(), Box<dyn Error>> {
+    let socket =
+        std::env::temp_dir().join(format!("easyhttpmock-drop-{}.sock", std::process::id()));
+
+    let server = start(
+        HyperAdapterConfig::builder()
+            .unix_socket(&socket)
+            .build(),
+    )
+    .await?;
+    assert!(socket.exists());
+
+    drop(server);
+    assert!(!socket.exists());
+
+    Ok(())
+}
@@ -0,0 +1,87 @@
+use crate::{
+    matchers::{method, path},
+    mock::{given, AsyncMatcherExt, Mock, StatusCodeExt},
+    pool,
+};
+use http::StatusCode;
+use std::{
+    error::Error,
+    io::{Read, Write},
+    net::TcpStream,
+};
+
+fn get(url: &str) -> Result<String, Box<dyn Error>> {
+    let Some((authority, path)) = url
+        .trim_start_matches("http://")
+        .split_once('/')
+    else {
+        panic!("URL without path");
+    };
+
+    let mut stream = TcpStream::connect(authority)?;
+    write!(stream, "GET /{} HTTP/1.1\r\nhost: {}\r\nconnection: close\r\n\r\n", path, authority)?;
+
+    let mut response = String::new();
+    stream.read_to_string(&mut response)?;
+    Ok(response)
+}
+
+#[test]
+fn test_namespaces_share_a_server() -> Result<(), Box<dyn Error>> {
+    let first = pool::namespace()?;
+    let second = pool::namespace()?;
+
+    first.register_mock(Mock::of(
+        given(path("^/test$").and(method("GET"))).will_return(
+            StatusCode::OK
+                .respond()
+                .with_body(b"first"),
+        ),
+    ));
+    second.register_mock(Mock::of(
+        given(path("^/test$").and(method("GET"))).will_return(
+            StatusCode::OK
+                .respond()
+                .with_body(b"second"),
+        ),
+    ));
+
+    assert_ne!(first.url("/test"), second.url("/test"));
+    assert!(get(&first.url("/test"))?.ends_with("first"));
+    assert!(get(&second.url("/test"))?.ends_with("second"));
+
+    assert_eq!(
+        first
+            .journal()
+            .len(),
+        1
+    );
+    first
+        .journal()
+        .verify()?;
+
+    let url = first.url("/test");
+    drop(first);
+    assert!(get(&url)?.starts_with("HTTP/1.1 404"));
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_namespace_from_async_test() -> Result<(), Box<dyn Error>> {
+    let namespace = pool::namespace()?;
+    namespace.register_mock(Mock::of(
+        given(path("^/test$")).will_return(
+            StatusCode::OK
+                .respond()
+                .empty(),
+        ),
+    ));
+
+    let url = namespace.url("/test");
+    let response =
+        tokio::task::spawn_blocking(move || get(&url).map_err(|e| e.to_string())).await??;
+    assert!(response.starts_with("HTTP/1.1 200"));
+
+    Ok(())
+}
@@ -0,0 +1,72 @@
+use crate::hyper_adapter::HyperAdapterConfig;
+use easyhttpmock::{
+    errors::{EasyHttpMockError, ServerError},
+    tls::ClientAuth,
+};
+use http::Version;
+use std::sync::Arc;
+use tokio_rustls::{
+    rustls::{
+        pki_types::{CertificateDer, PrivateKeyDer},
+        server::WebPkiClientVerifier,
+        RootCertStore, ServerConfig,
+    },
+    TlsAcceptor,
+};
+
+/// Builds the TLS acceptor of the server, if a certificate and a key are configured.
+///
+/// # Arguments
+/// * `config` - The configuration of the adapter.
+///
+/// # Returns
+/// The TLS acceptor, `None` for plain HTTP, or an error if the certificates are invalid.
+pub(crate) fn acceptor(
+    config: &HyperAdapterConfig,
+) -> Result<Option<TlsAcceptor>, EasyHttpMockError> {
+    let (Some(cert), Some(key)) = (config.cert(), config.key()) else {
+        return Ok(None);
+    };
+
+    let error = |message: String| EasyHttpMockError::Server(ServerError::Config(message));
+
+    let builder = ServerConfig::builder();
+    let builder = match (config.ca(), config.client_auth()) {
+        (_, ClientAuth::None) => builder.with_no_client_auth(),
+        (None, _) => {
+            return Err(error("Client authentication requires a CA certificate".to_string()));
+        }
+        (Some(ca), client_auth) => {
+            let mut roots = RootCertStore::empty();
+            roots
+                .add(CertificateDer::from(ca.clone()))
+                .map_err(|e| error(e.to_string()))?;
+
+            let verifier = WebPkiClientVerifier::builder(Arc::new(roots));
+            let verifier = if client_auth == ClientAuth::Optional {
+                verifier.allow_unauthenticated()
+            } else {
+                verifier
+            };
+
+            builder.with_client_cert_verifier(
+                verifier
+                    .build()
+                    .map_err(|e| error(e.to_string()))?,
+            )
+        }
+    };
+
+    let key = PrivateKeyDer::try_from(key.clone()).map_err(|e| error(e.to_string()))?;
+    let mut server_config = builder
+        .with_single_cert(vec![CertificateDer::from(cert.clone())], key)
+        .map_err(|e| error(e.to_string()))?;
+
+    server_config.alpn_protocols = if config.protocol_version() == Version::HTTP_2 {
+        vec![b"h2".to_vec()]
+    } else {
+        vec![b"http/1.1".to_vec()]
+    };
+
+    Ok(Some(TlsAcceptor::from(Arc::new(server_config))))
+}
@@ -0,0 +1,208 @@
+#![doc = include_str!("../README.md")]
+#![deny(missing_docs)]
+use proc_macro::TokenStream;
+use proc_macro2::Span;
+use quote::{format_ident, quote};
+use syn::{parse_macro_input, spanned::Spanned, Expr, FnArg, Ident, ItemFn, Pat, ReturnType};
+
+/// Adapters the attribute can run a test on, with the crate providing them
+const ADAPTERS: [(&str, &str); 3] = [
+    ("tokio", "easyhttpmock_vetis_tokio"),
+    ("smol", "easyhttpmock_vetis_smol"),
+    ("compio", "easyhttpmock_vetis_compio"),
+];
+
+/// Arguments of the `test` attribute
+#[derive(Default)]
+struct TestArgs {
+    adapter: Option<Ident>,
+    config: Option<Expr>,
+}
+
+/// Declares an async test receiving a mock server
+///
+/// The test function takes a single `EasyHttpMock` argument. The attribute creates the server
+/// before the test, runs the test on the runtime of the adapter, then verifies every request
+/// received by the server matched its mock and stops the server. The server starts listening
+/// when the test registers its mock.
+///
+/// # Arguments
+///
+/// * `adapter` - The Vetis adapter to use, one of `tokio`, `smol` or `compio`
+/// * `config` - The `VetisAdapterConfig` of the server, a random port by default
+///
+/// # Examples
+///
+/// ```rust,ignore
+/// use easyhttpmock_vetis_tokio::{
+///     matchers::path,
+///     mock::{given, Mock, StatusCodeExt},
+///     vetis_adapter::VetisAdapter,
+///     EasyHttpMock,
+/// };
+/// use http::StatusCode;
+/// use std::error::Error;
+///
+/// #[easyhttpmock::test(adapter = tokio)]
+/// async fn test_get(mut server: EasyHttpMock<VetisAdapter>) -> Result<(), Box<dyn Error>> {
+///     Mock::of(given(path("/test")).will_return(StatusCode::OK.respond().empty()))
+///         .use_on(&mut server)
+///         .await?;
+///
+///     // ... call server.url("/test") with any client ...
+///
+///     Ok(())
+/// }
+/// ```
+#[proc_macro_attribute]
+pub fn test(args: TokenStream, item: TokenStream) -> TokenStream {
+    let mut test_args = TestArgs::default();
+    let parser = syn::meta::parser(|meta| {
+        if meta
+            .path
+            .is_ident("adapter")
+        {
+            test_args.adapter = Some(
+                meta.value()?
+                    .parse()?,
+            );
+            Ok(())
+        } else if meta
+            .path
+            .is_ident("config")
+        {
+            test_args.config = Some(
+                meta.value()?
+                    .parse()?,
+            );
+            Ok(())
+        } else {
+            Err(meta.error("unsupported argument, expected `adapter` or `config`"))
+        }
+    });
+    parse_macro_input!(args with parser);
+
+    let item = parse_macro_input!(item as ItemFn);
+    match expand(test_args, item) {
+        Ok(tokens) => tokens.into(),
+        Err(error) => error
+            .to_compile_error()
+            .into(),
+    }
+}
+
+/// Expands the test function into a test running it against a mock server
+///
+/// # Arguments
+///
+/// * `args` - The arguments of the attribute
+/// * `item` - The test function
+///
+/// # Returns
+///
+/// * `syn::Result<proc_macro2::TokenStream>` - The expanded test, or the error to report
+fn expand(args: TestArgs, item: ItemFn) -> syn::Result<proc_macro2::TokenStream> {
+    let Some(adapter) = args.adapter else {
+        return Err(syn::Error::new(
+            Span::call_site(),
+            "missing adapter, expected `adapter = tokio`, `smol` or `compio`",
+        ));
+    };
+    let Some((_, adapter_crate)) = ADAPTERS
+        .iter()
+        .find(|(name, _)| adapter == name)
+    else {
+        return Err(syn::Error::new(
+            adapter.span(),
+            "unknown adapter, expected one of `tokio`, `smol` or `compio`",
+        ));
+    };
+    let adapter_crate = format_ident!("{}", adapter_crate);
+
+    let ItemFn { attrs, vis, sig, block } = item;
+    if sig
+        .asyncness
+        .is_none()
+    {
+        return Err(syn::Error::new(sig.fn_token.span(), "the test function must be async"));
+    }
+    if sig.inputs.len() != 1 {
+        return Err(syn::Error::new(
+            sig.inputs.span(),
+            "the test function must take the mock server as its only argument",
+        ));
+    }
+
+    let Some(FnArg::Typed(argument)) = sig.inputs.first() else {
+        return Err(syn::Error::new(sig.inputs.span(), "the mock server must be a typed argument"));
+    };
+    let Pat::Ident(pattern) = argument
+        .pat
+        .as_ref()
+    else {
+        return Err(syn::Error::new(
+            argument.pat.span(),
+            "the mock server must be bound to a name",
+        ));
+    };
+    let server = &pattern.ident;
+    let server_type = &argument.ty;
+
+    let name = &sig.ident;
+    let (output, result_type) = match &sig.output {
+        ReturnType::Default => (quote! {}, quote! { () }),
+        ReturnType::Type(arrow, result_type) => {
+            (quote! { #arrow #result_type }, quote! { #result_type })
+        }
+    };
+
+    let config = match args.config {
+        Some(config) => quote! { #config },
+        None => quote! {
+            ::#adapter_crate::vetis_adapter::VetisAdapterConfig::builder()
+                .with_random_port()
+                .build()
+        },
+    };
+
+    Ok(quote! {
+        #(#attrs)*
+        #[test]
+        #vis fn #name() #output {
+            #[allow(unused_imports)]
+            use ::#adapter_crate::server::PortGenerator as _;
+
+            fn __easyhttpmock_test<F>(test: F) -> F
+            where
+                F: ::core::future::Future<Output = #result_type>,
+            {
+                test
+            }
+
+            ::#adapter_crate::__private::block_on(async {
+                let __easyhttpmock_config = ::#adapter_crate::config::EasyHttpMockConfig::<
+                    ::#adapter_crate::vetis_adapter::VetisAdapter,
+                >::builder()
+                .server_config(#config)
+                .build();
+
+                let mut #server: #server_type =
+                    match ::#adapter_crate::EasyHttpMock::new(__easyhttpmock_config) {
+                        Ok(server) => server,
+                        Err(err) => panic!("Failed to create mock server: {}", err),
+                    };
+
+                let __easyhttpmock_result = __easyhttpmock_test(async #block).await;
+
+                if let Err(err) = #server.journal().verify() {
+                    panic!("{}", err);
+                }
+                if let Err(err) = #server.stop().await {
+                    panic!("Failed to stop mock server: {}", err);
+                }
+
+                __easyhttpmock_result
+            })
+        }
+    })
+}
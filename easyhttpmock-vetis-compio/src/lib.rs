@@ -4,5 +4,21 @@
 pub mod vetis_adapter;
 pub use easyhttpmock::*;
 pub use vetis_compio::handler_fn;
+
+#[doc(hidden)]
+pub mod __private {
+    /// Runs a test to completion on a new compio runtime.
+    ///
+    /// # Arguments
+    /// * `future` - The test to run.
+    pub fn block_on<F: std::future::Future>(future: F) -> F::Output {
+        compio::runtime::Runtime::new()
+            .expect("Failed to create compio runtime")
+            .block_on(future)
+    }
+}
+
+#[cfg(test)]
+extern crate self as easyhttpmock_vetis_compio;
 #[cfg(test)]
 mod tests;
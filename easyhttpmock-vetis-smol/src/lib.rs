@@ -4,5 +4,19 @@
 pub mod vetis_adapter;
 pub use easyhttpmock::*;
 pub use vetis_smol::handler_fn;
+
+#[doc(hidden)]
+pub mod __private {
+    /// Runs a test to completion on the global smol executor.
+    ///
+    /// # Arguments
+    /// * `future` - The test to run.
+    pub fn block_on<F: std::future::Future>(future: F) -> F::Output {
+        smol::block_on(future)
+    }
+}
+
+#[cfg(test)]
+extern crate self as easyhttpmock_vetis_smol;
 #[cfg(test)]
 mod tests;
@@ -0,0 +1,51 @@
+use crate::vetis_adapter::{VetisAdapter, VetisAdapterConfig};
+use deboa::{request::get, HttpClient};
+use deboa_smol::Client;
+use easyhttpmock::{
+    matchers::{method, path},
+    mock::{given, AsyncMatcherExt, Mock, StatusCodeExt},
+    EasyHttpMock,
+};
+use http::StatusCode;
+use std::error::Error;
+
+#[easyhttpmock::test(adapter = smol)]
+async fn test_server_from_attribute(
+    mut server: EasyHttpMock<VetisAdapter>,
+) -> Result<(), Box<dyn Error>> {
+    Mock::of(
+        given(path("/test").and(method("GET"))).will_return(
+            StatusCode::OK
+                .respond()
+                .with_body(b"teste"),
+        ),
+    )
+    .use_on(&mut server)
+    .await?;
+
+    let request = get(server.url("/test"))?.build()?;
+    let response = Client::builder()
+        .build()
+        .execute(request)
+        .await?;
+
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(
+        server
+            .journal()
+            .len(),
+        1
+    );
+
+    Ok(())
+}
+
+#[easyhttpmock::test(
+    adapter = smol,
+    config = VetisAdapterConfig::builder().hostname("127.0.0.1").with_random_port().build()
+)]
+async fn test_server_from_attribute_with_config(server: EasyHttpMock<VetisAdapter>) {
+    assert!(server
+        .url("/test")
+        .starts_with("http://127.0.0.1:"));
+}
@@ -4,5 +4,23 @@
 pub mod vetis_adapter;
 pub use easyhttpmock::*;
 pub use vetis_tokio::handler_fn;
+
+#[doc(hidden)]
+pub mod __private {
+    /// Runs a test to completion on a new current thread Tokio runtime.
+    ///
+    /// # Arguments
+    /// * `future` - The test to run.
+    pub fn block_on<F: std::future::Future>(future: F) -> F::Output {
+        tokio::runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()
+            .expect("Failed to create Tokio runtime")
+            .block_on(future)
+    }
+}
+
+#[cfg(test)]
+extern crate self as easyhttpmock_vetis_tokio;
 #[cfg(test)]
 mod tests;
@@ -0,0 +1,401 @@
+/// Generates the `VetisAdapter` of a runtime crate from its Vetis crate.
+///
+/// The Vetis crates of every runtime expose the same API under different crate names, the macro
+/// expands the adapter against the given one so request handling, listeners and TLS wiring are
+/// shared. Runtimes whose handler futures are not `Send` pass a function wrapping them, others
+/// pass `std::convert::identity`. The detach function runs the stop of a server dropped without
+/// being stopped in the background of the runtime. The connect and sleep functions are the async
+/// TCP connect and timer of the runtime, used to wait for the listeners and the requests being
+/// handled without blocking the runtime.
+///
+/// The adapter is a macro rather than a `VetisAdapter<R: Runtime>` because the Vetis crates share
+/// no trait: `Vetis`, `ServerConfig`, `SecurityConfig`, the virtual hosts and `handler_fn` are
+/// distinct types in each crate, and their handler futures differ in whether they must be `Send`.
+/// A generic adapter would need a trait mirroring that whole API, implemented again in every
+/// runtime crate. The macro only holds the code touching those types, the rest lives in the
+/// configuration and in the [`handler`](crate::handler) module.
+///
+/// # Arguments
+/// * `vetis` - The Vetis crate of the runtime, such as `vetis_tokio`.
+/// * `wrap` - The function wrapping the handler futures.
+/// * `detach` - The function running a future in the background.
+/// * `connect` - The async function opening a TCP connection to a `SocketAddr`.
+/// * `sleep` - The async function sleeping for a `Duration`.
+///
+/// # Examples
+///
+/// ```rust,ignore
+/// easyhttpmock_vetis::vetis_adapter!(vetis_tokio, std::convert::identity, detach, connect, sleep);
+/// ```
+#[macro_export]
+macro_rules! vetis_adapter {
+    ($vetis:ident, $wrap:path, $detach:path, $connect:path, $sleep:path) => {
+        pub use $crate::config::{VetisAdapterConfig, VetisAdapterConfigBuilder};
+
+        use std::sync::Arc;
+        use $crate::__private::{
+            easyhttpmock::{
+                errors::{EasyHttpMockError, MockError, ServerError},
+                journal::Journal,
+                mock::{Mock, Respond},
+                server::{
+                    generate_randon_port, wait_until_ready, InFlight, PortGenerator, ServerAdapter,
+                    READY_TIMEOUT,
+                },
+                tls::{ClientAuth, ClientCertificate},
+                HttpMockResult,
+            },
+            http::Extensions,
+        };
+        use $crate::handler::{respond, response_headers};
+        use $vetis::VetisServer as _;
+
+        #[derive(Default)]
+        /// Vetis adapter implementation
+        pub struct VetisAdapter {
+            server: $vetis::Vetis,
+            config: VetisAdapterConfig,
+            mock: Option<Arc<Mock>>,
+            journal: Journal,
+            in_flight: InFlight,
+            running: bool,
+        }
+
+        impl PortGenerator<VetisAdapter> for VetisAdapterConfigBuilder {
+            fn with_random_port(self) -> Self {
+                self.port(generate_randon_port())
+            }
+        }
+
+        impl ServerAdapter for VetisAdapter {
+            /// The configuration type for the adapter.
+            type Config = VetisAdapterConfig;
+
+            /// Creates a new VetisAdapter instance.
+            ///
+            /// # Arguments
+            /// * `config` - The configuration for the adapter.
+            ///
+            /// # Returns
+            /// A new `VetisAdapter` instance.
+            fn new(config: Self::Config) -> Result<Self, EasyHttpMockError> {
+                let server = $vetis::Vetis::new(server_config(&config));
+                let journal = Journal::with_body_limit(config.max_journal_body_size());
+
+                Ok(Self {
+                    server,
+                    config,
+                    mock: None,
+                    journal,
+                    in_flight: InFlight::new(),
+                    running: false,
+                })
+            }
+
+            /// Returns the hostname of the server.
+            ///
+            /// # Returns
+            /// The hostname of the server.
+            fn hostname(&self) -> String {
+                self.config
+                    .hostname()
+                    .clone()
+            }
+
+            /// Returns the base URL of the server.
+            ///
+            /// # Returns
+            /// The base URL of the server.
+            fn base_url(&self) -> String {
+                self.config
+                    .base_url()
+            }
+
+            /// Returns the base URL of the HTTP listener.
+            ///
+            /// # Returns
+            /// The base URL of the HTTP listener, if the server serves plain HTTP.
+            fn http_base_url(&self) -> Option<String> {
+                self.config
+                    .http_base_url()
+            }
+
+            /// Returns the base URL of the HTTPS listener.
+            ///
+            /// # Returns
+            /// The base URL of the HTTPS listener, if the server serves HTTPS.
+            fn https_base_url(&self) -> Option<String> {
+                self.config
+                    .https_base_url()
+            }
+
+            /// Returns the configuration of the server.
+            ///
+            /// # Returns
+            /// The configuration of the server.
+            fn config(&self) -> &Self::Config {
+                &self.config
+            }
+
+            /// Returns a mutable reference to the configuration of the server.
+            ///
+            /// # Returns
+            /// A mutable reference to the configuration of the server.
+            fn config_mut(&mut self) -> &mut Self::Config {
+                &mut self.config
+            }
+
+            /// Returns the journal of the requests received by the server.
+            ///
+            /// # Returns
+            /// The journal of the server.
+            fn journal(&self) -> &Journal {
+                &self.journal
+            }
+
+            /// Sets the mock to handle incoming requests.
+            ///
+            /// # Arguments
+            /// * `mock` - The mock to handle incoming requests.
+            fn register_mock(&mut self, mock: Arc<Mock>) {
+                self.mock = Some(mock);
+            }
+
+            /// Starts the server, with one virtual host per listener sharing the mock and the
+            /// journal.
+            ///
+            /// Resolves once every TCP listener accepts connections, HTTP/3 listeners use QUIC
+            /// and are not waited for.
+            ///
+            /// # Returns
+            /// A result indicating whether the server started successfully or a
+            /// `EasyHttpMockError` if it failed.
+            async fn start(&mut self) -> HttpMockResult<()> {
+                let mock = match self.mock.as_ref() {
+                    Some(mocker) => mocker,
+                    None => return Err(MockError::Notfound.into()),
+                };
+
+                let listeners = self
+                    .config
+                    .listeners()?;
+                let hostname = self.hostname();
+
+                let max_body_size = self
+                    .config
+                    .max_body_size();
+
+                for (port, secure) in listeners {
+                    let mock_clone = mock.clone();
+                    let journal = self.journal.clone();
+                    let in_flight = self
+                        .in_flight
+                        .clone();
+                    let path = $vetis::virtual_host::path::HandlerPath::builder()
+                        .uri("/")
+                        .handler($vetis::handler_fn(move |request| {
+                            // Since handler function is defined here, we need to clone the mocker
+                            // and the journal to move them into the async block
+                            let mock = mock_clone.clone();
+                            let journal = journal.clone();
+                            let in_flight = in_flight.clone();
+                            $wrap(async move {
+                                let _request = in_flight.enter();
+
+                                let (parts, body) = request.into_parts();
+                                let certificate = client_certificate(&parts.extensions);
+
+                                respond(&mock, &journal, parts, body, max_body_size, certificate)
+                                    .await
+                                    .map(|respond| into_response(&respond))
+                                    .map_err(|e| $vetis::errors::VetisError::Handler(e.to_string()))
+                            })
+                        }))
+                        .build()
+                        .map_err(|e| {
+                            EasyHttpMockError::Server(ServerError::Creation(e.to_string()))
+                        })?;
+
+                    let host_config = $vetis::VirtualHostConfig::builder()
+                        .hostname(&hostname)
+                        .root_directory(".")
+                        .port(port);
+
+                    let host_config = if secure {
+                        host_config.security(security_config(&self.config)?)
+                    } else {
+                        host_config
+                    };
+
+                    let host_config = host_config
+                        .build()
+                        .map_err(|e| {
+                            EasyHttpMockError::Server(ServerError::Creation(e.to_string()))
+                        })?;
+
+                    let mut host = $vetis::virtual_host::VirtualHostImpl::new(host_config);
+                    host.add_path(path);
+
+                    self.server
+                        .add_virtual_host(host)
+                        .await;
+                }
+
+                self.server
+                    .start()
+                    .await
+                    .map_err(|e| EasyHttpMockError::Server(ServerError::Start(e.to_string())))?;
+                self.running = true;
+
+                for port in self
+                    .config
+                    .tcp_ports()
+                {
+                    wait_until_ready(
+                        self.config
+                            .interface(),
+                        port,
+                        READY_TIMEOUT,
+                        $connect,
+                        $sleep,
+                    )
+                    .await?;
+                }
+
+                Ok(())
+            }
+
+            /// Stops the server, once the requests being handled completed or the drain timeout
+            /// elapsed. Stopping a server that never started does nothing.
+            ///
+            /// # Returns
+            /// A result indicating whether the server stopped successfully.
+            async fn stop(&mut self) -> HttpMockResult<()> {
+                if !self.running {
+                    return Ok(());
+                }
+
+                self.in_flight
+                    .drain($sleep(
+                        self.config
+                            .drain_timeout(),
+                    ))
+                    .await;
+                self.running = false;
+
+                self.server
+                    .stop()
+                    .await
+                    .map_err(|e| EasyHttpMockError::Server(ServerError::Stop(e.to_string())))
+            }
+
+            /// Stops a running server in the background, when the mock is dropped without being
+            /// stopped.
+            fn shutdown(&mut self) {
+                if !std::mem::take(&mut self.running) {
+                    return;
+                }
+
+                let mut server = std::mem::take(&mut self.server);
+                $detach(async move {
+                    let _ = server.stop().await;
+                });
+            }
+        }
+
+        /// Builds the Vetis server configuration, with one listener per port of the adapter.
+        ///
+        /// # Arguments
+        /// * `config` - The configuration of the adapter.
+        ///
+        /// # Returns
+        /// The server configuration.
+        fn server_config(config: &VetisAdapterConfig) -> $vetis::ServerConfig {
+            config
+                .http_port()
+                .into_iter()
+                .chain(config.https_port())
+                .fold($vetis::ServerConfig::builder(), |builder, port| {
+                    let listener_config = $vetis::ListenerConfig::builder()
+                        .interface(config.interface())
+                        .protocol_version(config.protocol_version())
+                        .port(port)
+                        .build()
+                        .expect("Failed to build listener config");
+                    builder.add_listener(listener_config)
+                })
+                .build()
+                .expect("Failed to build server config")
+        }
+
+        /// Builds the security configuration of the HTTPS listener.
+        ///
+        /// # Arguments
+        /// * `config` - The configuration of the adapter.
+        ///
+        /// # Returns
+        /// The security configuration or an error if the certificates are invalid.
+        fn security_config(
+            config: &VetisAdapterConfig,
+        ) -> Result<$vetis::SecurityConfig, EasyHttpMockError> {
+            let (Some(cert), Some(key)) = (config.cert(), config.key()) else {
+                return Err(EasyHttpMockError::Server(ServerError::Config(
+                    "HTTPS requires a certificate and a key".to_string(),
+                )));
+            };
+
+            let security = $vetis::SecurityConfig::builder()
+                .cert_from_bytes(cert.clone())
+                .key_from_bytes(key.clone());
+
+            let security = match (config.ca(), config.client_auth()) {
+                (None, _) => security,
+                (Some(ca), ClientAuth::None) => security.ca_cert_from_bytes(ca.clone()),
+                (Some(ca), ClientAuth::Optional) => security
+                    .ca_cert_from_bytes(ca.clone())
+                    .client_auth(false),
+                (Some(ca), ClientAuth::Required) => security
+                    .ca_cert_from_bytes(ca.clone())
+                    .client_auth(true),
+            };
+
+            security
+                .build()
+                .map_err(|e| EasyHttpMockError::Server(ServerError::Config(e.to_string())))
+        }
+
+        /// Returns the certificate the client presented during the TLS handshake.
+        ///
+        /// Vetis records the peer certificates of TLS connections in the extensions of their
+        /// requests, the first one is the certificate of the client.
+        ///
+        /// # Arguments
+        /// * `extensions` - The extensions of the request.
+        ///
+        /// # Returns
+        /// The client certificate, if the client presented one.
+        fn client_certificate(extensions: &Extensions) -> Option<ClientCertificate> {
+            extensions
+                .get::<$vetis::PeerCertificates>()
+                .and_then(|certificates| certificates.first())
+                .map(|certificate| ClientCertificate::new(certificate.to_vec()))
+        }
+
+        /// Builds the response sent to the client from a mocked respond.
+        ///
+        /// # Arguments
+        /// * `respond` - The mocked respond.
+        ///
+        /// # Returns
+        /// The response with the status, headers and body of the respond.
+        fn into_response(respond: &Respond) -> $vetis::http::Response {
+            response_headers(respond)
+                .into_iter()
+                .fold(
+                    $vetis::http::Response::builder().status(respond.status_code()),
+                    |builder, (name, value)| builder.header(name, value),
+                )
+                .bytes(&respond.body())
+        }
+    };
+}
@@ -0,0 +1,630 @@
+#[cfg(feature = "self-signed")]
+use easyhttpmock::tls::SelfSignedTls;
+use easyhttpmock::{
+    errors::{EasyHttpMockError, ServerError},
+    server::{generate_randon_port, OversizedBody, DEFAULT_DRAIN_TIMEOUT},
+    tls::ClientAuth,
+};
+use http::Version;
+#[cfg(feature = "self-signed")]
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr};
+use std::time::Duration;
+
+/// Builder for VetisAdapterConfig
+pub struct VetisAdapterConfigBuilder {
+    hostname: String,
+    interface: String,
+    protocol_version: Version,
+    port: u16,
+    tls_port: Option<u16>,
+    cert: Option<Vec<u8>>,
+    key: Option<Vec<u8>>,
+    ca: Option<Vec<u8>>,
+    client_auth: ClientAuth,
+    drain_timeout: Duration,
+    max_body_size: Option<usize>,
+    oversized_body: OversizedBody,
+    max_journal_body_size: Option<usize>,
+    #[cfg(feature = "self-signed")]
+    self_signed_tls: bool,
+}
+
+impl VetisAdapterConfigBuilder {
+    /// Sets the hostname for the server.
+    ///
+    /// # Arguments
+    /// * `hostname` - The hostname to set.
+    ///
+    /// # Returns
+    /// A new `VetisAdapterConfigBuilder` instance with the hostname set.
+    pub fn hostname(mut self, hostname: &str) -> Self {
+        self.hostname = hostname.to_string();
+        self
+    }
+
+    /// Sets the interface for the server.
+    ///
+    /// # Arguments
+    /// * `interface` - The interface to set.
+    ///
+    /// # Returns
+    /// A new `VetisAdapterConfigBuilder` instance with the interface set.
+    pub fn interface(mut self, interface: &str) -> Self {
+        self.interface = interface.to_string();
+        self
+    }
+
+    /// Sets the protocol for the server.
+    ///
+    /// # Arguments
+    /// * `protocol_version` - The protocol version to set.
+    ///
+    /// # Returns
+    /// A new `VetisAdapterConfigBuilder` instance with the protocol version set.
+    pub fn protocol_version(mut self, protocol_version: Version) -> Self {
+        self.protocol_version = protocol_version;
+        self
+    }
+
+    /// Sets the port for the server.
+    ///
+    /// # Arguments
+    /// * `port` - The port to set.
+    ///
+    /// # Returns
+    /// A new `VetisAdapterConfigBuilder` instance with the port set.
+    pub fn port(mut self, port: u16) -> Self {
+        self.port = port;
+        self
+    }
+
+    /// Sets the port of the HTTPS listener, served alongside the HTTP listener on `port`.
+    ///
+    /// Both listeners share the same mock and journal. Requires a certificate and a key, without
+    /// a TLS port the server only listens on `port`, using HTTPS when a certificate is set.
+    ///
+    /// # Arguments
+    /// * `tls_port` - The TLS port to set.
+    ///
+    /// # Returns
+    /// A new `VetisAdapterConfigBuilder` instance with the TLS port set.
+    pub fn tls_port(mut self, tls_port: u16) -> Self {
+        self.tls_port = Some(tls_port);
+        self
+    }
+
+    /// Sets the HTTPS listener to use a random port, see `tls_port`.
+    ///
+    /// # Returns
+    /// A new `VetisAdapterConfigBuilder` instance with a random TLS port set.
+    pub fn with_random_tls_port(self) -> Self {
+        let mut tls_port = generate_randon_port();
+        while tls_port == self.port {
+            tls_port = generate_randon_port();
+        }
+        self.tls_port(tls_port)
+    }
+
+    /// Sets the certificate for the server.
+    ///
+    /// # Arguments
+    /// * `cert` - The certificate to set.
+    ///
+    /// # Returns
+    /// A new `VetisAdapterConfigBuilder` instance with the certificate set.
+    pub fn cert(mut self, cert: Vec<u8>) -> Self {
+        self.cert = Some(cert);
+        self
+    }
+
+    /// Sets the key for the server.
+    ///
+    /// # Arguments
+    /// * `key` - The key to set.
+    ///
+    /// # Returns
+    /// A new `VetisAdapterConfigBuilder` instance with the key set.
+    pub fn key(mut self, key: Vec<u8>) -> Self {
+        self.key = Some(key);
+        self
+    }
+
+    /// Sets the CA certificate for the server.
+    ///
+    /// # Arguments
+    /// * `ca` - The CA certificate to set.
+    ///
+    /// # Returns
+    /// A new `VetisAdapterConfigBuilder` instance with the CA certificate set.
+    pub fn ca(mut self, ca: Vec<u8>) -> Self {
+        self.ca = Some(ca);
+        self
+    }
+
+    /// Sets whether the server asks clients for a certificate.
+    ///
+    /// Client certificates are verified against the CA certificate, which is required when
+    /// client authentication is enabled.
+    ///
+    /// # Arguments
+    /// * `client_auth` - The client authentication mode to set.
+    ///
+    /// # Returns
+    /// A new `VetisAdapterConfigBuilder` instance with the client authentication mode set.
+    pub fn client_auth(mut self, client_auth: ClientAuth) -> Self {
+        self.client_auth = client_auth;
+        self
+    }
+
+    /// Sets the time in-flight requests have to complete when the server stops.
+    ///
+    /// # Arguments
+    /// * `drain_timeout` - The drain timeout to set.
+    ///
+    /// # Returns
+    /// A new `VetisAdapterConfigBuilder` instance with the drain timeout set.
+    pub fn drain_timeout(mut self, drain_timeout: Duration) -> Self {
+        self.drain_timeout = drain_timeout;
+        self
+    }
+
+    /// Sets the maximum size of request bodies, larger requests are not matched.
+    ///
+    /// Encoded bodies must fit the limit both as received and once decoded.
+    ///
+    /// # Arguments
+    /// * `max_body_size` - The maximum size of request bodies, in bytes.
+    ///
+    /// # Returns
+    /// A new `VetisAdapterConfigBuilder` instance with the maximum body size set.
+    pub fn max_body_size(mut self, max_body_size: usize) -> Self {
+        self.max_body_size = Some(max_body_size);
+        self
+    }
+
+    /// Sets what the server does with requests whose body exceeds the maximum size.
+    ///
+    /// Vetis handlers cannot abort their connection, so only `OversizedBody::Reject` is
+    /// supported and `OversizedBody::Reset` fails validation.
+    ///
+    /// # Arguments
+    /// * `oversized_body` - Whether to answer `413 Payload Too Large` or reset the connection.
+    ///
+    /// # Returns
+    /// A new `VetisAdapterConfigBuilder` instance with the oversized body action set.
+    pub fn oversized_body(mut self, oversized_body: OversizedBody) -> Self {
+        self.oversized_body = oversized_body;
+        self
+    }
+
+    /// Sets the size the bodies recorded in the journal are truncated to.
+    ///
+    /// # Arguments
+    /// * `max_journal_body_size` - The maximum size of recorded bodies, in bytes.
+    ///
+    /// # Returns
+    /// A new `VetisAdapterConfigBuilder` instance with the maximum journal body size set.
+    pub fn max_journal_body_size(mut self, max_journal_body_size: usize) -> Self {
+        self.max_journal_body_size = Some(max_journal_body_size);
+        self
+    }
+
+    /// Enables TLS with an ephemeral certificate generated when the configuration is built.
+    ///
+    /// The certificate is signed by a generated CA and valid for the hostname, the loopback
+    /// addresses and the interface when it is an IP address. It replaces the certificate and key
+    /// set with `cert` and `key`, the CA is available with `VetisAdapterConfig::self_signed_tls`.
+    /// A certificate that cannot be generated fails the validation of the configuration.
+    ///
+    /// # Returns
+    /// A new `VetisAdapterConfigBuilder` instance with self-signed TLS enabled.
+    #[cfg(feature = "self-signed")]
+    pub fn with_self_signed_tls(mut self) -> Self {
+        self.self_signed_tls = true;
+        self
+    }
+
+    /// Builds the VetisAdapterConfig from the builder.
+    ///
+    /// # Returns
+    /// A new `VetisAdapterConfig` instance.
+    pub fn build(self) -> VetisAdapterConfig {
+        #[cfg(feature = "self-signed")]
+        let self_signed_tls = self
+            .self_signed_tls
+            .then(|| {
+                let mut ip_addresses =
+                    vec![IpAddr::V4(Ipv4Addr::LOCALHOST), IpAddr::V6(Ipv6Addr::LOCALHOST)];
+                if let Ok(interface) = self
+                    .interface
+                    .parse::<IpAddr>()
+                {
+                    if !interface.is_unspecified() && !ip_addresses.contains(&interface) {
+                        ip_addresses.push(interface);
+                    }
+                }
+
+                SelfSignedTls::generate(&self.hostname, &ip_addresses)
+            });
+
+        #[cfg(feature = "self-signed")]
+        let (cert, key) = match &self_signed_tls {
+            Some(Ok(tls)) => (
+                Some(
+                    tls.cert_der()
+                        .to_vec(),
+                ),
+                Some(
+                    tls.key_der()
+                        .to_vec(),
+                ),
+            ),
+            _ => (self.cert, self.key),
+        };
+        #[cfg(not(feature = "self-signed"))]
+        let (cert, key) = (self.cert, self.key);
+
+        VetisAdapterConfig {
+            hostname: self.hostname,
+            interface: self.interface,
+            protocol_version: self.protocol_version,
+            port: self.port,
+            tls_port: self.tls_port,
+            cert,
+            key,
+            ca: self.ca,
+            client_auth: self.client_auth,
+            drain_timeout: self.drain_timeout,
+            max_body_size: self.max_body_size,
+            oversized_body: self.oversized_body,
+            max_journal_body_size: self.max_journal_body_size,
+            #[cfg(feature = "self-signed")]
+            self_signed_tls,
+        }
+    }
+}
+
+/// Configuration for the Vetis adapter.
+#[derive(Clone, PartialEq)]
+pub struct VetisAdapterConfig {
+    hostname: String,
+    interface: String,
+    protocol_version: Version,
+    port: u16,
+    tls_port: Option<u16>,
+    cert: Option<Vec<u8>>,
+    key: Option<Vec<u8>>,
+    ca: Option<Vec<u8>>,
+    client_auth: ClientAuth,
+    drain_timeout: Duration,
+    max_body_size: Option<usize>,
+    oversized_body: OversizedBody,
+    max_journal_body_size: Option<usize>,
+    #[cfg(feature = "self-signed")]
+    self_signed_tls: Option<Result<SelfSignedTls, ServerError>>,
+}
+
+impl Default for VetisAdapterConfig {
+    /// Creates a default configuration for the Vetis adapter.
+    ///
+    /// This function sets up a basic server configuration with:
+    /// - Interface: "0.0.0.0"
+    /// - Port: random port between 9000 and 65535
+    /// - No TLS certificates (HTTP only)
+    /// - Drain timeout: 5 seconds
+    /// - No limit on the size of request bodies
+    ///
+    /// # Returns
+    /// A default `VetisAdapterConfig` instance.
+    fn default() -> Self {
+        Self {
+            hostname: "localhost".into(),
+            interface: "0.0.0.0".into(),
+            protocol_version: Version::HTTP_11,
+            port: generate_randon_port(),
+            tls_port: None,
+            cert: None,
+            key: None,
+            ca: None,
+            client_auth: ClientAuth::None,
+            drain_timeout: DEFAULT_DRAIN_TIMEOUT,
+            max_body_size: None,
+            oversized_body: OversizedBody::Reject,
+            max_journal_body_size: None,
+            #[cfg(feature = "self-signed")]
+            self_signed_tls: None,
+        }
+    }
+}
+
+impl VetisAdapterConfig {
+    /// Creates a new builder for the Vetis adapter configuration.
+    ///
+    /// This function sets up a basic server configuration with:
+    /// - Interface: "0.0.0.0"
+    /// - Port: random port between 9000 and 65535
+    /// - No TLS certificates (HTTP only)
+    /// - Drain timeout: 5 seconds
+    /// - No limit on the size of request bodies
+    ///
+    /// # Returns
+    /// A new `VetisAdapterConfigBuilder` instance.
+    pub fn builder() -> VetisAdapterConfigBuilder {
+        VetisAdapterConfigBuilder {
+            hostname: "localhost".into(),
+            interface: "0.0.0.0".into(),
+            protocol_version: Version::HTTP_11,
+            port: generate_randon_port(),
+            tls_port: None,
+            cert: None,
+            key: None,
+            ca: None,
+            client_auth: ClientAuth::None,
+            drain_timeout: DEFAULT_DRAIN_TIMEOUT,
+            max_body_size: None,
+            oversized_body: OversizedBody::Reject,
+            max_journal_body_size: None,
+            #[cfg(feature = "self-signed")]
+            self_signed_tls: false,
+        }
+    }
+
+    /// Returns the hostname of the server.
+    ///
+    /// # Returns
+    /// The hostname of the server.
+    pub fn hostname(&self) -> &String {
+        &self.hostname
+    }
+
+    /// Returns the interface of the server.
+    ///
+    /// # Returns
+    /// The interface of the server.
+    pub fn interface(&self) -> &str {
+        &self.interface
+    }
+
+    /// Returns the protocol version of the server.
+    ///
+    /// `HTTP_2` serves h2c over plain HTTP and h2 negotiated with ALPN over TLS, `HTTP_3` serves
+    /// HTTP/3 over QUIC and requires a certificate and the `http3` feature.
+    ///
+    /// # Returns
+    /// The protocol version of the server.
+    pub fn protocol_version(&self) -> Version {
+        self.protocol_version
+    }
+
+    /// Returns the port of the server.
+    ///
+    /// # Returns
+    /// The port of the server.
+    pub fn port(&self) -> u16 {
+        self.port
+    }
+
+    /// Returns the port of the HTTPS listener served alongside the HTTP listener, if any.
+    ///
+    /// # Returns
+    /// The TLS port of the server.
+    pub fn tls_port(&self) -> Option<u16> {
+        self.tls_port
+    }
+
+    /// Returns the port of the HTTP listener, if the server serves plain HTTP.
+    ///
+    /// # Returns
+    /// The HTTP port of the server.
+    pub fn http_port(&self) -> Option<u16> {
+        if self
+            .tls_port
+            .is_some()
+            || !self.is_tls()
+        {
+            Some(self.port)
+        } else {
+            None
+        }
+    }
+
+    /// Returns the port of the HTTPS listener, if the server serves HTTPS.
+    ///
+    /// # Returns
+    /// The HTTPS port of the server.
+    pub fn https_port(&self) -> Option<u16> {
+        if self.is_tls() {
+            Some(
+                self.tls_port
+                    .unwrap_or(self.port),
+            )
+        } else {
+            None
+        }
+    }
+
+    /// Returns the base URL of the server, the HTTP listener one when the server serves both.
+    ///
+    /// # Returns
+    /// The base URL of the server.
+    pub fn base_url(&self) -> String {
+        self.http_base_url()
+            .or_else(|| self.https_base_url())
+            .unwrap_or_else(|| format!("http://{}:{}", self.hostname, self.port))
+    }
+
+    /// Returns the base URL of the HTTP listener.
+    ///
+    /// # Returns
+    /// The base URL of the HTTP listener, if the server serves plain HTTP.
+    pub fn http_base_url(&self) -> Option<String> {
+        self.http_port()
+            .map(|port| format!("http://{}:{}", self.hostname, port))
+    }
+
+    /// Returns the base URL of the HTTPS listener.
+    ///
+    /// # Returns
+    /// The base URL of the HTTPS listener, if the server serves HTTPS.
+    pub fn https_base_url(&self) -> Option<String> {
+        self.https_port()
+            .map(|port| format!("https://{}:{}", self.hostname, port))
+    }
+
+    /// Returns the ports of the listeners accepting TCP connections, which `start` waits for.
+    ///
+    /// # Returns
+    /// The ports of the listeners, none for HTTP/3 since it runs over QUIC.
+    pub fn tcp_ports(&self) -> Vec<u16> {
+        if self.protocol_version == Version::HTTP_3 {
+            return Vec::new();
+        }
+
+        self.http_port()
+            .into_iter()
+            .chain(self.https_port())
+            .collect()
+    }
+
+    /// Returns the certificate of the server.
+    ///
+    /// # Returns
+    /// The certificate of the server.
+    pub fn cert(&self) -> &Option<Vec<u8>> {
+        &self.cert
+    }
+
+    /// Returns the key of the server.
+    ///
+    /// # Returns
+    /// The key of the server.
+    pub fn key(&self) -> &Option<Vec<u8>> {
+        &self.key
+    }
+
+    /// Returns the CA certificate of the server.
+    ///
+    /// # Returns
+    /// The CA certificate of the server.
+    pub fn ca(&self) -> &Option<Vec<u8>> {
+        &self.ca
+    }
+
+    /// Returns the client authentication mode of the server.
+    ///
+    /// # Returns
+    /// The client authentication mode of the server.
+    pub fn client_auth(&self) -> ClientAuth {
+        self.client_auth
+    }
+
+    /// Returns the time in-flight requests have to complete when the server stops.
+    ///
+    /// # Returns
+    /// The drain timeout of the server.
+    pub fn drain_timeout(&self) -> Duration {
+        self.drain_timeout
+    }
+
+    /// Returns the maximum size of request bodies.
+    ///
+    /// # Returns
+    /// The maximum size of request bodies, if they are limited.
+    pub fn max_body_size(&self) -> Option<usize> {
+        self.max_body_size
+    }
+
+    /// Returns what the server does with requests whose body exceeds the maximum size.
+    ///
+    /// # Returns
+    /// The oversized body action of the server.
+    pub fn oversized_body(&self) -> OversizedBody {
+        self.oversized_body
+    }
+
+    /// Returns the size the bodies recorded in the journal are truncated to.
+    ///
+    /// # Returns
+    /// The maximum size of recorded bodies, if they are truncated.
+    pub fn max_journal_body_size(&self) -> Option<usize> {
+        self.max_journal_body_size
+    }
+
+    /// Returns the ephemeral certificates generated for the server, if self-signed TLS is
+    /// enabled.
+    ///
+    /// # Returns
+    /// The generated CA and server certificate.
+    #[cfg(feature = "self-signed")]
+    pub fn self_signed_tls(&self) -> Option<&SelfSignedTls> {
+        self.self_signed_tls
+            .as_ref()
+            .and_then(|tls| tls.as_ref().ok())
+    }
+
+    /// Returns whether the server has a certificate and a key to serve HTTPS.
+    ///
+    /// # Returns
+    /// `true` if the server serves HTTPS.
+    pub fn is_tls(&self) -> bool {
+        self.cert.is_some() && self.key.is_some()
+    }
+
+    /// Checks that the TLS settings of the configuration are consistent.
+    ///
+    /// # Returns
+    /// An error if the self-signed certificate could not be generated, if a TLS port is set
+    /// without a certificate or equal to the HTTP port, if client authentication is enabled
+    /// without a CA certificate, or if oversized bodies should reset the connection.
+    pub fn validate(&self) -> Result<(), EasyHttpMockError> {
+        let error = |message: &str| {
+            Err(EasyHttpMockError::Server(ServerError::Config(message.to_string())))
+        };
+
+        #[cfg(feature = "self-signed")]
+        if let Some(Err(e)) = &self.self_signed_tls {
+            return Err(EasyHttpMockError::Server(e.clone()));
+        }
+
+        match self.tls_port {
+            Some(_) if !self.is_tls() => return error("TLS port requires a certificate and a key"),
+            Some(tls_port) if tls_port == self.port => {
+                return error("TLS port must differ from the HTTP port")
+            }
+            _ => {}
+        }
+
+        if self.is_tls()
+            && self
+                .client_auth
+                .is_enabled()
+            && self.ca.is_none()
+        {
+            return error("Client authentication requires a CA certificate");
+        }
+
+        if self.oversized_body == OversizedBody::Reset {
+            return error("Vetis cannot reset connections, oversized bodies must be rejected");
+        }
+
+        Ok(())
+    }
+
+    /// Returns the ports to listen on, each with whether it serves HTTPS.
+    ///
+    /// # Returns
+    /// The ports of the listeners or an error if the configuration is not valid.
+    pub fn listeners(&self) -> Result<Vec<(u16, bool)>, EasyHttpMockError> {
+        self.validate()?;
+
+        Ok(self
+            .http_port()
+            .map(|port| (port, false))
+            .into_iter()
+            .chain(
+                self.https_port()
+                    .map(|port| (port, true)),
+            )
+            .collect())
+    }
+}
@@ -0,0 +1,685 @@
+/// Generates the conformance test suite every Vetis adapter runs.
+///
+/// The tests are expanded in a `conformance` module of the calling test module, which must
+/// define the `CA_CERT`, `SERVER_CERT` and `SERVER_KEY` constants, and the `CLIENT_CERT` and
+/// `CLIENT_KEY` constants with the `mtls` feature, and depend on `deboa` and the Deboa client of
+/// the runtime.
+///
+/// # Arguments
+/// * `test` - The attribute running an async test on the runtime.
+/// * `client` - The Deboa client crate of the runtime, such as `deboa_tokio`.
+/// * `sleep` - The async function of the runtime sleeping for a `Duration`.
+///
+/// # Examples
+///
+/// ```rust,ignore
+/// easyhttpmock_vetis::conformance_tests!(#[tokio::test], deboa_tokio, tokio::time::sleep);
+/// ```
+#[macro_export]
+macro_rules! conformance_tests {
+    (#[$test:meta], $client:ident, $sleep:path) => {
+        mod conformance {
+            use super::{CA_CERT, SERVER_CERT, SERVER_KEY};
+            use crate::vetis_adapter::{VetisAdapter, VetisAdapterConfig};
+            use deboa::{
+                cert::{CertificateExt as _, ContentEncoding},
+                request::{get, post},
+                HttpClient, HttpVersion,
+            };
+            use std::{error::Error, net::TcpListener, time::Duration};
+            use $client::{cert::DeboaCertificate, Client};
+            use $crate::__private::{
+                easyhttpmock::{
+                    config::EasyHttpMockConfig,
+                    matchers::{method, path, version},
+                    mock::{given, AsyncMatcherExt, Mock, StatusCodeExt},
+                    server::{OversizedBody, PortGenerator},
+                    EasyHttpMock,
+                },
+                http::{StatusCode, Version},
+            };
+
+            fn tls_client() -> Client {
+                Client::builder()
+                    .certificate(DeboaCertificate::from_slice(CA_CERT, ContentEncoding::DER))
+                    .build()
+            }
+
+            /// Starts a mock answering `/test` with `200 OK` on the given configuration, then
+            /// sends a GET request with `client` to the URL returned by `url`.
+            async fn assert_request(
+                vetis_adapter_config: VetisAdapterConfig,
+                client: Client,
+                url: fn(&EasyHttpMock<VetisAdapter>) -> String,
+            ) -> Result<(), Box<dyn Error>> {
+                let protocol_version = vetis_adapter_config.protocol_version();
+
+                let config = EasyHttpMockConfig::<VetisAdapter>::builder()
+                    .server_config(vetis_adapter_config)
+                    .build();
+
+                let Ok(mut server) = EasyHttpMock::new(config) else {
+                    panic!("Failed to create mock server");
+                };
+
+                Mock::of(
+                    given(
+                        path("/test")
+                            .and(method("GET"))
+                            .and(version(protocol_version)),
+                    )
+                    .will_return(
+                        StatusCode::OK
+                            .respond()
+                            .with_body(b"teste"),
+                    ),
+                )
+                .use_on(&mut server)
+                .await?;
+
+                let request = get(url(&server))?.build()?;
+                let response = client
+                    .execute(request)
+                    .await?;
+
+                assert_eq!(response.status(), StatusCode::OK);
+                assert!(server
+                    .journal()
+                    .entries()
+                    .iter()
+                    .all(|entry| entry.is_matched()));
+
+                server
+                    .stop()
+                    .await?;
+
+                Ok(())
+            }
+
+            #[$test]
+            async fn test_mock_request() -> Result<(), Box<dyn Error>> {
+                let vetis_adapter_config = VetisAdapterConfig::builder()
+                    .protocol_version(Version::HTTP_2)
+                    .with_random_port()
+                    .cert(SERVER_CERT.to_vec())
+                    .key(SERVER_KEY.to_vec())
+                    .ca(CA_CERT.to_vec())
+                    .build();
+
+                assert_request(vetis_adapter_config, tls_client(), |server| server.url("/test"))
+                    .await
+            }
+
+            #[$test]
+            async fn test_respond_status_and_headers() -> Result<(), Box<dyn Error>> {
+                let vetis_adapter_config = VetisAdapterConfig::builder()
+                    .with_random_port()
+                    .build();
+
+                let config = EasyHttpMockConfig::<VetisAdapter>::builder()
+                    .server_config(vetis_adapter_config)
+                    .build();
+
+                let Ok(mut server) = EasyHttpMock::new(config) else {
+                    panic!("Failed to create mock server");
+                };
+
+                Mock::of(
+                    given(path("/test")).will_return(
+                        StatusCode::CREATED
+                            .respond()
+                            .with_header("x-mock", "conformance")
+                            .with_body(b"created"),
+                    ),
+                )
+                .use_on(&mut server)
+                .await?;
+
+                let request = get(server.url("/test"))?.build()?;
+                let response = Client::builder()
+                    .build()
+                    .execute(request)
+                    .await?;
+
+                assert_eq!(response.status(), StatusCode::CREATED);
+                assert_eq!(
+                    response
+                        .headers()
+                        .get("x-mock")
+                        .and_then(|value| value.to_str().ok()),
+                    Some("conformance")
+                );
+                assert_eq!(
+                    server
+                        .journal()
+                        .len(),
+                    1
+                );
+
+                server
+                    .stop()
+                    .await?;
+
+                Ok(())
+            }
+
+            #[$test]
+            async fn test_mock_request_over_http_and_https() -> Result<(), Box<dyn Error>> {
+                let vetis_adapter_config = VetisAdapterConfig::builder()
+                    .with_random_port()
+                    .with_random_tls_port()
+                    .cert(SERVER_CERT.to_vec())
+                    .key(SERVER_KEY.to_vec())
+                    .build();
+
+                let config = EasyHttpMockConfig::<VetisAdapter>::builder()
+                    .server_config(vetis_adapter_config)
+                    .build();
+
+                let Ok(mut server) = EasyHttpMock::new(config) else {
+                    panic!("Failed to create mock server");
+                };
+
+                Mock::of(
+                    given(path("/test").and(method("GET"))).will_return(
+                        StatusCode::OK
+                            .respond()
+                            .with_body(b"teste"),
+                    ),
+                )
+                .use_on(&mut server)
+                .await?;
+
+                let client = tls_client();
+                for url in [server.http_url("/test"), server.https_url("/test")] {
+                    let request = get(url)?.build()?;
+                    let response = client
+                        .execute(request)
+                        .await?;
+
+                    assert_eq!(response.status(), StatusCode::OK);
+                }
+
+                assert_eq!(
+                    server
+                        .journal()
+                        .len(),
+                    2
+                );
+
+                server
+                    .stop()
+                    .await?;
+
+                Ok(())
+            }
+
+            #[$test]
+            async fn test_listener_ready_once_started() -> Result<(), Box<dyn Error>> {
+                let vetis_adapter_config = VetisAdapterConfig::builder()
+                    .with_random_port()
+                    .build();
+                let port = vetis_adapter_config.port();
+
+                let config = EasyHttpMockConfig::<VetisAdapter>::builder()
+                    .server_config(vetis_adapter_config)
+                    .build();
+
+                let Ok(mut server) = EasyHttpMock::new(config) else {
+                    panic!("Failed to create mock server");
+                };
+
+                Mock::of(
+                    given(path("/test")).will_return(
+                        StatusCode::OK
+                            .respond()
+                            .empty(),
+                    ),
+                )
+                .use_on(&mut server)
+                .await?;
+
+                assert!(std::net::TcpStream::connect(("127.0.0.1", port)).is_ok());
+
+                server
+                    .stop()
+                    .await?;
+
+                Ok(())
+            }
+
+            #[$test]
+            async fn test_tls_port_requires_certificate() -> Result<(), Box<dyn Error>> {
+                let vetis_adapter_config = VetisAdapterConfig::builder()
+                    .with_random_port()
+                    .with_random_tls_port()
+                    .build();
+
+                let config = EasyHttpMockConfig::<VetisAdapter>::builder()
+                    .server_config(vetis_adapter_config)
+                    .build();
+
+                let Ok(mut server) = EasyHttpMock::new(config) else {
+                    panic!("Failed to create mock server");
+                };
+
+                let result = Mock::of(
+                    given(path("/test")).will_return(
+                        StatusCode::OK
+                            .respond()
+                            .empty(),
+                    ),
+                )
+                .use_on(&mut server)
+                .await;
+
+                assert!(result.is_err());
+
+                Ok(())
+            }
+
+            #[$test]
+            async fn test_h2c_request() -> Result<(), Box<dyn Error>> {
+                let vetis_adapter_config = VetisAdapterConfig::builder()
+                    .protocol_version(Version::HTTP_2)
+                    .with_random_port()
+                    .build();
+
+                let client = Client::builder()
+                    .protocol(HttpVersion::Http2)
+                    .build();
+
+                assert_request(vetis_adapter_config, client, |server| server.http_url("/test"))
+                    .await
+            }
+
+            #[$test]
+            async fn test_h2_over_tls_request() -> Result<(), Box<dyn Error>> {
+                let vetis_adapter_config = VetisAdapterConfig::builder()
+                    .protocol_version(Version::HTTP_2)
+                    .with_random_port()
+                    .cert(SERVER_CERT.to_vec())
+                    .key(SERVER_KEY.to_vec())
+                    .build();
+
+                assert_request(vetis_adapter_config, tls_client(), |server| {
+                    server.https_url("/test")
+                })
+                .await
+            }
+
+            #[cfg(feature = "http3")]
+            #[$test]
+            async fn test_h3_request() -> Result<(), Box<dyn Error>> {
+                let vetis_adapter_config = VetisAdapterConfig::builder()
+                    .protocol_version(Version::HTTP_3)
+                    .interface("127.0.0.1")
+                    .with_random_port()
+                    .cert(SERVER_CERT.to_vec())
+                    .key(SERVER_KEY.to_vec())
+                    .build();
+
+                let client = Client::builder()
+                    .protocol(HttpVersion::Http3)
+                    .certificate(DeboaCertificate::from_slice(CA_CERT, ContentEncoding::DER))
+                    .build();
+
+                assert_request(vetis_adapter_config, client, |server| server.https_url("/test"))
+                    .await
+            }
+
+            #[$test]
+            async fn test_oversized_body_rejected() -> Result<(), Box<dyn Error>> {
+                let vetis_adapter_config = VetisAdapterConfig::builder()
+                    .with_random_port()
+                    .max_body_size(8)
+                    .max_journal_body_size(4)
+                    .build();
+
+                let config = EasyHttpMockConfig::<VetisAdapter>::builder()
+                    .server_config(vetis_adapter_config)
+                    .build();
+
+                let Ok(mut server) = EasyHttpMock::new(config) else {
+                    panic!("Failed to create mock server");
+                };
+
+                Mock::of(
+                    given(path("/upload")).will_return(
+                        StatusCode::OK
+                            .respond()
+                            .empty(),
+                    ),
+                )
+                .use_on(&mut server)
+                .await?;
+
+                let client = Client::builder().build();
+
+                let request = post(server.url("/upload"))?
+                    .raw_body(b"0123456789")
+                    .build()?;
+                let response = client
+                    .execute(request)
+                    .await?;
+                assert_eq!(response.status(), StatusCode::PAYLOAD_TOO_LARGE);
+                assert!(server
+                    .journal()
+                    .is_empty());
+
+                let request = post(server.url("/upload"))?
+                    .raw_body(b"012345")
+                    .build()?;
+                let response = client
+                    .execute(request)
+                    .await?;
+                assert_eq!(response.status(), StatusCode::OK);
+
+                let entries = server
+                    .journal()
+                    .entries();
+                assert_eq!(entries[0].truncated_bytes(), 2);
+
+                server
+                    .stop()
+                    .await?;
+
+                Ok(())
+            }
+
+            #[$test]
+            async fn test_oversized_body_reset_unsupported() -> Result<(), Box<dyn Error>> {
+                let vetis_adapter_config = VetisAdapterConfig::builder()
+                    .with_random_port()
+                    .max_body_size(8)
+                    .oversized_body(OversizedBody::Reset)
+                    .build();
+                assert!(vetis_adapter_config
+                    .validate()
+                    .is_err());
+
+                let config = EasyHttpMockConfig::<VetisAdapter>::builder()
+                    .server_config(vetis_adapter_config)
+                    .build();
+
+                let Ok(mut server) = EasyHttpMock::new(config) else {
+                    panic!("Failed to create mock server");
+                };
+
+                let result = Mock::of(
+                    given(path("/upload")).will_return(
+                        StatusCode::OK
+                            .respond()
+                            .empty(),
+                    ),
+                )
+                .use_on(&mut server)
+                .await;
+
+                assert!(result.is_err());
+
+                Ok(())
+            }
+
+            #[cfg(feature = "mtls")]
+            #[$test]
+            async fn test_client_certificate_required() -> Result<(), Box<dyn Error>> {
+                use $client::cert::DeboaIdentity;
+                use $crate::__private::easyhttpmock::{matchers::client_cert, tls::ClientAuth};
+
+                let vetis_adapter_config = VetisAdapterConfig::builder()
+                    .with_random_port()
+                    .cert(SERVER_CERT.to_vec())
+                    .key(SERVER_KEY.to_vec())
+                    .ca(CA_CERT.to_vec())
+                    .client_auth(ClientAuth::Required)
+                    .build();
+
+                let config = EasyHttpMockConfig::<VetisAdapter>::builder()
+                    .server_config(vetis_adapter_config)
+                    .build();
+
+                let Ok(mut server) = EasyHttpMock::new(config) else {
+                    panic!("Failed to create mock server");
+                };
+
+                Mock::of(
+                    given(path("/test").and(client_cert().subject_cn("testuser"))).will_return(
+                        StatusCode::OK
+                            .respond()
+                            .empty(),
+                    ),
+                )
+                .use_on(&mut server)
+                .await?;
+
+                let client = Client::builder()
+                    .certificate(DeboaCertificate::from_slice(CA_CERT, ContentEncoding::DER))
+                    .identity(DeboaIdentity::from_slice(
+                        super::CLIENT_CERT,
+                        super::CLIENT_KEY,
+                        ContentEncoding::DER,
+                    ))
+                    .build();
+
+                let request = get(server.url("/test"))?.build()?;
+                let response = client
+                    .execute(request)
+                    .await?;
+
+                assert_eq!(response.status(), StatusCode::OK);
+                assert_eq!(
+                    server
+                        .journal()
+                        .entries()[0]
+                        .client_certificate()
+                        .map(|certificate| certificate
+                            .der()
+                            .to_vec()),
+                    Some(super::CLIENT_CERT.to_vec())
+                );
+
+                let request = get(server.url("/test"))?.build()?;
+                let anonymous = tls_client()
+                    .execute(request)
+                    .await;
+
+                assert!(anonymous.is_err());
+                assert_eq!(
+                    server
+                        .journal()
+                        .len(),
+                    1
+                );
+
+                server
+                    .stop()
+                    .await?;
+
+                Ok(())
+            }
+
+            #[$test]
+            async fn test_shutdown_on_drop() -> Result<(), Box<dyn Error>> {
+                let vetis_adapter_config = VetisAdapterConfig::builder()
+                    .with_random_port()
+                    .build();
+                let port = vetis_adapter_config.port();
+
+                let config = EasyHttpMockConfig::<VetisAdapter>::builder()
+                    .server_config(vetis_adapter_config)
+                    .build();
+
+                let Ok(mut server) = EasyHttpMock::new(config) else {
+                    panic!("Failed to create mock server");
+                };
+
+                Mock::of(
+                    given(path("/test")).will_return(
+                        StatusCode::OK
+                            .respond()
+                            .empty(),
+                    ),
+                )
+                .use_on(&mut server)
+                .await?;
+                assert!(TcpListener::bind(("127.0.0.1", port)).is_err());
+
+                drop(server);
+
+                let mut released = false;
+                for _ in 0..100 {
+                    if TcpListener::bind(("127.0.0.1", port)).is_ok() {
+                        released = true;
+                        break;
+                    }
+                    $sleep(Duration::from_millis(10)).await;
+                }
+                assert!(released);
+
+                Ok(())
+            }
+
+            #[cfg(feature = "gzip")]
+            #[$test]
+            async fn test_compressed_request_body() -> Result<(), Box<dyn Error>> {
+                use $crate::__private::{
+                    easyhttpmock::{
+                        compression::Encoding,
+                        matchers::{body, content_encoding},
+                    },
+                    http::header::CONTENT_ENCODING,
+                };
+
+                let vetis_adapter_config = VetisAdapterConfig::builder()
+                    .with_random_port()
+                    .build();
+
+                let config = EasyHttpMockConfig::<VetisAdapter>::builder()
+                    .server_config(vetis_adapter_config)
+                    .build();
+
+                let Ok(mut server) = EasyHttpMock::new(config) else {
+                    panic!("Failed to create mock server");
+                };
+
+                Mock::of(
+                    given(
+                        path("/upload")
+                            .and(content_encoding(Encoding::Gzip))
+                            .and(body("^hello$")),
+                    )
+                    .will_return(
+                        StatusCode::OK
+                            .respond()
+                            .empty(),
+                    ),
+                )
+                .use_on(&mut server)
+                .await?;
+
+                let gzipped = Encoding::Gzip.encode(b"hello")?;
+                let request = post(server.url("/upload"))?
+                    .header(CONTENT_ENCODING, "gzip")
+                    .raw_body(&gzipped)
+                    .build()?;
+                let response = Client::builder()
+                    .build()
+                    .execute(request)
+                    .await?;
+
+                assert_eq!(response.status(), StatusCode::OK);
+                assert_eq!(
+                    server
+                        .journal()
+                        .entries()[0]
+                        .request()
+                        .raw_body()
+                        .map(|raw_body| raw_body.to_vec()),
+                    Some(gzipped)
+                );
+
+                server
+                    .stop()
+                    .await?;
+
+                Ok(())
+            }
+
+            #[cfg(feature = "gzip")]
+            #[$test]
+            async fn test_compressed_respond() -> Result<(), Box<dyn Error>> {
+                use $crate::__private::{
+                    easyhttpmock::compression::Encoding, http::header::CONTENT_ENCODING,
+                };
+
+                let vetis_adapter_config = VetisAdapterConfig::builder()
+                    .with_random_port()
+                    .build();
+
+                let config = EasyHttpMockConfig::<VetisAdapter>::builder()
+                    .server_config(vetis_adapter_config)
+                    .build();
+
+                let Ok(mut server) = EasyHttpMock::new(config) else {
+                    panic!("Failed to create mock server");
+                };
+
+                Mock::of(
+                    given(path("/test")).will_return(
+                        StatusCode::OK
+                            .respond()
+                            .compressed(Encoding::Gzip)
+                            .with_body(b"hello"),
+                    ),
+                )
+                .use_on(&mut server)
+                .await?;
+
+                let request = get(server.url("/test"))?.build()?;
+                let response = Client::builder()
+                    .build()
+                    .execute(request)
+                    .await?;
+
+                assert_eq!(response.status(), StatusCode::OK);
+                assert_eq!(
+                    response
+                        .headers()
+                        .get(CONTENT_ENCODING)
+                        .and_then(|value| value.to_str().ok()),
+                    Some("gzip")
+                );
+
+                server
+                    .stop()
+                    .await?;
+
+                Ok(())
+            }
+
+            #[cfg(feature = "self-signed")]
+            #[$test]
+            async fn test_mock_request_with_self_signed_tls() -> Result<(), Box<dyn Error>> {
+                let vetis_adapter_config = VetisAdapterConfig::builder()
+                    .protocol_version(Version::HTTP_2)
+                    .with_random_port()
+                    .with_self_signed_tls()
+                    .build();
+
+                let Some(tls) = vetis_adapter_config.self_signed_tls() else {
+                    panic!("Self-signed certificate was not generated");
+                };
+                let ca_cert = tls
+                    .ca_der()
+                    .to_vec();
+                let client = Client::builder()
+                    .certificate(DeboaCertificate::from_slice(&ca_cert, ContentEncoding::DER))
+                    .build();
+
+                assert_request(vetis_adapter_config, client, |server| server.https_url("/test"))
+                    .await
+            }
+        }
+    };
+}
@@ -0,0 +1,75 @@
+use easyhttpmock::{
+    errors::RequestError,
+    journal::Journal,
+    mock::{Mock, Request, Respond},
+    server::{collect_body, handle_request, payload_too_large},
+    tls::ClientCertificate,
+};
+use http::{request::Parts, HeaderName, HeaderValue};
+use http_body::Body;
+
+/// Handles a request received by a Vetis listener, whatever the runtime.
+///
+/// The body is collected up to the maximum size, bodies exceeding it, before or after being
+/// decoded, are answered with `413 Payload Too Large` without reaching the mock or the journal.
+///
+/// # Arguments
+/// * `mock` - The mock handling the request.
+/// * `journal` - The journal recording the request.
+/// * `parts` - The head of the request.
+/// * `body` - The body of the request.
+/// * `max_body_size` - The maximum size of the body, `None` for no limit.
+/// * `client_certificate` - The certificate the client presented during the TLS handshake.
+///
+/// # Returns
+/// The respond to send or an error if the body could not be read or the mock has no respond.
+pub async fn respond<B>(
+    mock: &Mock,
+    journal: &Journal,
+    parts: Parts,
+    body: B,
+    max_body_size: Option<usize>,
+    client_certificate: Option<ClientCertificate>,
+) -> Result<Respond, RequestError>
+where
+    B: Body,
+    B::Error: Into<Box<dyn std::error::Error + Send + Sync>>,
+{
+    // Encoded bodies are limited once decoded too, small bodies may expand to huge ones
+    let request = match collect_body(body, max_body_size)
+        .await
+        .and_then(|body| Request::from_parts(parts).with_limited_body(body, max_body_size))
+    {
+        Ok(request) => request,
+        Err(RequestError::BodyTooLarge(limit)) => return Ok(payload_too_large(limit)),
+        Err(e) => return Err(e),
+    };
+
+    let request = match client_certificate {
+        Some(certificate) => request.with_client_certificate(certificate),
+        None => request,
+    };
+
+    handle_request(mock, journal, request)
+        .ok_or_else(|| RequestError::Failed("Missing respond mock".to_string()))
+}
+
+/// Returns the headers of a respond as HTTP headers, skipping the invalid ones.
+///
+/// # Arguments
+/// * `respond` - The mocked respond.
+///
+/// # Returns
+/// The names and values of the headers.
+pub fn response_headers(respond: &Respond) -> Vec<(HeaderName, HeaderValue)> {
+    respond
+        .headers()
+        .iter()
+        .filter_map(|(name, value)| {
+            Some((
+                HeaderName::from_bytes(name.as_bytes()).ok()?,
+                HeaderValue::from_str(value).ok()?,
+            ))
+        })
+        .collect()
+}
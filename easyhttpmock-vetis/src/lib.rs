@@ -0,0 +1,16 @@
+#![doc = include_str!("../README.md")]
+#![deny(missing_docs)]
+/// Vetis adapter generation module
+mod adapter;
+/// Vetis adapter configuration module
+pub mod config;
+/// Conformance test suite module
+mod conformance;
+/// Runtime independent request handling module
+pub mod handler;
+
+#[doc(hidden)]
+pub mod __private {
+    pub use easyhttpmock;
+    pub use http;
+}
@@ -0,0 +1,345 @@
+use crate::errors::RequestError;
+use bytes::Bytes;
+use http::{
+    header::{ACCEPT_ENCODING, CONTENT_ENCODING},
+    HeaderMap,
+};
+use std::{fmt, io, str::FromStr};
+
+/// Content coding of an HTTP body, as listed in the `Content-Encoding` header
+///
+/// Decoding or encoding a body requires the cargo feature of the same name: `gzip`, `deflate`, `brotli`
+/// or `zstd`, all enabled by the `compression` feature.
+///
+/// # Examples
+///
+/// ```rust
+/// use easyhttpmock::compression::Encoding;
+///
+/// let encoding: Encoding = "br".parse().unwrap();
+/// assert_eq!(encoding, Encoding::Brotli);
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Encoding {
+    /// `gzip`, also accepted as `x-gzip`
+    Gzip,
+    /// `deflate`, the zlib format
+    Deflate,
+    /// `br`
+    Brotli,
+    /// `zstd`
+    Zstd,
+}
+
+/// Every encoding, in the order `*` in `Accept-Encoding` prefers them
+const ALL: [Encoding; 4] = [Encoding::Zstd, Encoding::Brotli, Encoding::Gzip, Encoding::Deflate];
+
+impl Encoding {
+    /// Get the name of the encoding in the `Content-Encoding` header
+    pub fn name(&self) -> &'static str {
+        match self {
+            Encoding::Gzip => "gzip",
+            Encoding::Deflate => "deflate",
+            Encoding::Brotli => "br",
+            Encoding::Zstd => "zstd",
+        }
+    }
+
+    /// Check whether the cargo feature decoding and encoding the encoding is enabled
+    pub fn is_enabled(&self) -> bool {
+        match self {
+            Encoding::Gzip => cfg!(feature = "gzip"),
+            Encoding::Deflate => cfg!(feature = "deflate"),
+            Encoding::Brotli => cfg!(feature = "brotli"),
+            Encoding::Zstd => cfg!(feature = "zstd"),
+        }
+    }
+
+    /// Decode a body encoded with this encoding
+    ///
+    /// # Arguments
+    ///
+    /// * `body` - The encoded body
+    ///
+    /// # Returns
+    ///
+    /// * `io::Result<Vec<u8>>` - The decoded body, or an error if the body is invalid or the
+    ///   feature of the encoding is not enabled
+    pub fn decode(&self, body: &[u8]) -> io::Result<Vec<u8>> {
+        self.decode_up_to(body, None)
+    }
+
+    /// Decode a body encoded with this encoding, stopping one byte past the limit
+    ///
+    /// A decoded body longer than the limit exceeds it, decoding stops there so small bodies
+    /// expanding to huge ones are never decoded entirely.
+    fn decode_up_to(&self, body: &[u8], limit: Option<usize>) -> io::Result<Vec<u8>> {
+        match self {
+            #[cfg(feature = "gzip")]
+            Encoding::Gzip => read_all(flate2::read::GzDecoder::new(body), limit),
+            #[cfg(feature = "deflate")]
+            Encoding::Deflate => read_all(flate2::read::ZlibDecoder::new(body), limit),
+            #[cfg(feature = "brotli")]
+            Encoding::Brotli => read_all(brotli::Decompressor::new(body, 4096), limit),
+            #[cfg(feature = "zstd")]
+            Encoding::Zstd => read_all(zstd::stream::read::Decoder::new(body)?, limit),
+            #[allow(unreachable_patterns)]
+            _ => {
+                let _ = (body, limit);
+                Err(io::Error::new(
+                    io::ErrorKind::Unsupported,
+                    format!("{} decoding is not enabled", self),
+                ))
+            }
+        }
+    }
+
+    /// Encode a body with this encoding
+    ///
+    /// # Arguments
+    ///
+    /// * `body` - The body to encode
+    ///
+    /// # Returns
+    ///
+    /// * `io::Result<Vec<u8>>` - The encoded body, or an error if the feature of the encoding is
+    ///   not enabled
+    pub fn encode(&self, body: &[u8]) -> io::Result<Vec<u8>> {
+        match self {
+            #[cfg(feature = "gzip")]
+            Encoding::Gzip => write_all(
+                flate2::write::GzEncoder::new(Vec::new(), flate2::Compression::default()),
+                body,
+            )?
+            .finish(),
+            #[cfg(feature = "deflate")]
+            Encoding::Deflate => write_all(
+                flate2::write::ZlibEncoder::new(Vec::new(), flate2::Compression::default()),
+                body,
+            )?
+            .finish(),
+            #[cfg(feature = "brotli")]
+            Encoding::Brotli => {
+                Ok(write_all(brotli::CompressorWriter::new(Vec::new(), 4096, 5, 22), body)?
+                    .into_inner())
+            }
+            #[cfg(feature = "zstd")]
+            Encoding::Zstd => zstd::stream::encode_all(body, 0),
+            #[allow(unreachable_patterns)]
+            _ => {
+                let _ = body;
+                Err(io::Error::new(
+                    io::ErrorKind::Unsupported,
+                    format!("{} encoding is not enabled", self),
+                ))
+            }
+        }
+    }
+}
+
+impl fmt::Display for Encoding {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+impl FromStr for Encoding {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value
+            .trim()
+            .to_ascii_lowercase()
+            .as_str()
+        {
+            "gzip" | "x-gzip" => Ok(Encoding::Gzip),
+            "deflate" => Ok(Encoding::Deflate),
+            "br" => Ok(Encoding::Brotli),
+            "zstd" => Ok(Encoding::Zstd),
+            other => Err(format!("Unknown content encoding {}", other)),
+        }
+    }
+}
+
+/// Get the content codings listed in the `Content-Encoding` headers, in the order they were
+/// applied
+///
+/// # Arguments
+///
+/// * `headers` - The headers of the request
+///
+/// # Returns
+///
+/// * `Vec<String>` - The lowercase names of the codings, without `identity`
+pub fn content_encodings(headers: &HeaderMap) -> Vec<String> {
+    headers
+        .get_all(CONTENT_ENCODING)
+        .iter()
+        .filter_map(|value| value.to_str().ok())
+        .flat_map(|value| value.split(','))
+        .map(|coding| {
+            coding
+                .trim()
+                .to_ascii_lowercase()
+        })
+        .filter(|coding| !coding.is_empty() && coding != "identity")
+        .collect()
+}
+
+/// Pick the encoding to compress a response with from the `Accept-Encoding` headers
+///
+/// The enabled encoding with the highest quality value is picked, the first one listed on a tie.
+/// A `*` stands for every enabled encoding not listed, and encodings with a quality of `0` are
+/// refused.
+///
+/// # Arguments
+///
+/// * `headers` - The headers of the request
+///
+/// # Returns
+///
+/// * `Option<Encoding>` - The encoding to use, `None` when the request accepts none of the enabled
+///   encodings
+pub fn negotiate_encoding(headers: &HeaderMap) -> Option<Encoding> {
+    let accepted: Vec<(String, f32)> = headers
+        .get_all(ACCEPT_ENCODING)
+        .iter()
+        .filter_map(|value| value.to_str().ok())
+        .flat_map(|value| value.split(','))
+        .filter_map(|coding| {
+            let mut parts = coding.split(';');
+            let name = parts
+                .next()?
+                .trim()
+                .to_ascii_lowercase();
+            let quality = parts
+                .filter_map(|param| {
+                    param
+                        .trim()
+                        .strip_prefix("q=")
+                })
+                .find_map(|quality| {
+                    quality
+                        .trim()
+                        .parse::<f32>()
+                        .ok()
+                })
+                .unwrap_or(1.0);
+            Some((name, quality)).filter(|(name, _)| !name.is_empty())
+        })
+        .collect();
+
+    let listed: Vec<Encoding> = accepted
+        .iter()
+        .filter_map(|(name, _)| {
+            name.parse::<Encoding>()
+                .ok()
+        })
+        .collect();
+
+    let mut best: Option<(Encoding, f32)> = None;
+    for (name, quality) in &accepted {
+        let candidates: Vec<Encoding> = if name == "*" {
+            ALL.iter()
+                .filter(|encoding| !listed.contains(encoding))
+                .copied()
+                .collect()
+        } else {
+            name.parse::<Encoding>()
+                .into_iter()
+                .collect()
+        };
+
+        for encoding in candidates {
+            let better = match best {
+                Some((_, best)) => *quality > best,
+                None => true,
+            };
+            if *quality > 0.0 && encoding.is_enabled() && better {
+                best = Some((encoding, *quality));
+            }
+        }
+    }
+
+    best.map(|(encoding, _)| encoding)
+}
+
+/// Decode a body according to the `Content-Encoding` headers
+///
+/// # Arguments
+///
+/// * `headers` - The headers of the request
+/// * `body` - The body as received
+/// * `limit` - The maximum size of the decoded body, `None` for no limit
+///
+/// # Returns
+///
+/// * `Result<Option<Bytes>, RequestError>` - The decoded body, `None` when the body is not
+///   encoded, one of its encodings is unknown or not enabled, or it could not be decoded, and
+///   `RequestError::BodyTooLarge` when the decoded body exceeds the limit
+pub(crate) fn decode_body(
+    headers: &HeaderMap,
+    body: &Bytes,
+    limit: Option<usize>,
+) -> Result<Option<Bytes>, RequestError> {
+    let encodings = content_encodings(headers);
+    if encodings.is_empty() {
+        return Ok(None);
+    }
+
+    let mut decoded = body.to_vec();
+    for coding in encodings
+        .iter()
+        .rev()
+    {
+        let Ok(encoding) = coding.parse::<Encoding>() else {
+            return Ok(None);
+        };
+        let Ok(body) = encoding.decode_up_to(&decoded, limit) else {
+            return Ok(None);
+        };
+        if let Some(limit) = limit.filter(|limit| body.len() > *limit) {
+            return Err(RequestError::BodyTooLarge(limit));
+        }
+        decoded = body;
+    }
+
+    Ok(Some(Bytes::from(decoded)))
+}
+
+/// Read a decoder to the end, or to one byte past the limit
+///
+/// # Arguments
+///
+/// * `reader` - The decoder
+/// * `limit` - The maximum size of the decoded bytes, `None` for no limit
+///
+/// # Returns
+///
+/// * `io::Result<Vec<u8>>` - The decoded bytes
+#[cfg(any(feature = "gzip", feature = "deflate", feature = "brotli", feature = "zstd"))]
+fn read_all(reader: impl io::Read, limit: Option<usize>) -> io::Result<Vec<u8>> {
+    use io::Read;
+
+    let mut decoded = Vec::new();
+    reader
+        .take(limit.map_or(u64::MAX, |limit| limit as u64 + 1))
+        .read_to_end(&mut decoded)?;
+    Ok(decoded)
+}
+
+/// Write a whole body to an encoder
+///
+/// # Arguments
+///
+/// * `writer` - The encoder
+/// * `body` - The body to encode
+///
+/// # Returns
+///
+/// * `io::Result<W>` - The encoder, to finish
+#[cfg(any(feature = "gzip", feature = "deflate", feature = "brotli"))]
+fn write_all<W: io::Write>(mut writer: W, body: &[u8]) -> io::Result<W> {
+    writer.write_all(body)?;
+    Ok(writer)
+}
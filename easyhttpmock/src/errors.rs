@@ -63,4 +63,7 @@ pub enum RequestError {
     /// Invalid header
     #[error("Invalid header, expected {0}")]
     InvalidHeader(String),
+    /// Request body too large
+    #[error("Request body exceeds {0} bytes")]
+    BodyTooLarge(usize),
 }
@@ -0,0 +1,193 @@
+use crate::{
+    errors::{MockError, RequestError},
+    matchers::{Mismatch, PathParams},
+    mock::{Request, RequestMock},
+    tls::ClientCertificate,
+};
+use std::sync::{Arc, Mutex};
+
+/// Journal of the requests received by a mock server
+///
+/// Cloning a journal is cheap, every clone records into the same entries.
+///
+/// # Examples
+///
+/// ```rust,ignore
+/// let entries = server.journal().entries();
+/// assert_eq!(entries[0].path_params().get("id"), Some(&"42".to_string()));
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct Journal {
+    entries: Arc<Mutex<Vec<JournalEntry>>>,
+    body_limit: Option<usize>,
+}
+
+impl Journal {
+    /// Creates an empty journal
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates an empty journal truncating the recorded bodies to the given size
+    ///
+    /// Requests are matched against the whole body, only the copy kept in the journal is
+    /// truncated, followed by a marker telling how many bytes were removed.
+    ///
+    /// # Arguments
+    ///
+    /// * `limit` - The maximum size of the recorded bodies, `None` for no limit
+    pub fn with_body_limit(limit: Option<usize>) -> Self {
+        Self { body_limit: limit, ..Self::default() }
+    }
+
+    /// Records a new entry in the journal
+    ///
+    /// # Arguments
+    ///
+    /// * `entry` - The entry to record
+    pub fn record(&self, mut entry: JournalEntry) {
+        if let Some(limit) = self.body_limit {
+            entry.truncated = entry
+                .request
+                .truncate_body(limit);
+        }
+
+        if let Ok(mut entries) = self.entries.lock() {
+            entries.push(entry);
+        }
+    }
+
+    /// Returns a snapshot of the recorded entries, in the order they were received
+    ///
+    /// # Returns
+    ///
+    /// * `Vec<JournalEntry>` - The recorded entries
+    pub fn entries(&self) -> Vec<JournalEntry> {
+        self.entries
+            .lock()
+            .map(|entries| entries.clone())
+            .unwrap_or_default()
+    }
+
+    /// Returns the number of recorded entries
+    pub fn len(&self) -> usize {
+        self.entries
+            .lock()
+            .map(|entries| entries.len())
+            .unwrap_or_default()
+    }
+
+    /// Returns whether no entry was recorded
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Checks every recorded request matched the mock
+    ///
+    /// # Returns
+    ///
+    /// * `Result<(), MockError>` - An error describing the first request that did not match
+    pub fn verify(&self) -> Result<(), MockError> {
+        let entries = self.entries();
+        let Some(entry) = entries
+            .iter()
+            .find(|entry| !entry.is_matched())
+        else {
+            return Ok(());
+        };
+
+        let request = entry.request();
+        Err(RequestError::Failed(format!(
+            "{} {} did not match the mock\n{}",
+            request.method(),
+            request.path(),
+            entry
+                .mismatch
+                .as_ref()
+                .map(ToString::to_string)
+                .unwrap_or_default()
+        ))
+        .into())
+    }
+
+    /// Removes all recorded entries
+    pub fn clear(&self) {
+        if let Ok(mut entries) = self.entries.lock() {
+            entries.clear();
+        }
+    }
+}
+
+/// A request received by the mock server, along with how it was matched
+#[derive(Clone, Debug, PartialEq)]
+pub struct JournalEntry {
+    request: Request,
+    path_params: PathParams,
+    mismatch: Option<Mismatch>,
+    truncated: usize,
+}
+
+impl JournalEntry {
+    /// Creates a new entry by matching the request against the given mock
+    ///
+    /// # Arguments
+    ///
+    /// * `request` - The received request
+    /// * `mock` - The mock the request was matched against
+    ///
+    /// # Returns
+    ///
+    /// * `JournalEntry` - The journal entry
+    pub fn new(request: Request, mock: &RequestMock) -> Self {
+        let mismatch = mock.explain(&request);
+        let path_params =
+            if mismatch.is_none() { mock.captures(&request) } else { PathParams::new() };
+
+        Self { request, path_params, mismatch, truncated: 0 }
+    }
+
+    #[inline]
+    /// Get the received request
+    pub fn request(&self) -> &Request {
+        &self.request
+    }
+
+    #[inline]
+    /// Get the certificate the client presented during the TLS handshake
+    pub fn client_certificate(&self) -> Option<&ClientCertificate> {
+        self.request
+            .client_certificate()
+    }
+
+    #[inline]
+    /// Get the path parameters captured from the request
+    pub fn path_params(&self) -> &PathParams {
+        &self.path_params
+    }
+
+    #[inline]
+    /// Get why the request did not match, if it did not
+    pub fn mismatch(&self) -> Option<&Mismatch> {
+        self.mismatch
+            .as_ref()
+    }
+
+    #[inline]
+    /// Check whether the request matched the mock
+    pub fn is_matched(&self) -> bool {
+        self.mismatch
+            .is_none()
+    }
+
+    #[inline]
+    /// Get the number of bytes removed from the recorded body, 0 when it was not truncated
+    pub fn truncated_bytes(&self) -> usize {
+        self.truncated
+    }
+
+    #[inline]
+    /// Check whether the recorded body was truncated
+    pub fn is_truncated(&self) -> bool {
+        self.truncated > 0
+    }
+}
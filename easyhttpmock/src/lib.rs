@@ -1,24 +1,40 @@
 #![doc = include_str!("../README.md")]
 #![deny(missing_docs)]
 use crate::{
-    config::EasyHttpMockConfig, errors::EasyHttpMockError, mock::MockState, server::ServerAdapter,
+    config::EasyHttpMockConfig, errors::EasyHttpMockError, journal::Journal, mock::MockState,
+    server::ServerAdapter,
 };
 use std::ops::{Deref, DerefMut};
 
+/// Content encoding module
+pub mod compression;
 /// Configuration module
 pub mod config;
 /// Error module
 pub mod errors;
+/// Journal module
+pub mod journal;
 /// Matchers module
 pub mod matchers;
 /// Mock module
 pub mod mock;
+/// Namespaces module
+pub mod namespace;
+/// OAuth2 and OpenID Connect provider module
+#[cfg(feature = "oauth")]
+pub mod oauth;
 /// Server module
 pub mod server;
+/// TLS module
+pub mod tls;
 
 #[cfg(test)]
 mod tests;
 
+/// Attribute declaring an async test receiving a mock server
+#[cfg(feature = "macros")]
+pub use easyhttpmock_macros::test;
+
 /// Result type for HTTP mock operations
 ///
 /// # Examples
@@ -42,7 +58,7 @@ where
     /// Configuration for the mock server
     config: EasyHttpMockConfig<S>,
     /// The actual server implementation
-    server: S,
+    server: ServerGuard<S>,
 }
 
 impl<S: ServerAdapter> Deref for EasyHttpMock<S> {
@@ -59,6 +75,32 @@ impl<S: ServerAdapter> DerefMut for EasyHttpMock<S> {
     }
 }
 
+/// Owns the server of a mock and stops it if the test did not, so its listeners do not outlive
+/// the mock
+///
+/// The mock itself does not implement `Drop`, so its fields can still be moved out.
+struct ServerGuard<S: ServerAdapter>(S);
+
+impl<S: ServerAdapter> Deref for ServerGuard<S> {
+    type Target = S;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl<S: ServerAdapter> DerefMut for ServerGuard<S> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
+
+impl<S: ServerAdapter> Drop for ServerGuard<S> {
+    fn drop(&mut self) {
+        self.0.shutdown();
+    }
+}
+
 impl<S: ServerAdapter> EasyHttpMock<S> {
     /// Creates a new mock with the given configuration
     ///
@@ -77,7 +119,7 @@ impl<S: ServerAdapter> EasyHttpMock<S> {
                 .clone(),
         )?;
 
-        Ok(EasyHttpMock { config, server })
+        Ok(EasyHttpMock { config, server: ServerGuard(server) })
     }
 
     /// Returns the full URL for a given path
@@ -102,6 +144,57 @@ impl<S: ServerAdapter> EasyHttpMock<S> {
         }
     }
 
+    /// Returns the full URL on the plain HTTP listener for a given path
+    ///
+    /// Servers listening on both HTTP and HTTPS share the mock and the journal between both
+    /// listeners, so a client redirected from one to the other hits the same mock.
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - The path to append to the base URL of the HTTP listener
+    ///
+    /// # Returns
+    ///
+    /// * `String` - The full HTTP URL for the given path
+    ///
+    /// # Panics
+    ///
+    /// Panics if the server does not serve plain HTTP
+    pub fn http_url(&self, path: &str) -> String {
+        let Some(base_url) = self
+            .server
+            .http_base_url()
+        else {
+            panic!("Mock server has no HTTP listener");
+        };
+
+        format!("{}{}", base_url, path)
+    }
+
+    /// Returns the full URL on the HTTPS listener for a given path
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - The path to append to the base URL of the HTTPS listener
+    ///
+    /// # Returns
+    ///
+    /// * `String` - The full HTTPS URL for the given path
+    ///
+    /// # Panics
+    ///
+    /// Panics if the server does not serve HTTPS
+    pub fn https_url(&self, path: &str) -> String {
+        let Some(base_url) = self
+            .server
+            .https_base_url()
+        else {
+            panic!("Mock server has no HTTPS listener");
+        };
+
+        format!("{}{}", base_url, path)
+    }
+
     /// Returns the base URL for the mock server
     ///
     /// # Returns
@@ -113,6 +206,17 @@ impl<S: ServerAdapter> EasyHttpMock<S> {
             .base_url()
     }
 
+    /// Returns the journal of the requests received by the mock server
+    ///
+    /// # Returns
+    ///
+    /// * `&Journal` - The journal of the mock server
+    ///
+    pub fn journal(&self) -> &Journal {
+        self.server
+            .journal()
+    }
+
     /// Starts the mock server with the given mocker function
     ///
     /// # Arguments
@@ -5,6 +5,8 @@ use caramelo::{
     Matcher, TypedMatcher,
 };
 
+use crate::matchers::{MatcherNode, Mismatch, PathParams};
+
 /// Creates a matcher that matches values that satisfy all given matchers
 ///
 /// # Examples
@@ -16,8 +18,8 @@ use caramelo::{
 /// expect("hello").to_match(and!(contains("ell"), contains("llo")));
 /// ```
 pub fn and<T: Send + Sync + 'static>(
-    matchers: Vec<Arc<dyn TypedMatcher<T> + Send + Sync + 'static>>,
-) -> Arc<dyn TypedMatcher<T> + Send + Sync + 'static> {
+    matchers: Vec<Arc<dyn MatcherNode<T> + Send + Sync + 'static>>,
+) -> Arc<dyn MatcherNode<T> + Send + Sync + 'static> {
     Arc::new(And { matchers })
 }
 
@@ -32,7 +34,7 @@ pub fn and<T: Send + Sync + 'static>(
 /// expect("hello").to_match(and!(contains("ell"), contains("llo")));
 /// ```
 pub struct And<T: Send + Sync + 'static> {
-    matchers: Vec<Arc<dyn TypedMatcher<T> + Send + Sync + 'static>>,
+    matchers: Vec<Arc<dyn MatcherNode<T> + Send + Sync + 'static>>,
 }
 
 unsafe impl<T: Send + Sync + 'static> Send for And<T> {}
@@ -40,7 +42,7 @@ unsafe impl<T: Send + Sync + 'static> Sync for And<T> {}
 
 impl<T: Send + Sync + 'static> And<T> {
     /// Creates a new And matcher with the given matchers
-    pub fn new(matchers: Vec<Arc<dyn TypedMatcher<T> + Send + Sync + 'static>>) -> Self {
+    pub fn new(matchers: Vec<Arc<dyn MatcherNode<T> + Send + Sync + 'static>>) -> Self {
         And { matchers }
     }
 }
@@ -75,3 +77,33 @@ where
             .unwrap_or(To)
     }
 }
+
+impl<T> MatcherNode<T> for And<T>
+where
+    T: Send + Sync + 'static,
+{
+    fn explain(&self, value: &T) -> Option<Mismatch> {
+        let causes = self
+            .matchers
+            .iter()
+            .filter_map(|m| m.explain(value))
+            .collect::<Vec<_>>();
+
+        if causes.is_empty() {
+            return None;
+        }
+
+        Some(Mismatch::with_causes(
+            self.description(),
+            format!("{} of {} matchers failed", causes.len(), self.matchers.len()),
+            causes,
+        ))
+    }
+
+    fn captures(&self, value: &T) -> PathParams {
+        self.matchers
+            .iter()
+            .flat_map(|m| m.captures(value))
+            .collect()
+    }
+}
@@ -0,0 +1,82 @@
+/// Pairing of expected items with actual items, each actual item serving one expected item
+#[derive(Debug, Default, PartialEq)]
+pub(crate) struct Assignment {
+    /// Indices of the expected items no actual item was assigned to
+    pub(crate) unmatched: Vec<usize>,
+    /// Indices of the actual items assigned to no expected item
+    pub(crate) unused: Vec<usize>,
+}
+
+/// Assigns actual items to expected items, matching as many expected items as possible
+///
+/// Matchers comparing collections regardless of order use this instead of a first-fit search,
+/// which fails when an actual item fitting several expected items is taken by the wrong one. The
+/// assignment is a maximum bipartite matching, found with augmenting paths.
+///
+/// # Arguments
+///
+/// * `expected` - The number of expected items
+/// * `actual` - The number of actual items
+/// * `fits` - Whether the actual item at the second index satisfies the expected item at the first
+///
+/// # Returns
+///
+/// * `Assignment` - The expected items left unmatched and the actual items left unused
+pub(crate) fn assign(
+    expected: usize,
+    actual: usize,
+    fits: impl Fn(usize, usize) -> bool,
+) -> Assignment {
+    let candidates = (0..expected)
+        .map(|expected| {
+            (0..actual)
+                .filter(|actual| fits(expected, *actual))
+                .collect::<Vec<_>>()
+        })
+        .collect::<Vec<_>>();
+
+    let mut owners = vec![None; actual];
+    let mut unmatched = Vec::new();
+    for expected in 0..expected {
+        let mut visited = vec![false; actual];
+        if !augment(expected, &candidates, &mut owners, &mut visited) {
+            unmatched.push(expected);
+        }
+    }
+
+    let unused = owners
+        .iter()
+        .enumerate()
+        .filter(|(_, owner)| owner.is_none())
+        .map(|(actual, _)| actual)
+        .collect();
+
+    Assignment { unmatched, unused }
+}
+
+/// Looks for an actual item for the expected item, moving the items assigned before to other
+/// candidates of theirs when needed
+fn augment(
+    expected: usize,
+    candidates: &[Vec<usize>],
+    owners: &mut [Option<usize>],
+    visited: &mut [bool],
+) -> bool {
+    for &actual in &candidates[expected] {
+        if visited[actual] {
+            continue;
+        }
+        visited[actual] = true;
+
+        let available = match owners[actual] {
+            Some(owner) => augment(owner, candidates, owners, visited),
+            None => true,
+        };
+        if available {
+            owners[actual] = Some(expected);
+            return true;
+        }
+    }
+
+    false
+}
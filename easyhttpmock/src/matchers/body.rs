@@ -4,6 +4,7 @@ use caramelo::MatchType::ToHave;
 use caramelo::Matcher;
 use caramelo::TypedMatcher;
 
+use crate::matchers::{describe_body, MatcherNode, Mismatch};
 use crate::mock::Request;
 
 pub use self::json::*;
@@ -30,7 +31,7 @@ pub use self::xml::*;
 ///
 /// let matcher = body(r"^Hello World$");
 /// ```
-pub fn body(value: &str) -> Arc<dyn TypedMatcher<Request> + Send + Sync + 'static> {
+pub fn body(value: &str) -> Arc<dyn MatcherNode<Request> + Send + Sync + 'static> {
     let regex = regex::Regex::new(value);
     match regex {
         Ok(regex) => Arc::new(Body(regex)),
@@ -79,6 +80,16 @@ impl TypedMatcher<Request> for Body {
     }
 }
 
+impl MatcherNode<Request> for Body {
+    fn explain(&self, value: &Request) -> Option<Mismatch> {
+        if self.matches(value) {
+            None
+        } else {
+            Some(Mismatch::new(self.description(), describe_body(value)))
+        }
+    }
+}
+
 #[cfg(feature = "json")]
 pub(crate) mod json {
     use std::sync::Arc;
@@ -87,9 +98,13 @@ pub(crate) mod json {
     use jsonpath_rust::JsonPath;
     use sonic_rs::Serialize;
 
+    use crate::matchers::{assignment::assign, describe_body, MatcherNode, Mismatch};
     use crate::mock::Request;
 
-    /// Creates a matcher that checks if the request body matches the given JSON exactly.
+    /// Creates a matcher that checks if the request body is structurally equal to the given JSON.
+    ///
+    /// Whitespace, key ordering and number formatting are ignored, use the returned matcher
+    /// options to also ignore array ordering, extra fields or whole values.
     ///
     /// # Arguments
     ///
@@ -97,7 +112,11 @@ pub(crate) mod json {
     ///
     /// # Returns
     ///
-    /// * `BodyWithExactJson` - A matcher that checks if the request body matches the given JSON exactly.
+    /// * `BodyWithExactJson` - A matcher that checks if the request body matches the given JSON.
+    ///
+    /// # Panics
+    ///
+    /// * Panics if the value cannot be serialized to JSON.
     ///
     /// # Examples
     ///
@@ -106,17 +125,17 @@ pub(crate) mod json {
     ///
     /// let matcher = exact_json_body(&serde_json::json!({"name": "John", "age": 30}));
     /// ```
-    pub fn exact_json_body<T: Serialize>(
-        value: &T,
-    ) -> Arc<dyn TypedMatcher<Request> + Send + Sync + 'static> {
-        match sonic_rs::to_string(value) {
-            Ok(json) => Arc::new(BodyWithExactJson(json)),
+    pub fn exact_json_body<T: Serialize>(value: &T) -> BodyWithExactJson {
+        let expected = sonic_rs::to_string(value)
+            .and_then(|json| sonic_rs::from_str::<serde_json::Value>(&json));
+        match expected {
+            Ok(expected) => BodyWithExactJson { expected, comparison: JsonComparison::default() },
             Err(e) => panic!("Failed to serialize JSON: {}", e),
         }
     }
 
     #[derive(Clone)]
-    /// A matcher that checks if the request body matches the given JSON exactly.
+    /// A matcher that checks if the request body is structurally equal to the given JSON.
     ///
     /// # Arguments
     ///
@@ -124,28 +143,270 @@ pub(crate) mod json {
     ///
     /// # Returns
     ///
-    /// * `BodyWithExactJson` - A matcher that checks if the request body matches the given JSON exactly.
+    /// * `BodyWithExactJson` - A matcher that checks if the request body matches the given JSON.
     ///
     /// # Examples
     ///
     /// ```rust
     /// use easyhttpmock::matchers::exact_json_body;
     ///
-    /// let matcher = exact_json_body(&serde_json::json!({"name": "John", "age": 30}));
+    /// let matcher = exact_json_body(&serde_json::json!({"id": 0, "tags": ["a", "b"]}))
+    ///     .ignore_array_order()
+    ///     .ignore_extra_fields()
+    ///     .wildcard("/id");
     /// ```
-    pub struct BodyWithExactJson(String);
+    pub struct BodyWithExactJson {
+        expected: serde_json::Value,
+        comparison: JsonComparison,
+    }
+
+    impl BodyWithExactJson {
+        /// Accepts arrays holding the expected elements in any order.
+        pub fn ignore_array_order(mut self) -> Self {
+            self.comparison
+                .ignore_array_order = true;
+            self
+        }
+
+        /// Accepts objects holding fields that are not in the expected JSON.
+        pub fn ignore_extra_fields(mut self) -> Self {
+            self.comparison
+                .ignore_extra_fields = true;
+            self
+        }
+
+        /// Accepts any value at the given JSON pointer.
+        ///
+        /// # Arguments
+        ///
+        /// * `pointer` - The JSON pointer to ignore, `*` matches any key or array index.
+        pub fn wildcard(mut self, pointer: &str) -> Self {
+            self.comparison
+                .wildcards
+                .push(split_pointer(pointer));
+            self
+        }
+
+        fn differences(&self, value: &Request) -> Result<Vec<Mismatch>, String> {
+            let Some(body) = value.body() else {
+                return Err("body was empty".to_string());
+            };
+
+            let actual = sonic_rs::from_str::<serde_json::Value>(&String::from_utf8_lossy(body))
+                .map_err(|e| format!("body was not valid JSON: {}", e))?;
+
+            Ok(self
+                .comparison
+                .differences(&self.expected, &actual))
+        }
+    }
+
+    #[derive(Clone, Default)]
+    /// Structural comparison of JSON values, shared by the JSON body and JSONPath matchers.
+    ///
+    /// Numbers are compared by value. Without options the values must be equal, with arrays in
+    /// the same order and objects holding the same fields.
+    pub(crate) struct JsonComparison {
+        ignore_array_order: bool,
+        ignore_extra_fields: bool,
+        wildcards: Vec<Vec<String>>,
+    }
+
+    impl JsonComparison {
+        /// Returns the differences between the expected and the actual value, one per pointer.
+        pub(crate) fn differences(
+            &self,
+            expected: &serde_json::Value,
+            actual: &serde_json::Value,
+        ) -> Vec<Mismatch> {
+            let mut differences = Vec::new();
+            self.diff(expected, actual, &mut Vec::new(), &mut differences);
+            differences
+        }
+
+        /// Returns whether the actual value has no difference with the expected one.
+        pub(crate) fn equals(
+            &self,
+            expected: &serde_json::Value,
+            actual: &serde_json::Value,
+        ) -> bool {
+            self.differences(expected, actual)
+                .is_empty()
+        }
+
+        fn is_wildcard(&self, path: &[String]) -> bool {
+            self.wildcards
+                .iter()
+                .any(|wildcard| {
+                    wildcard.len() == path.len()
+                        && wildcard
+                            .iter()
+                            .zip(path)
+                            .all(|(w, p)| w == "*" || w == p)
+                })
+        }
+
+        fn diff(
+            &self,
+            expected: &serde_json::Value,
+            actual: &serde_json::Value,
+            path: &mut Vec<String>,
+            differences: &mut Vec<Mismatch>,
+        ) {
+            use serde_json::Value;
+
+            if self.is_wildcard(path) {
+                return;
+            }
+
+            match (expected, actual) {
+                (Value::Object(expected), Value::Object(actual)) => {
+                    for (key, expected) in expected {
+                        path.push(key.clone());
+                        match actual.get(key) {
+                            Some(actual) => self.diff(expected, actual, path, differences),
+                            None if self.is_wildcard(path) => {}
+                            None => differences.push(Mismatch::new(
+                                to_pointer(path),
+                                format!("missing, expected {}", expected),
+                            )),
+                        }
+                        path.pop();
+                    }
+
+                    if !self.ignore_extra_fields {
+                        for (key, actual) in actual {
+                            if expected.contains_key(key) {
+                                continue;
+                            }
+                            path.push(key.clone());
+                            if !self.is_wildcard(path) {
+                                differences.push(Mismatch::new(
+                                    to_pointer(path),
+                                    format!("unexpected field with value {}", actual),
+                                ));
+                            }
+                            path.pop();
+                        }
+                    }
+                }
+                (Value::Array(expected), Value::Array(actual)) if self.ignore_array_order => {
+                    let assignment = assign(expected.len(), actual.len(), |e, a| {
+                        // Wildcards address the elements by their expected index
+                        let mut path = path.clone();
+                        path.push(e.to_string());
+                        let mut nested = Vec::new();
+                        self.diff(&expected[e], &actual[a], &mut path, &mut nested);
+                        nested.is_empty()
+                    });
+
+                    for index in assignment.unmatched {
+                        path.push(index.to_string());
+                        differences.push(Mismatch::new(
+                            to_pointer(path),
+                            format!("no element matching {}", expected[index]),
+                        ));
+                        path.pop();
+                    }
+
+                    for index in assignment.unused {
+                        differences.push(Mismatch::new(
+                            to_pointer(path),
+                            format!("unexpected element {}", actual[index]),
+                        ));
+                    }
+                }
+                (Value::Array(expected), Value::Array(actual)) => {
+                    for index in 0..expected
+                        .len()
+                        .max(actual.len())
+                    {
+                        path.push(index.to_string());
+                        match (expected.get(index), actual.get(index)) {
+                            (Some(expected), Some(actual)) => {
+                                self.diff(expected, actual, path, differences)
+                            }
+                            (Some(expected), None) => differences.push(Mismatch::new(
+                                to_pointer(path),
+                                format!("missing, expected {}", expected),
+                            )),
+                            (None, Some(actual)) => differences.push(Mismatch::new(
+                                to_pointer(path),
+                                format!("unexpected element {}", actual),
+                            )),
+                            (None, None) => {}
+                        }
+                        path.pop();
+                    }
+                }
+                (Value::Number(expected), Value::Number(actual)) => {
+                    if !numbers_equal(expected, actual) {
+                        differences.push(Mismatch::new(
+                            to_pointer(path),
+                            format!("expected {}, got {}", expected, actual),
+                        ));
+                    }
+                }
+                (expected, actual) => {
+                    if expected != actual {
+                        differences.push(Mismatch::new(
+                            to_pointer(path),
+                            format!("expected {}, got {}", expected, actual),
+                        ));
+                    }
+                }
+            }
+        }
+    }
+
+    /// Compares JSON numbers by value, so `1`, `1.0` and `1e0` are equal.
+    fn numbers_equal(expected: &serde_json::Number, actual: &serde_json::Number) -> bool {
+        if let (Some(expected), Some(actual)) = (expected.as_i64(), actual.as_i64()) {
+            return expected == actual;
+        }
+        if let (Some(expected), Some(actual)) = (expected.as_u64(), actual.as_u64()) {
+            return expected == actual;
+        }
+        expected.as_f64() == actual.as_f64()
+    }
+
+    fn split_pointer(pointer: &str) -> Vec<String> {
+        pointer
+            .split('/')
+            .skip(1)
+            .map(|segment| {
+                segment
+                    .replace("~1", "/")
+                    .replace("~0", "~")
+            })
+            .collect()
+    }
+
+    fn to_pointer(path: &[String]) -> String {
+        if path.is_empty() {
+            return "(root)".to_string();
+        }
+
+        path.iter()
+            .map(|segment| {
+                format!(
+                    "/{}",
+                    segment
+                        .replace('~', "~0")
+                        .replace('/', "~1")
+                )
+            })
+            .collect()
+    }
 
     impl Matcher<Request> for BodyWithExactJson {
         fn matches(&self, value: &Request) -> bool {
-            if let Some(body) = value.body() {
-                body == &self.0
-            } else {
-                false
-            }
+            self.differences(value)
+                .is_ok_and(|differences| differences.is_empty())
         }
 
         fn description(&self) -> String {
-            format!("body contents matching {}", self.0)
+            format!("body contents matching {}", self.expected)
         }
     }
 
@@ -155,6 +416,27 @@ pub(crate) mod json {
         }
     }
 
+    impl MatcherNode<Request> for BodyWithExactJson {
+        fn explain(&self, value: &Request) -> Option<Mismatch> {
+            match self.differences(value) {
+                Ok(differences) if differences.is_empty() => None,
+                Ok(differences) => {
+                    let pointers = differences
+                        .iter()
+                        .map(|difference| difference.expected())
+                        .collect::<Vec<_>>()
+                        .join(", ");
+                    Some(Mismatch::with_causes(
+                        self.description(),
+                        format!("body differs at {}", pointers),
+                        differences,
+                    ))
+                }
+                Err(actual) => Some(Mismatch::new(self.description(), actual)),
+            }
+        }
+    }
+
     /// Creates a matcher that checks if the request body contains the given JSON partial.
     ///
     /// # Arguments
@@ -172,9 +454,7 @@ pub(crate) mod json {
     ///
     /// let matcher = partial_json_body(r#"$.name"#);
     /// ```
-    pub fn partial_json_body(
-        value: &str,
-    ) -> Arc<dyn TypedMatcher<Request> + Send + Sync + 'static> {
+    pub fn partial_json_body(value: &str) -> Arc<dyn MatcherNode<Request> + Send + Sync + 'static> {
         Arc::new(BodyWithPartialJson(value.to_owned()))
     }
 
@@ -227,20 +507,35 @@ pub(crate) mod json {
             ToHave
         }
     }
+
+    impl MatcherNode<Request> for BodyWithPartialJson {
+        fn explain(&self, value: &Request) -> Option<Mismatch> {
+            if self.matches(value) {
+                None
+            } else {
+                Some(Mismatch::new(self.description(), describe_body(value)))
+            }
+        }
+    }
 }
 
 #[cfg(feature = "xml")]
 pub(crate) mod xml {
-    use std::sync::Arc;
+    use std::{collections::BTreeMap, sync::Arc};
 
     use caramelo::{MatchType::ToHave, Matcher, TypedMatcher};
     use serde::Serialize;
     use serde_xml_rs::to_string;
     use simdxml::parse;
+    use xml_rs::reader::XmlEvent;
 
+    use crate::matchers::{describe_body, MatcherNode, Mismatch};
     use crate::mock::Request;
 
-    /// Creates a matcher that checks if the request body matches the given XML exactly.
+    /// Creates a matcher that checks if the request body is semantically equal to the given XML.
+    ///
+    /// Whitespace between elements, comments, attribute order and namespace prefixes are ignored,
+    /// elements and attributes are compared by namespace URI and local name.
     ///
     /// # Arguments
     ///
@@ -248,7 +543,11 @@ pub(crate) mod xml {
     ///
     /// # Returns
     ///
-    /// * `BodyWithExactXml` - A matcher that checks if the request body matches the given XML exactly.
+    /// * `BodyWithExactXml` - A matcher that checks if the request body matches the given XML.
+    ///
+    /// # Panics
+    ///
+    /// * Panics if the value cannot be serialized to XML.
     ///
     /// # Examples
     ///
@@ -267,56 +566,299 @@ pub(crate) mod xml {
     ///     age: 30,
     /// });
     /// ```
-    pub fn exact_xml_body<T: Serialize>(
-        value: &T,
-    ) -> Arc<dyn TypedMatcher<Request> + Send + Sync + 'static> {
+    pub fn exact_xml_body<T: Serialize>(value: &T) -> BodyWithExactXml {
         match to_string(value) {
-            Ok(xml) => Arc::new(BodyWithExactXml(xml)),
+            Ok(xml) => exact_xml_body_str(&xml),
             Err(e) => panic!("Failed to serialize XML: {}", e),
         }
     }
 
-    #[derive(Clone)]
-    /// A matcher that checks if the request body matches the given XML exactly.
+    /// Creates a matcher that checks if the request body is semantically equal to the given XML
+    /// document.
     ///
     /// # Arguments
     ///
-    /// * `value` - The XML value to match against.
+    /// * `xml` - The XML document to match against.
     ///
     /// # Returns
     ///
-    /// * `BodyWithExactXml` - A matcher that checks if the request body matches the given XML exactly.
+    /// * `BodyWithExactXml` - A matcher that checks if the request body matches the given XML.
+    ///
+    /// # Panics
+    ///
+    /// * Panics if the document is not well-formed XML.
     ///
     /// # Examples
     ///
     /// ```rust
-    /// use easyhttpmock::matchers::exact_xml_body;
-    /// use serde::Serialize;
+    /// use easyhttpmock::matchers::exact_xml_body_str;
     ///
-    /// #[derive(Serialize)]
-    /// struct User {
-    ///     name: String,
-    ///     age: u32,
-    /// }
+    /// let matcher = exact_xml_body_str(
+    ///     r#"<soap:Envelope xmlns:soap="http://schemas.xmlsoap.org/soap/envelope/">
+    ///         <soap:Body><GetUser id="1"/></soap:Body>
+    ///     </soap:Envelope>"#,
+    /// );
+    /// ```
+    pub fn exact_xml_body_str(xml: &str) -> BodyWithExactXml {
+        match parse_xml(xml.as_bytes()) {
+            Ok(expected) => BodyWithExactXml { xml: xml.to_string(), expected },
+            Err(e) => panic!("Failed to parse XML: {}", e),
+        }
+    }
+
+    #[derive(Clone)]
+    /// A matcher that checks if the request body is semantically equal to the given XML.
     ///
-    /// let matcher = exact_xml_body(&User {
-    ///     name: "John".to_string(),
-    ///     age: 30,
-    /// });
+    /// # Arguments
+    ///
+    /// * `xml` - The XML document to match against.
+    ///
+    /// # Returns
+    ///
+    /// * `BodyWithExactXml` - A matcher that checks if the request body matches the given XML.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use easyhttpmock::matchers::exact_xml_body_str;
+    ///
+    /// let matcher = exact_xml_body_str(r#"<user><name>John</name><age>30</age></user>"#);
     /// ```
-    pub struct BodyWithExactXml(String);
+    pub struct BodyWithExactXml {
+        xml: String,
+        expected: XmlElement,
+    }
+
+    /// Element of a canonical XML tree, names are resolved to their namespace URI.
+    #[derive(Clone, Debug, PartialEq)]
+    struct XmlElement {
+        namespace: Option<String>,
+        name: String,
+        attributes: BTreeMap<(Option<String>, String), String>,
+        children: Vec<XmlNode>,
+    }
+
+    #[derive(Clone, Debug, PartialEq)]
+    enum XmlNode {
+        Element(XmlElement),
+        Text(String),
+    }
+
+    /// Parses a document into its canonical tree, dropping comments and whitespace-only text.
+    fn parse_xml(xml: &[u8]) -> Result<XmlElement, String> {
+        let reader = xml_rs::ParserConfig::new()
+            .trim_whitespace(true)
+            .cdata_to_characters(true)
+            .ignore_comments(true)
+            .coalesce_characters(true)
+            .create_reader(xml);
+
+        let mut stack: Vec<XmlElement> = Vec::new();
+        let mut root = None;
+        for event in reader {
+            match event.map_err(|e| e.to_string())? {
+                XmlEvent::StartElement { name, attributes, .. } => stack.push(XmlElement {
+                    namespace: name.namespace,
+                    name: name.local_name,
+                    attributes: attributes
+                        .into_iter()
+                        .map(|attribute| {
+                            (
+                                (
+                                    attribute
+                                        .name
+                                        .namespace,
+                                    attribute
+                                        .name
+                                        .local_name,
+                                ),
+                                attribute.value,
+                            )
+                        })
+                        .collect(),
+                    children: Vec::new(),
+                }),
+                XmlEvent::EndElement { .. } => {
+                    let element = stack
+                        .pop()
+                        .ok_or_else(|| "unbalanced end element".to_string())?;
+                    match stack.last_mut() {
+                        Some(parent) => parent
+                            .children
+                            .push(XmlNode::Element(element)),
+                        None => root = Some(element),
+                    }
+                }
+                XmlEvent::Characters(text) => {
+                    if let Some(parent) = stack.last_mut() {
+                        parent
+                            .children
+                            .push(XmlNode::Text(text));
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        root.ok_or_else(|| "no root element".to_string())
+    }
+
+    fn qualified_name(namespace: &Option<String>, name: &str) -> String {
+        match namespace {
+            Some(namespace) => format!("{{{}}}{}", namespace, name),
+            None => name.to_string(),
+        }
+    }
+
+    fn describe_node(node: &XmlNode) -> String {
+        match node {
+            XmlNode::Element(element) => {
+                format!("element {}", qualified_name(&element.namespace, &element.name))
+            }
+            XmlNode::Text(text) => format!("text {:?}", text),
+        }
+    }
+
+    /// Builds the location of a child node, indexing the elements sharing its name.
+    fn child_path(path: &str, siblings: &[XmlNode], index: usize) -> String {
+        let XmlNode::Element(element) = &siblings[index] else {
+            return format!("{}/text()", path);
+        };
+
+        let same_name = |node: &&XmlNode| {
+            matches!(node, XmlNode::Element(sibling)
+                if sibling.name == element.name && sibling.namespace == element.namespace)
+        };
+        let position = siblings[..index]
+            .iter()
+            .filter(same_name)
+            .count();
+        let total = siblings
+            .iter()
+            .filter(same_name)
+            .count();
+
+        if total > 1 {
+            format!("{}/{}[{}]", path, element.name, position + 1)
+        } else {
+            format!("{}/{}", path, element.name)
+        }
+    }
+
+    /// Compares two elements recursively, recording a mismatch per differing location.
+    fn diff_xml(
+        expected: &XmlElement,
+        actual: &XmlElement,
+        path: &str,
+        differences: &mut Vec<Mismatch>,
+    ) {
+        if expected.namespace != actual.namespace || expected.name != actual.name {
+            differences.push(Mismatch::new(
+                path,
+                format!(
+                    "expected element {}, got {}",
+                    qualified_name(&expected.namespace, &expected.name),
+                    qualified_name(&actual.namespace, &actual.name)
+                ),
+            ));
+            return;
+        }
+
+        for (key, expected_value) in &expected.attributes {
+            let attribute_path = format!("{}/@{}", path, qualified_name(&key.0, &key.1));
+            match actual
+                .attributes
+                .get(key)
+            {
+                Some(actual_value) if actual_value == expected_value => {}
+                Some(actual_value) => differences.push(Mismatch::new(
+                    attribute_path,
+                    format!("expected {:?}, got {:?}", expected_value, actual_value),
+                )),
+                None => differences.push(Mismatch::new(
+                    attribute_path,
+                    format!("missing, expected {:?}", expected_value),
+                )),
+            }
+        }
+        for (key, actual_value) in &actual.attributes {
+            if !expected
+                .attributes
+                .contains_key(key)
+            {
+                differences.push(Mismatch::new(
+                    format!("{}/@{}", path, qualified_name(&key.0, &key.1)),
+                    format!("unexpected attribute with value {:?}", actual_value),
+                ));
+            }
+        }
+
+        let expected_children = &expected.children;
+        let actual_children = &actual.children;
+        for index in 0..expected_children
+            .len()
+            .max(actual_children.len())
+        {
+            match (expected_children.get(index), actual_children.get(index)) {
+                (Some(XmlNode::Element(expected)), Some(XmlNode::Element(actual))) => diff_xml(
+                    expected,
+                    actual,
+                    &child_path(path, expected_children, index),
+                    differences,
+                ),
+                (Some(XmlNode::Text(expected)), Some(XmlNode::Text(actual))) => {
+                    if expected != actual {
+                        differences.push(Mismatch::new(
+                            child_path(path, expected_children, index),
+                            format!("expected {:?}, got {:?}", expected, actual),
+                        ));
+                    }
+                }
+                (Some(expected), Some(actual)) => differences.push(Mismatch::new(
+                    child_path(path, expected_children, index),
+                    format!("expected {}, got {}", describe_node(expected), describe_node(actual)),
+                )),
+                (Some(expected), None) => differences.push(Mismatch::new(
+                    child_path(path, expected_children, index),
+                    format!("missing {}", describe_node(expected)),
+                )),
+                (None, Some(actual)) => differences.push(Mismatch::new(
+                    child_path(path, actual_children, index),
+                    format!("unexpected {}", describe_node(actual)),
+                )),
+                (None, None) => {}
+            }
+        }
+    }
+
+    impl BodyWithExactXml {
+        /// Returns the locations where the request body differs from the expected document.
+        fn differences(&self, value: &Request) -> Result<Vec<Mismatch>, String> {
+            let Some(body) = value.body() else {
+                return Err(describe_body(value));
+            };
+
+            let actual = parse_xml(body).map_err(|e| format!("body was not valid XML: {}", e))?;
+
+            let mut differences = Vec::new();
+            diff_xml(
+                &self.expected,
+                &actual,
+                &format!("/{}", self.expected.name),
+                &mut differences,
+            );
+            Ok(differences)
+        }
+    }
 
     impl Matcher<Request> for BodyWithExactXml {
         fn matches(&self, value: &Request) -> bool {
-            if let Some(body) = value.body() {
-                body == &self.0
-            } else {
-                false
-            }
+            self.differences(value)
+                .is_ok_and(|differences| differences.is_empty())
         }
 
         fn description(&self) -> String {
-            format!("body contents matching {}", self.0)
+            format!("body contents matching {}", self.xml)
         }
     }
 
@@ -326,6 +868,27 @@ pub(crate) mod xml {
         }
     }
 
+    impl MatcherNode<Request> for BodyWithExactXml {
+        fn explain(&self, value: &Request) -> Option<Mismatch> {
+            match self.differences(value) {
+                Ok(differences) if differences.is_empty() => None,
+                Ok(differences) => {
+                    let locations = differences
+                        .iter()
+                        .map(|difference| difference.expected())
+                        .collect::<Vec<_>>()
+                        .join(", ");
+                    Some(Mismatch::with_causes(
+                        self.description(),
+                        format!("body differs at {}", locations),
+                        differences,
+                    ))
+                }
+                Err(actual) => Some(Mismatch::new(self.description(), actual)),
+            }
+        }
+    }
+
     /// Creates a matcher that checks if the request body contains the given XML partial.
     ///
     /// # Arguments
@@ -343,7 +906,7 @@ pub(crate) mod xml {
     ///
     /// let matcher = partial_xml_body(r#"//name"#);
     /// ```
-    pub fn partial_xml_body(value: &str) -> Arc<dyn TypedMatcher<Request> + Send + Sync + 'static> {
+    pub fn partial_xml_body(value: &str) -> Arc<dyn MatcherNode<Request> + Send + Sync + 'static> {
         Arc::new(BodyWithPartialXml(value.to_owned()))
     }
 
@@ -394,4 +957,14 @@ pub(crate) mod xml {
             ToHave
         }
     }
+
+    impl MatcherNode<Request> for BodyWithPartialXml {
+        fn explain(&self, value: &Request) -> Option<Mismatch> {
+            if self.matches(value) {
+                None
+            } else {
+                Some(Mismatch::new(self.description(), describe_body(value)))
+            }
+        }
+    }
 }
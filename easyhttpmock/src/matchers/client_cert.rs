@@ -0,0 +1,187 @@
+use caramelo::{MatchType::ToHave, Matcher, TypedMatcher};
+
+use crate::{
+    matchers::{MatcherNode, Mismatch},
+    mock::Request,
+    tls::ClientCertificate,
+};
+
+/// Creates a matcher that checks the certificate the client presented during the TLS handshake.
+///
+/// Without any assertion the matcher only checks that a certificate was presented.
+///
+/// # Returns
+///
+/// * `ClientCert` - A matcher that checks the client certificate.
+///
+/// # Examples
+///
+/// ```rust
+/// use easyhttpmock::matchers::client_cert;
+///
+/// let matcher = client_cert()
+///     .subject_cn("testuser")
+///     .san("testuser.example.com");
+/// ```
+pub fn client_cert() -> ClientCert {
+    ClientCert { checks: Vec::new() }
+}
+
+#[derive(Clone)]
+enum CertCheck {
+    SubjectCn(String),
+    San(String),
+    Fingerprint(String),
+}
+
+impl CertCheck {
+    fn describe(&self) -> String {
+        match self {
+            CertCheck::SubjectCn(common_name) => format!("subject CN {:?}", common_name),
+            CertCheck::San(name) => format!("SAN {:?}", name),
+            CertCheck::Fingerprint(fingerprint) => format!("SHA-256 fingerprint {}", fingerprint),
+        }
+    }
+
+    /// Checks the certificate, returning what it contained when the check fails.
+    fn check(&self, certificate: &ClientCertificate) -> Option<String> {
+        match self {
+            CertCheck::SubjectCn(expected) => match certificate.common_name() {
+                Some(common_name) if common_name == *expected => None,
+                Some(common_name) => Some(format!("subject CN was {:?}", common_name)),
+                None => Some("subject has no CN".to_string()),
+            },
+            CertCheck::San(expected) => {
+                let names = certificate.subject_alt_names();
+                if names.contains(expected) {
+                    None
+                } else if names.is_empty() {
+                    Some("certificate has no SAN".to_string())
+                } else {
+                    Some(format!("SANs were {}", names.join(", ")))
+                }
+            }
+            CertCheck::Fingerprint(expected) => {
+                let fingerprint = certificate.fingerprint();
+                if fingerprint == *expected {
+                    None
+                } else {
+                    Some(format!("fingerprint was {}", fingerprint))
+                }
+            }
+        }
+    }
+}
+
+#[derive(Clone)]
+/// A matcher that checks the certificate the client presented during the TLS handshake.
+///
+/// # Arguments
+///
+/// * `checks` - The assertions on the certificate.
+///
+/// # Returns
+///
+/// * `ClientCert` - A matcher that checks the client certificate.
+///
+/// # Examples
+///
+/// ```rust
+/// use easyhttpmock::matchers::client_cert;
+///
+/// let matcher = client_cert().fingerprint(
+///     "84:A6:E2:23:85:63:D2:7A:9F:6F:B1:82:B8:9C:EB:D2:FB:EC:F9:90:76:35:25:2E:2A:AB:6F:B2:36:27:43:72",
+/// );
+/// ```
+pub struct ClientCert {
+    checks: Vec<CertCheck>,
+}
+
+impl ClientCert {
+    /// Checks that the common name of the certificate subject is equal to the given one.
+    pub fn subject_cn(mut self, common_name: &str) -> Self {
+        self.checks
+            .push(CertCheck::SubjectCn(common_name.to_string()));
+        self
+    }
+
+    /// Checks that the subject alternative names contain the given DNS name, IP address, email or
+    /// URI.
+    pub fn san(mut self, name: &str) -> Self {
+        self.checks
+            .push(CertCheck::San(name.to_string()));
+        self
+    }
+
+    /// Checks the SHA-256 fingerprint of the certificate, given as hex with or without colons.
+    pub fn fingerprint(mut self, fingerprint: &str) -> Self {
+        let fingerprint = fingerprint
+            .chars()
+            .filter(|c| *c != ':')
+            .collect::<String>()
+            .to_lowercase();
+        self.checks
+            .push(CertCheck::Fingerprint(fingerprint));
+        self
+    }
+
+    fn failures(&self, value: &Request) -> Result<Vec<Mismatch>, String> {
+        let certificate = value
+            .client_certificate()
+            .ok_or_else(|| "no client certificate was presented".to_string())?;
+
+        Ok(self
+            .checks
+            .iter()
+            .filter_map(|check| {
+                check
+                    .check(certificate)
+                    .map(|actual| Mismatch::new(check.describe(), actual))
+            })
+            .collect())
+    }
+}
+
+impl Matcher<Request> for ClientCert {
+    fn matches(&self, value: &Request) -> bool {
+        self.failures(value)
+            .is_ok_and(|failures| failures.is_empty())
+    }
+
+    fn description(&self) -> String {
+        if self
+            .checks
+            .is_empty()
+        {
+            return "client certificate".to_string();
+        }
+
+        let checks = self
+            .checks
+            .iter()
+            .map(CertCheck::describe)
+            .collect::<Vec<_>>()
+            .join(", ");
+        format!("client certificate with {}", checks)
+    }
+}
+
+impl TypedMatcher<Request> for ClientCert {
+    fn matcher_type(&self) -> caramelo::MatchType {
+        ToHave
+    }
+}
+
+impl MatcherNode<Request> for ClientCert {
+    fn explain(&self, value: &Request) -> Option<Mismatch> {
+        match self.failures(value) {
+            Ok(failures) if failures.is_empty() => None,
+            Ok(failures) => Some(Mismatch::with_causes(
+                self.description(),
+                format!("{} certificate checks failed", failures.len()),
+                failures,
+            )),
+            Err(actual) => Some(Mismatch::new(self.description(), actual)),
+        }
+    }
+}
@@ -0,0 +1,90 @@
+use std::sync::Arc;
+
+use caramelo::{MatchType::ToHave, Matcher, TypedMatcher};
+
+use crate::{
+    compression::{content_encodings, Encoding},
+    matchers::{MatcherNode, Mismatch},
+    mock::Request,
+};
+
+/// Creates a matcher that checks if the request body was encoded with the given encoding.
+///
+/// The encoding must be listed in the `Content-Encoding` header of the request. Body matchers
+/// see the decoded body, this matcher checks the client actually compressed it.
+///
+/// # Arguments
+///
+/// * `encoding` - The encoding to match against.
+///
+/// # Returns
+///
+/// * `ContentEncoding` - A matcher that checks if the request body was encoded with the encoding.
+///
+/// # Examples
+///
+/// ```rust
+/// use easyhttpmock::{compression::Encoding, matchers::content_encoding};
+///
+/// let matcher = content_encoding(Encoding::Gzip);
+/// ```
+pub fn content_encoding(
+    encoding: Encoding,
+) -> Arc<dyn MatcherNode<Request> + Send + Sync + 'static> {
+    Arc::new(ContentEncoding(encoding))
+}
+
+#[derive(Clone)]
+/// A matcher that checks if the request body was encoded with a specific content encoding.
+///
+/// # Arguments
+///
+/// * `encoding` - The encoding to match against.
+///
+/// # Returns
+///
+/// * `ContentEncoding` - A matcher that checks if the request body was encoded with the encoding.
+///
+/// # Examples
+///
+/// ```rust
+/// use easyhttpmock::{compression::Encoding, matchers::content_encoding};
+///
+/// let matcher = content_encoding(Encoding::Zstd);
+/// ```
+pub struct ContentEncoding(Encoding);
+
+impl Matcher<Request> for ContentEncoding {
+    fn matches(&self, value: &Request) -> bool {
+        content_encodings(value.headers())
+            .iter()
+            .any(|coding| coding.parse::<Encoding>() == Ok(self.0))
+    }
+
+    fn description(&self) -> String {
+        format!("content encoding {}", self.0)
+    }
+}
+
+impl TypedMatcher<Request> for ContentEncoding {
+    fn matcher_type(&self) -> caramelo::MatchType {
+        ToHave
+    }
+}
+
+impl MatcherNode<Request> for ContentEncoding {
+    fn explain(&self, value: &Request) -> Option<Mismatch> {
+        if self.matches(value) {
+            return None;
+        }
+
+        let encodings = content_encodings(value.headers());
+        let actual = if encodings.is_empty() {
+            "body was not encoded".to_string()
+        } else {
+            format!("content encoding was {}", encodings.join(", "))
+        };
+
+        Some(Mismatch::new(self.description(), actual))
+    }
+}
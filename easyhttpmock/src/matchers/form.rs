@@ -0,0 +1,624 @@
+use std::sync::Arc;
+
+use bytes::Bytes;
+use caramelo::{MatchType::ToHave, Matcher, TypedMatcher};
+use http::{
+    header::{CONTENT_DISPOSITION, CONTENT_TYPE},
+    HeaderMap, HeaderName, HeaderValue,
+};
+
+use crate::{
+    matchers::{
+        assignment::assign, excerpt, format_query_params, missing_pairs, MatcherNode, Mismatch,
+    },
+    mock::{QueryParams, Request},
+};
+
+/// Content type of URL-encoded form bodies
+const URL_ENCODED: &str = "application/x-www-form-urlencoded";
+
+/// Content type of multipart form bodies
+const MULTIPART: &str = "multipart/form-data";
+
+/// Creates a matcher that checks if the request is a URL-encoded form with the given fields.
+///
+/// The request must have the `application/x-www-form-urlencoded` content type. Other fields may
+/// be present, and repeated fields must be present as many times as they were given.
+///
+/// # Arguments
+///
+/// * `fields` - The decoded name and value pairs that must be present.
+///
+/// # Returns
+///
+/// * `FormFields` - A matcher that checks if the request form has all the given fields.
+///
+/// # Examples
+///
+/// ```rust
+/// use easyhttpmock::matchers::form_fields;
+///
+/// let matcher = form_fields(&[("name", "John Doe"), ("tag", "a"), ("tag", "b")]);
+/// ```
+pub fn form_fields(fields: &[(&str, &str)]) -> FormFields {
+    FormFields(QueryParams::from(fields))
+}
+
+#[derive(Clone)]
+/// A matcher that checks if the request is a URL-encoded form with all the given fields.
+///
+/// # Arguments
+///
+/// * `fields` - The decoded name and value pairs that must be present.
+///
+/// # Returns
+///
+/// * `FormFields` - A matcher that checks if the request form has all the given fields.
+///
+/// # Examples
+///
+/// ```rust
+/// use easyhttpmock::matchers::form_fields;
+///
+/// let matcher = form_fields(&[("name", "John")]);
+/// ```
+pub struct FormFields(QueryParams);
+
+impl FormFields {
+    fn received(&self, value: &Request) -> Option<QueryParams> {
+        if !has_content_type(value.headers(), URL_ENCODED) {
+            return None;
+        }
+
+        Some(
+            value
+                .body()
+                .as_ref()
+                .map(|body| QueryParams::parse(&String::from_utf8_lossy(body)))
+                .unwrap_or_default(),
+        )
+    }
+}
+
+impl Matcher<Request> for FormFields {
+    fn matches(&self, value: &Request) -> bool {
+        self.received(value)
+            .is_some_and(|received| missing_pairs(&self.0, &received).is_empty())
+    }
+
+    fn description(&self) -> String {
+        format!("form fields including {}", format_query_params(self.0.iter()))
+    }
+}
+
+impl TypedMatcher<Request> for FormFields {
+    fn matcher_type(&self) -> caramelo::MatchType {
+        ToHave
+    }
+}
+
+impl MatcherNode<Request> for FormFields {
+    fn explain(&self, value: &Request) -> Option<Mismatch> {
+        let Some(received) = self.received(value) else {
+            return Some(Mismatch::new(self.description(), describe_content_type(value)));
+        };
+
+        let missing = missing_pairs(&self.0, &received);
+        if missing.is_empty() {
+            return None;
+        }
+
+        let present = if received.is_empty() {
+            "form was empty".to_string()
+        } else {
+            format!("form fields were {}", format_query_params(received.iter()))
+        };
+        Some(Mismatch::new(
+            self.description(),
+            format!("missing {}; {}", format_query_params(missing.into_iter()), present),
+        ))
+    }
+}
+
+/// Creates a matcher that checks if the request is a multipart form with the given parts.
+///
+/// The request must have the `multipart/form-data` content type. Every expected part must
+/// match a different part of the body, in any order, and other parts may be present.
+///
+/// # Arguments
+///
+/// * `parts` - The parts that must be present.
+///
+/// # Returns
+///
+/// * `Multipart` - A matcher that checks if the request has all the given parts.
+///
+/// # Examples
+///
+/// ```rust
+/// use easyhttpmock::matchers::{multipart, part};
+///
+/// let matcher = multipart(vec![
+///     part("title").contents("^Holidays$"),
+///     part("photo").filename("beach.png").content_type("image/png"),
+/// ]);
+/// ```
+pub fn multipart(parts: Vec<PartMatcher>) -> Multipart {
+    Multipart(parts)
+}
+
+#[derive(Clone)]
+/// A matcher that checks if the request is a multipart form with all the given parts.
+///
+/// # Arguments
+///
+/// * `parts` - The parts that must be present.
+///
+/// # Returns
+///
+/// * `Multipart` - A matcher that checks if the request has all the given parts.
+///
+/// # Examples
+///
+/// ```rust
+/// use easyhttpmock::matchers::{multipart, part};
+///
+/// let matcher = multipart(vec![part("title")]);
+/// ```
+pub struct Multipart(Vec<PartMatcher>);
+
+impl Multipart {
+    /// Returns the index of the expected parts not matched by a different part of the body
+    fn unmatched(&self, value: &Request, parts: &[FormPart]) -> Vec<usize> {
+        assign(self.0.len(), parts.len(), |expected, actual| {
+            self.0[expected].matches_part(value, &parts[actual])
+        })
+        .unmatched
+    }
+}
+
+impl Matcher<Request> for Multipart {
+    fn matches(&self, value: &Request) -> bool {
+        parse_multipart(value).is_some_and(|parts| {
+            self.unmatched(value, &parts)
+                .is_empty()
+        })
+    }
+
+    fn description(&self) -> String {
+        let parts = self
+            .0
+            .iter()
+            .map(|part| part.description())
+            .collect::<Vec<_>>();
+
+        format!("multipart form with {}", parts.join(", "))
+    }
+}
+
+impl TypedMatcher<Request> for Multipart {
+    fn matcher_type(&self) -> caramelo::MatchType {
+        ToHave
+    }
+}
+
+impl MatcherNode<Request> for Multipart {
+    fn explain(&self, value: &Request) -> Option<Mismatch> {
+        let Some(parts) = parse_multipart(value) else {
+            let actual = if has_content_type(value.headers(), MULTIPART) {
+                "body was not a valid multipart form".to_string()
+            } else {
+                describe_content_type(value)
+            };
+            return Some(Mismatch::new(self.description(), actual));
+        };
+
+        let unmatched = self.unmatched(value, &parts);
+        if unmatched.is_empty() {
+            return None;
+        }
+
+        let causes = unmatched
+            .into_iter()
+            .map(|index| self.0[index].explain_part(value, &parts))
+            .collect::<Vec<_>>();
+
+        Some(Mismatch::with_causes(
+            self.description(),
+            format!("{} part(s) did not match; {}", causes.len(), describe_parts(&parts)),
+            causes,
+        ))
+    }
+}
+
+/// Creates a matcher for the part of a multipart form with the given name.
+///
+/// The filename, content type and contents of the part can also be checked, and any body
+/// matcher, such as the JSON and XML ones, can be run against the contents of the part.
+///
+/// # Arguments
+///
+/// * `name` - The name of the part, from its `Content-Disposition` header.
+///
+/// # Returns
+///
+/// * `PartMatcher` - A matcher for the part, to give to `multipart`.
+///
+/// # Examples
+///
+/// ```rust
+/// use easyhttpmock::matchers::{body, part};
+///
+/// let matcher = part("notes")
+///     .filename("notes.txt")
+///     .content_type("text/plain")
+///     .matching(body("^Hello"));
+/// ```
+pub fn part(name: &str) -> PartMatcher {
+    PartMatcher {
+        name: name.to_string(),
+        filename: None,
+        content_type: None,
+        contents: None,
+        matchers: Vec::new(),
+    }
+}
+
+#[derive(Clone)]
+/// A matcher for a single part of a multipart form.
+///
+/// # Arguments
+///
+/// * `name` - The name of the part.
+///
+/// # Returns
+///
+/// * `PartMatcher` - A matcher for the part, to give to `multipart`.
+///
+/// # Examples
+///
+/// ```rust
+/// use easyhttpmock::matchers::part;
+///
+/// let matcher = part("photo").filename("beach.png");
+/// ```
+pub struct PartMatcher {
+    name: String,
+    filename: Option<String>,
+    content_type: Option<String>,
+    contents: Option<regex::Regex>,
+    matchers: Vec<Arc<dyn MatcherNode<Request> + Send + Sync + 'static>>,
+}
+
+impl PartMatcher {
+    /// Requires the part to have the given filename.
+    ///
+    /// # Arguments
+    ///
+    /// * `filename` - The filename of the part, from its `Content-Disposition` header.
+    pub fn filename(mut self, filename: &str) -> Self {
+        self.filename = Some(filename.to_string());
+        self
+    }
+
+    /// Requires the part to have the given content type, ignoring its parameters.
+    ///
+    /// Parts without a `Content-Type` header are `text/plain`.
+    ///
+    /// # Arguments
+    ///
+    /// * `content_type` - The content type of the part, such as `image/png`.
+    pub fn content_type(mut self, content_type: &str) -> Self {
+        self.content_type = Some(content_type.to_string());
+        self
+    }
+
+    /// Requires the contents of the part to match the given regex pattern.
+    ///
+    /// # Arguments
+    ///
+    /// * `value` - The regex pattern to match against.
+    ///
+    /// # Panics
+    ///
+    /// * Panics if the regex pattern is invalid.
+    pub fn contents(mut self, value: &str) -> Self {
+        match regex::Regex::new(value) {
+            Ok(regex) => self.contents = Some(regex),
+            Err(_) => panic!("Invalid regex pattern"),
+        }
+        self
+    }
+
+    /// Requires the part to match the given request matcher.
+    ///
+    /// The matcher sees a request with the headers of the part and its contents as body, so
+    /// body matchers such as `exact_json_body` or `xpath` check the contents of the part.
+    ///
+    /// # Arguments
+    ///
+    /// * `matcher` - The matcher to run against the part.
+    pub fn matching(mut self, matcher: impl MatcherNode<Request> + Send + Sync + 'static) -> Self {
+        self.matchers
+            .push(Arc::new(matcher));
+        self
+    }
+
+    fn matches_part(&self, value: &Request, part: &FormPart) -> bool {
+        self.problems(part)
+            .is_empty()
+            && self
+                .matchers
+                .iter()
+                .all(|matcher| matcher.matches(&part.request(value)))
+    }
+
+    fn problems(&self, part: &FormPart) -> Vec<String> {
+        let mut problems = Vec::new();
+
+        if part.name.as_deref() != Some(self.name.as_str()) {
+            problems.push(format!("name was {:?}", part.name));
+        }
+        if let Some(filename) = &self.filename {
+            if part
+                .filename
+                .as_ref()
+                != Some(filename)
+            {
+                match &part.filename {
+                    Some(actual) => problems.push(format!("filename was {:?}", actual)),
+                    None => problems.push("filename was missing".to_string()),
+                }
+            }
+        }
+        if let Some(content_type) = &self.content_type {
+            if !mime_essence(part.content_type()).eq_ignore_ascii_case(content_type) {
+                problems.push(format!("content type was {}", part.content_type()));
+            }
+        }
+        if let Some(contents) = &self.contents {
+            let actual = String::from_utf8_lossy(&part.contents);
+            if !contents.is_match(&actual) {
+                problems.push(format!("contents were {}", excerpt(&actual)));
+            }
+        }
+
+        problems
+    }
+
+    fn explain_part(&self, value: &Request, parts: &[FormPart]) -> Mismatch {
+        let named = parts
+            .iter()
+            .filter(|part| part.name.as_deref() == Some(self.name.as_str()))
+            .collect::<Vec<_>>();
+
+        let part = match named.as_slice() {
+            [] => return Mismatch::new(self.description(), "missing"),
+            [part] => part,
+            _ => {
+                return Mismatch::new(
+                    self.description(),
+                    format!("none of the {} parts named {:?} matched", named.len(), self.name),
+                )
+            }
+        };
+
+        let problems = self.problems(part);
+        let causes = self
+            .matchers
+            .iter()
+            .filter_map(|matcher| matcher.explain(&part.request(value)))
+            .collect::<Vec<_>>();
+
+        let actual = if problems.is_empty() {
+            format!("part {} did not match", part.describe())
+        } else {
+            problems.join("; ")
+        };
+        Mismatch::with_causes(self.description(), actual, causes)
+    }
+
+    fn description(&self) -> String {
+        let mut description = format!("part {:?}", self.name);
+        if let Some(filename) = &self.filename {
+            description.push_str(&format!(" with filename {:?}", filename));
+        }
+        if let Some(content_type) = &self.content_type {
+            description.push_str(&format!(" of type {}", content_type));
+        }
+        if let Some(contents) = &self.contents {
+            description.push_str(&format!(" with contents matching {:?}", contents));
+        }
+        for matcher in &self.matchers {
+            description.push_str(&format!(" and {}", matcher.description()));
+        }
+        description
+    }
+}
+
+/// A part of a multipart body
+struct FormPart {
+    name: Option<String>,
+    filename: Option<String>,
+    headers: HeaderMap,
+    contents: Bytes,
+}
+
+impl FormPart {
+    fn content_type(&self) -> &str {
+        self.headers
+            .get(CONTENT_TYPE)
+            .and_then(|value| value.to_str().ok())
+            .unwrap_or("text/plain")
+    }
+
+    fn request(&self, value: &Request) -> Request {
+        value.for_part(
+            self.headers.clone(),
+            self.contents
+                .clone(),
+        )
+    }
+
+    fn describe(&self) -> String {
+        let mut description = match &self.name {
+            Some(name) => format!("{:?}", name),
+            None => "without name".to_string(),
+        };
+        if let Some(filename) = &self.filename {
+            description.push_str(&format!(" ({:?}, {})", filename, self.content_type()));
+        }
+        description
+    }
+}
+
+/// Describes the parts of a multipart body for a mismatch report.
+fn describe_parts(parts: &[FormPart]) -> String {
+    if parts.is_empty() {
+        return "form had no parts".to_string();
+    }
+
+    let parts = parts
+        .iter()
+        .map(FormPart::describe)
+        .collect::<Vec<_>>();
+    format!("parts were {}", parts.join(", "))
+}
+
+/// Describes the content type of a request for a mismatch report.
+fn describe_content_type(request: &Request) -> String {
+    match request
+        .headers()
+        .get(CONTENT_TYPE)
+    {
+        Some(value) => format!("content type was {}", String::from_utf8_lossy(value.as_bytes())),
+        None => "content type was missing".to_string(),
+    }
+}
+
+/// Checks the essence of the `Content-Type` header, ignoring its parameters.
+fn has_content_type(headers: &HeaderMap, expected: &str) -> bool {
+    let content_type = headers
+        .get(CONTENT_TYPE)
+        .and_then(|value| value.to_str().ok())
+        .unwrap_or_default();
+
+    mime_essence(content_type).eq_ignore_ascii_case(expected)
+}
+
+/// Returns a media type without its parameters.
+fn mime_essence(content_type: &str) -> &str {
+    content_type
+        .split(';')
+        .next()
+        .unwrap_or_default()
+        .trim()
+}
+
+/// Returns the value of a parameter of a header, such as the boundary of a content type.
+fn header_param(value: &str, name: &str) -> Option<String> {
+    value
+        .split(';')
+        .skip(1)
+        .filter_map(|param| param.split_once('='))
+        .find(|(key, _)| {
+            key.trim()
+                .eq_ignore_ascii_case(name)
+        })
+        .map(|(_, value)| {
+            value
+                .trim()
+                .trim_matches('"')
+                .to_string()
+        })
+}
+
+/// Splits a multipart form body into its parts.
+///
+/// # Returns
+///
+/// * `Option<Vec<FormPart>>` - The parts, `None` when the request is not a valid multipart form.
+fn parse_multipart(request: &Request) -> Option<Vec<FormPart>> {
+    if !has_content_type(request.headers(), MULTIPART) {
+        return None;
+    }
+
+    let content_type = request
+        .headers()
+        .get(CONTENT_TYPE)?
+        .to_str()
+        .ok()?;
+    let boundary = header_param(content_type, "boundary")?;
+    let delimiter = format!("--{}", boundary);
+    let body = request
+        .body()
+        .clone()
+        .unwrap_or_default();
+
+    let start = find(&body, delimiter.as_bytes())?;
+    let mut rest = &body[start + delimiter.len()..];
+    let mut parts = Vec::new();
+
+    loop {
+        if rest.starts_with(b"--") {
+            return Some(parts);
+        }
+
+        let line_end = find(rest, b"\n")?;
+        rest = &rest[line_end + 1..];
+
+        let end = find(rest, format!("\n{}", delimiter).as_bytes())?;
+        let raw_part = &rest[..end];
+        let raw_part = raw_part
+            .strip_suffix(b"\r")
+            .unwrap_or(raw_part);
+        parts.push(parse_part(&body.slice_ref(raw_part))?);
+
+        rest = &rest[end + 1 + delimiter.len()..];
+    }
+}
+
+/// Parses the headers and the contents of a part.
+fn parse_part(raw_part: &Bytes) -> Option<FormPart> {
+    let (head, contents) = match find(raw_part, b"\r\n\r\n") {
+        Some(index) => (&raw_part[..index], raw_part.slice(index + 4..)),
+        None => {
+            let index = find(raw_part, b"\n\n")?;
+            (&raw_part[..index], raw_part.slice(index + 2..))
+        }
+    };
+
+    let mut headers = HeaderMap::new();
+    for line in String::from_utf8_lossy(head).lines() {
+        let Some((name, value)) = line.split_once(':') else {
+            continue;
+        };
+        headers.append(
+            HeaderName::from_bytes(
+                name.trim()
+                    .as_bytes(),
+            )
+            .ok()?,
+            HeaderValue::from_str(value.trim()).ok()?,
+        );
+    }
+
+    let disposition = headers
+        .get(CONTENT_DISPOSITION)
+        .and_then(|value| value.to_str().ok())
+        .unwrap_or_default();
+
+    Some(FormPart {
+        name: header_param(disposition, "name"),
+        filename: header_param(disposition, "filename"),
+        headers,
+        contents,
+    })
+}
+
+/// Returns the position of the first occurrence of a needle in a haystack.
+fn find(haystack: &[u8], needle: &[u8]) -> Option<usize> {
+    haystack
+        .windows(needle.len())
+        .position(|window| window == needle)
+}
@@ -1,7 +1,11 @@
+use base64::{engine::general_purpose::STANDARD, Engine};
 use caramelo::{MatchType::ToHave, Matcher, TypedMatcher};
 use http::HeaderName;
 
-use crate::mock::Request;
+use crate::{
+    matchers::{describe_headers, excerpt, MatcherNode, Mismatch},
+    mock::Request,
+};
 
 /// Trait for converting values into HeaderName.
 pub trait AsHeaderName {
@@ -98,6 +102,19 @@ impl TypedMatcher<Request> for Header {
     }
 }
 
+impl MatcherNode<Request> for Header {
+    fn explain(&self, value: &Request) -> Option<Mismatch> {
+        if self.matches(value) {
+            None
+        } else {
+            Some(Mismatch::new(
+                format!("header {}", self.0),
+                format!("missing; {}", describe_headers(value)),
+            ))
+        }
+    }
+}
+
 /// Creates a matcher that checks if the request path matches the given regex pattern.
 ///
 /// # Arguments
@@ -175,7 +192,30 @@ impl TypedMatcher<Request> for HeaderValue {
     }
 }
 
-/// Creates a matcher that checks if the request has a JWT token in the Authorization header.
+impl MatcherNode<Request> for HeaderValue {
+    fn explain(&self, value: &Request) -> Option<Mismatch> {
+        if self.matches(value) {
+            return None;
+        }
+
+        let actual = match value
+            .headers()
+            .get(&self.name)
+        {
+            Some(header) => {
+                format!("value was {}", excerpt(&String::from_utf8_lossy(header.as_bytes())))
+            }
+            None => format!("missing; {}", describe_headers(value)),
+        };
+
+        Some(Mismatch::new(self.description(), actual))
+    }
+}
+
+/// Creates a matcher that checks if the request has the given JWT token in the Authorization header.
+///
+/// The token is compared as is, use [`jwt_claims`](crate::matchers::jwt_claims) to decode it and
+/// check its claims instead.
 ///
 /// # Arguments
 ///
@@ -223,11 +263,9 @@ impl Matcher<Request> for Jwt {
         value
             .headers()
             .get("Authorization")
-            .is_some_and(|v| {
-                v.to_str()
-                    .unwrap_or("")
-                    .starts_with("Bearer ")
-            })
+            .and_then(|v| v.to_str().ok())
+            .and_then(|v| v.strip_prefix("Bearer "))
+            .is_some_and(|token| token == self.token)
     }
 
     fn description(&self) -> String {
@@ -241,8 +279,32 @@ impl TypedMatcher<Request> for Jwt {
     }
 }
 
+impl MatcherNode<Request> for Jwt {
+    fn explain(&self, value: &Request) -> Option<Mismatch> {
+        if self.matches(value) {
+            return None;
+        }
+
+        let token = value
+            .headers()
+            .get("Authorization")
+            .and_then(|v| v.to_str().ok())
+            .and_then(|v| v.strip_prefix("Bearer "));
+        let actual = match token {
+            Some(token) => format!("bearer token was {}", excerpt(token)),
+            None => describe_authorization(value),
+        };
+
+        Some(Mismatch::new(self.description(), actual))
+    }
+}
+
 /// Creates a matcher that checks if the request has a basic auth token in the Authorization header.
 ///
+/// The header is decoded and both the username and the password must be equal to the given ones.
+/// See [`RequestMock::challenge_basic`](crate::mock::RequestMock::challenge_basic) to answer
+/// requests without credentials with a `401` challenge.
+///
 /// # Arguments
 ///
 /// * `username` - The username to match against.
@@ -289,14 +351,9 @@ pub struct BasicAuth {
 
 impl Matcher<Request> for BasicAuth {
     fn matches(&self, value: &Request) -> bool {
-        value
-            .headers()
-            .get("Authorization")
-            .is_some_and(|v| {
-                v.to_str()
-                    .unwrap_or("")
-                    .starts_with("Basic ")
-            })
+        basic_credentials(value).is_some_and(|(username, password)| {
+            username == self.username && password == self.password
+        })
     }
 
     fn description(&self) -> String {
@@ -309,3 +366,71 @@ impl TypedMatcher<Request> for BasicAuth {
         ToHave
     }
 }
+
+impl MatcherNode<Request> for BasicAuth {
+    fn explain(&self, value: &Request) -> Option<Mismatch> {
+        if self.matches(value) {
+            return None;
+        }
+
+        let actual = match basic_credentials(value) {
+            Some((username, _)) if username != self.username => {
+                format!("username was {}", excerpt(&username))
+            }
+            Some(_) => "password did not match".to_string(),
+            None => describe_authorization(value),
+        };
+
+        Some(Mismatch::new(self.description(), actual))
+    }
+}
+
+/// Decodes the username and password of the Basic Authorization header of a request.
+///
+/// # Arguments
+///
+/// * `request` - The request to decode the credentials from.
+///
+/// # Returns
+///
+/// * `Option<(String, String)>` - The username and password, `None` when the header is missing or
+///   is not valid Basic credentials.
+pub(crate) fn basic_credentials(request: &Request) -> Option<(String, String)> {
+    let header = request
+        .headers()
+        .get(http::header::AUTHORIZATION)?
+        .to_str()
+        .ok()?;
+    let (scheme, encoded) = header.split_once(' ')?;
+    if !scheme.eq_ignore_ascii_case("Basic") {
+        return None;
+    }
+
+    let decoded = STANDARD
+        .decode(encoded.trim())
+        .ok()?;
+    let (username, password) = String::from_utf8(decoded)
+        .ok()?
+        .split_once(':')
+        .map(|(username, password)| (username.to_string(), password.to_string()))?;
+
+    Some((username, password))
+}
+
+/// Describes the Authorization header of a request for a mismatch report.
+pub(crate) fn describe_authorization(request: &Request) -> String {
+    match request
+        .headers()
+        .get(http::header::AUTHORIZATION)
+    {
+        Some(header) => {
+            let value = String::from_utf8_lossy(header.as_bytes());
+            let scheme = value
+                .split_once(' ')
+                .map(|(scheme, _)| scheme)
+                .unwrap_or(&value);
+            format!("Authorization header used scheme {}", excerpt(scheme))
+        }
+        None => format!("Authorization header missing; {}", describe_headers(request)),
+    }
+}
@@ -0,0 +1,444 @@
+use std::{cmp::Ordering, fmt};
+
+use caramelo::{MatchType::ToHave, Matcher, TypedMatcher};
+use jsonpath_rust::JsonPath;
+use serde_json::Value;
+use sonic_rs::Serialize;
+
+use crate::{
+    matchers::{describe_body, JsonComparison, MatcherNode, Mismatch},
+    mock::Request,
+};
+
+/// Creates a selector for the values of the request body at the given JSONPath.
+///
+/// The selector is turned into a matcher by one of its assertions, every selected value must
+/// satisfy the assertion and at least one value must be selected.
+///
+/// # Arguments
+///
+/// * `path` - The JSONPath query selecting the values to check.
+///
+/// # Returns
+///
+/// * `JsonPathSelector` - A selector to build the matcher from.
+///
+/// # Examples
+///
+/// ```rust
+/// use easyhttpmock::matchers::{json_path, JsonType};
+///
+/// let name = json_path("$.name").equals(&"John");
+/// let prices = json_path("$.items[*].price").gt(0);
+/// let items = json_path("$.items").len(3);
+/// let id = json_path("$.id").is(JsonType::String);
+/// ```
+pub fn json_path(path: &str) -> JsonPathSelector {
+    JsonPathSelector { path: path.to_string() }
+}
+
+/// Selector for the values of the request body at a JSONPath.
+///
+/// # Examples
+///
+/// ```rust
+/// use easyhttpmock::matchers::{json_path, JsonPredicate};
+///
+/// let matcher = json_path("$.items").every(JsonPredicate::GreaterThan(0.0.into()));
+/// ```
+pub struct JsonPathSelector {
+    path: String,
+}
+
+impl JsonPathSelector {
+    /// Checks that the selected values satisfy the given predicate.
+    pub fn satisfies(self, predicate: JsonPredicate) -> BodyWithJsonPath {
+        BodyWithJsonPath { path: self.path, predicate }
+    }
+
+    /// Checks that the selected values are equal to the given value.
+    ///
+    /// # Panics
+    ///
+    /// * Panics if the value cannot be serialized to JSON.
+    pub fn equals<T: Serialize>(self, value: &T) -> BodyWithJsonPath {
+        let expected =
+            sonic_rs::to_string(value).and_then(|json| sonic_rs::from_str::<Value>(&json));
+        match expected {
+            Ok(expected) => self.satisfies(JsonPredicate::Equals(expected)),
+            Err(e) => panic!("Failed to serialize JSON: {}", e),
+        }
+    }
+
+    /// Checks that the selected values are strings matching the given regex pattern.
+    ///
+    /// # Panics
+    ///
+    /// * Panics if the regex pattern is invalid.
+    pub fn matches(self, pattern: &str) -> BodyWithJsonPath {
+        match regex::Regex::new(pattern) {
+            Ok(regex) => self.satisfies(JsonPredicate::Matches(regex)),
+            Err(_) => panic!("Invalid regex pattern"),
+        }
+    }
+
+    /// Checks that the selected values are numbers greater than the given one.
+    pub fn gt(self, value: impl Into<JsonNumber>) -> BodyWithJsonPath {
+        self.satisfies(JsonPredicate::GreaterThan(value.into()))
+    }
+
+    /// Checks that the selected values are numbers greater than or equal to the given one.
+    pub fn ge(self, value: impl Into<JsonNumber>) -> BodyWithJsonPath {
+        self.satisfies(JsonPredicate::GreaterOrEqual(value.into()))
+    }
+
+    /// Checks that the selected values are numbers less than the given one.
+    pub fn lt(self, value: impl Into<JsonNumber>) -> BodyWithJsonPath {
+        self.satisfies(JsonPredicate::LessThan(value.into()))
+    }
+
+    /// Checks that the selected values are numbers less than or equal to the given one.
+    pub fn le(self, value: impl Into<JsonNumber>) -> BodyWithJsonPath {
+        self.satisfies(JsonPredicate::LessOrEqual(value.into()))
+    }
+
+    /// Checks that the selected values are arrays, strings or objects of the given length.
+    pub fn len(self, len: usize) -> BodyWithJsonPath {
+        self.satisfies(JsonPredicate::Length(len))
+    }
+
+    /// Checks that the selected values are of the given JSON type.
+    pub fn is(self, json_type: JsonType) -> BodyWithJsonPath {
+        self.satisfies(JsonPredicate::Type(json_type))
+    }
+
+    /// Checks that the selected values are arrays whose elements all satisfy the given predicate.
+    pub fn every(self, predicate: JsonPredicate) -> BodyWithJsonPath {
+        self.satisfies(JsonPredicate::Every(Box::new(predicate)))
+    }
+}
+
+/// JSON value types, as checked by [`JsonPathSelector::is`]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum JsonType {
+    /// `null`
+    Null,
+    /// `true` or `false`
+    Bool,
+    /// Any number
+    Number,
+    /// A number without fractional part
+    Integer,
+    /// A string
+    String,
+    /// An array
+    Array,
+    /// An object
+    Object,
+}
+
+impl JsonType {
+    fn of(value: &Value) -> JsonType {
+        match value {
+            Value::Null => JsonType::Null,
+            Value::Bool(_) => JsonType::Bool,
+            Value::Number(number) if number.is_i64() || number.is_u64() => JsonType::Integer,
+            Value::Number(_) => JsonType::Number,
+            Value::String(_) => JsonType::String,
+            Value::Array(_) => JsonType::Array,
+            Value::Object(_) => JsonType::Object,
+        }
+    }
+
+    fn accepts(self, value: &Value) -> bool {
+        let actual = JsonType::of(value);
+        actual == self || (self == JsonType::Number && actual == JsonType::Integer)
+    }
+}
+
+/// Number the selected values are compared with by the JSONPath assertions
+///
+/// Integers are compared as integers, so large values keep their precision, and as floating
+/// point numbers only when either side has a fractional part.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum JsonNumber {
+    /// A signed integer
+    Int(i64),
+    /// An unsigned integer
+    UInt(u64),
+    /// A floating point number
+    Float(f64),
+}
+
+impl JsonNumber {
+    /// Compares the given JSON value with this number.
+    ///
+    /// # Arguments
+    ///
+    /// * `value` - The JSON value to compare.
+    ///
+    /// # Returns
+    ///
+    /// * `Option<Ordering>` - The ordering of the value relative to this number, `None` when the
+    ///   value is not a number.
+    fn compare(&self, value: &Value) -> Option<Ordering> {
+        let Value::Number(value) = value else {
+            return None;
+        };
+
+        let integer = value
+            .as_i64()
+            .map(i128::from)
+            .or_else(|| {
+                value
+                    .as_u64()
+                    .map(i128::from)
+            });
+        match (integer, self) {
+            (Some(value), JsonNumber::Int(bound)) => Some(value.cmp(&i128::from(*bound))),
+            (Some(value), JsonNumber::UInt(bound)) => Some(value.cmp(&i128::from(*bound))),
+            _ => value
+                .as_f64()?
+                .partial_cmp(&self.as_f64()),
+        }
+    }
+
+    fn as_f64(&self) -> f64 {
+        match *self {
+            JsonNumber::Int(number) => number as f64,
+            JsonNumber::UInt(number) => number as f64,
+            JsonNumber::Float(number) => number,
+        }
+    }
+}
+
+impl fmt::Display for JsonNumber {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            JsonNumber::Int(number) => write!(f, "{}", number),
+            JsonNumber::UInt(number) => write!(f, "{}", number),
+            JsonNumber::Float(number) => write!(f, "{}", number),
+        }
+    }
+}
+
+macro_rules! json_number_from {
+    ($variant:ident, $target:ty, $($source:ty),+) => {
+        $(
+            impl From<$source> for JsonNumber {
+                fn from(number: $source) -> Self {
+                    JsonNumber::$variant(<$target>::from(number))
+                }
+            }
+        )+
+    };
+}
+
+json_number_from!(Int, i64, i8, i16, i32, i64);
+json_number_from!(UInt, u64, u8, u16, u32, u64);
+json_number_from!(Float, f64, f32, f64);
+
+impl From<isize> for JsonNumber {
+    fn from(number: isize) -> Self {
+        JsonNumber::Int(number as i64)
+    }
+}
+
+impl From<usize> for JsonNumber {
+    fn from(number: usize) -> Self {
+        JsonNumber::UInt(number as u64)
+    }
+}
+
+/// Assertion applied to each value selected by a JSONPath
+#[derive(Clone, Debug)]
+pub enum JsonPredicate {
+    /// The value is structurally equal to the given JSON
+    Equals(Value),
+    /// The value is a string matching the regex
+    Matches(regex::Regex),
+    /// The value is a number greater than the given one
+    GreaterThan(JsonNumber),
+    /// The value is a number greater than or equal to the given one
+    GreaterOrEqual(JsonNumber),
+    /// The value is a number less than the given one
+    LessThan(JsonNumber),
+    /// The value is a number less than or equal to the given one
+    LessOrEqual(JsonNumber),
+    /// The value is an array, string or object of the given length
+    Length(usize),
+    /// The value is of the given JSON type
+    Type(JsonType),
+    /// The value is an array whose elements all satisfy the predicate
+    Every(Box<JsonPredicate>),
+}
+
+impl JsonPredicate {
+    /// Checks whether the given value satisfies this predicate.
+    ///
+    /// # Arguments
+    ///
+    /// * `value` - The JSON value to check.
+    ///
+    /// # Returns
+    ///
+    /// * `bool` - Whether the value satisfies the predicate.
+    pub fn test(&self, value: &Value) -> bool {
+        match self {
+            JsonPredicate::Equals(expected) => JsonComparison::default().equals(expected, value),
+            JsonPredicate::Matches(regex) => value
+                .as_str()
+                .is_some_and(|value| regex.is_match(value)),
+            JsonPredicate::GreaterThan(bound) => bound
+                .compare(value)
+                .is_some_and(Ordering::is_gt),
+            JsonPredicate::GreaterOrEqual(bound) => bound
+                .compare(value)
+                .is_some_and(Ordering::is_ge),
+            JsonPredicate::LessThan(bound) => bound
+                .compare(value)
+                .is_some_and(Ordering::is_lt),
+            JsonPredicate::LessOrEqual(bound) => bound
+                .compare(value)
+                .is_some_and(Ordering::is_le),
+            JsonPredicate::Length(len) => match value {
+                Value::Array(array) => array.len() == *len,
+                Value::String(string) => {
+                    string
+                        .chars()
+                        .count()
+                        == *len
+                }
+                Value::Object(object) => object.len() == *len,
+                _ => false,
+            },
+            JsonPredicate::Type(json_type) => json_type.accepts(value),
+            JsonPredicate::Every(predicate) => value
+                .as_array()
+                .is_some_and(|array| {
+                    array
+                        .iter()
+                        .all(|element| predicate.test(element))
+                }),
+        }
+    }
+
+    pub(crate) fn describe(&self) -> String {
+        match self {
+            JsonPredicate::Equals(expected) => format!("equal to {}", expected),
+            JsonPredicate::Matches(regex) => format!("matching {:?}", regex),
+            JsonPredicate::GreaterThan(bound) => format!("greater than {}", bound),
+            JsonPredicate::GreaterOrEqual(bound) => format!("greater than or equal to {}", bound),
+            JsonPredicate::LessThan(bound) => format!("less than {}", bound),
+            JsonPredicate::LessOrEqual(bound) => format!("less than or equal to {}", bound),
+            JsonPredicate::Length(len) => format!("of length {}", len),
+            JsonPredicate::Type(json_type) => format!("of type {:?}", json_type),
+            JsonPredicate::Every(predicate) => {
+                format!("with every element {}", predicate.describe())
+            }
+        }
+    }
+}
+
+#[derive(Clone)]
+/// A matcher that checks the values of the request body selected by a JSONPath.
+///
+/// # Arguments
+///
+/// * `path` - The JSONPath query selecting the values to check.
+/// * `predicate` - The assertion every selected value must satisfy.
+///
+/// # Returns
+///
+/// * `BodyWithJsonPath` - A matcher that checks the selected values.
+///
+/// # Examples
+///
+/// ```rust
+/// use easyhttpmock::matchers::json_path;
+///
+/// let matcher = json_path("$.items[*].price").gt(0);
+/// ```
+pub struct BodyWithJsonPath {
+    path: String,
+    predicate: JsonPredicate,
+}
+
+impl BodyWithJsonPath {
+    /// Returns the selected values that fail the predicate, along with their paths.
+    fn failures(&self, value: &Request) -> Result<Vec<(String, Value)>, String> {
+        let Some(body) = value.body() else {
+            return Err(describe_body(value));
+        };
+
+        let json = sonic_rs::from_str::<Value>(&String::from_utf8_lossy(body))
+            .map_err(|e| format!("body was not valid JSON: {}", e))?;
+
+        let results = json
+            .query_with_path(&self.path)
+            .map_err(|e| format!("invalid JSONPath {}: {}", self.path, e))?;
+
+        if results.is_empty() {
+            return Err(format!("no value selected by {}", self.path));
+        }
+
+        Ok(results
+            .into_iter()
+            .filter(|result| {
+                !self
+                    .predicate
+                    .test(result.clone().val())
+            })
+            .map(|result| {
+                (
+                    result
+                        .clone()
+                        .path(),
+                    result.val().clone(),
+                )
+            })
+            .collect())
+    }
+}
+
+impl Matcher<Request> for BodyWithJsonPath {
+    fn matches(&self, value: &Request) -> bool {
+        self.failures(value)
+            .is_ok_and(|failures| failures.is_empty())
+    }
+
+    fn description(&self) -> String {
+        format!(
+            "body values at {} {}",
+            self.path,
+            self.predicate
+                .describe()
+        )
+    }
+}
+
+impl TypedMatcher<Request> for BodyWithJsonPath {
+    fn matcher_type(&self) -> caramelo::MatchType {
+        ToHave
+    }
+}
+
+impl MatcherNode<Request> for BodyWithJsonPath {
+    fn explain(&self, value: &Request) -> Option<Mismatch> {
+        match self.failures(value) {
+            Ok(failures) if failures.is_empty() => None,
+            Ok(failures) => {
+                let causes = failures
+                    .into_iter()
+                    .map(|(path, value)| Mismatch::new(path, format!("value was {}", value)))
+                    .collect::<Vec<_>>();
+                Some(Mismatch::with_causes(
+                    self.description(),
+                    format!("{} selected values failed", causes.len()),
+                    causes,
+                ))
+            }
+            Err(actual) => Some(Mismatch::new(self.description(), actual)),
+        }
+    }
+}
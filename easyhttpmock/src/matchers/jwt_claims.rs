@@ -0,0 +1,353 @@
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use caramelo::{MatchType::ToHave, Matcher, TypedMatcher};
+use jsonpath_rust::JsonPath;
+use jsonwebtoken::{dangerous::insecure_decode, decode, Algorithm, DecodingKey, Validation};
+use serde_json::Value;
+
+use crate::{
+    matchers::{describe_authorization, JsonPredicate, MatcherNode, Mismatch},
+    mock::Request,
+};
+
+/// Creates a matcher that decodes the bearer JWT of the Authorization header and checks its
+/// claims.
+///
+/// The signature is not verified unless a key is given with one of the `verify_*` methods.
+///
+/// # Returns
+///
+/// * `BearerJwt` - A matcher checking the claims of the bearer token.
+///
+/// # Examples
+///
+/// ```rust
+/// use easyhttpmock::matchers::{jwt_claims, JsonPredicate};
+/// use serde_json::json;
+///
+/// let matcher = jwt_claims()
+///     .issuer("https://auth.example.com")
+///     .audience("orders-api")
+///     .scope("orders:read")
+///     .not_expired()
+///     .claim("$.tenant", JsonPredicate::Equals(json!("acme")))
+///     .verify_hs256(b"secret");
+/// ```
+pub fn jwt_claims() -> BearerJwt {
+    BearerJwt { checks: Vec::new(), verification: None }
+}
+
+#[derive(Clone)]
+enum ClaimCheck {
+    Issuer(String),
+    Audience(String),
+    Subject(String),
+    Scope(String),
+    NotExpired,
+    Claim(String, JsonPredicate),
+}
+
+impl ClaimCheck {
+    fn describe(&self) -> String {
+        match self {
+            ClaimCheck::Issuer(issuer) => format!("issuer {:?}", issuer),
+            ClaimCheck::Audience(audience) => format!("audience {:?}", audience),
+            ClaimCheck::Subject(subject) => format!("subject {:?}", subject),
+            ClaimCheck::Scope(scope) => format!("scope {:?}", scope),
+            ClaimCheck::NotExpired => "not expired".to_string(),
+            ClaimCheck::Claim(path, predicate) => {
+                format!("claims at {} {}", path, predicate.describe())
+            }
+        }
+    }
+
+    /// Checks the decoded claims, returning what they contained when the check fails.
+    fn check(&self, claims: &Value) -> Option<String> {
+        match self {
+            ClaimCheck::Issuer(issuer) => check_string(claims, "iss", issuer),
+            ClaimCheck::Subject(subject) => check_string(claims, "sub", subject),
+            ClaimCheck::Audience(audience) => match claims.get("aud") {
+                Some(Value::String(actual)) if actual == audience => None,
+                Some(Value::Array(actual))
+                    if actual
+                        .iter()
+                        .any(|actual| actual.as_str() == Some(audience)) =>
+                {
+                    None
+                }
+                Some(actual) => Some(format!("aud was {}", actual)),
+                None => Some("aud claim missing".to_string()),
+            },
+            ClaimCheck::Scope(scope) => {
+                let scopes = scopes(claims);
+                if scopes.contains(scope) {
+                    None
+                } else if scopes.is_empty() {
+                    Some("no scope claim".to_string())
+                } else {
+                    Some(format!("scopes were {}", scopes.join(" ")))
+                }
+            }
+            ClaimCheck::NotExpired => {
+                let now = SystemTime::now()
+                    .duration_since(UNIX_EPOCH)
+                    .map(|now| now.as_secs())
+                    .unwrap_or_default();
+                match claims
+                    .get("exp")
+                    .and_then(Value::as_u64)
+                {
+                    Some(exp) if exp > now => None,
+                    Some(exp) => Some(format!("expired {}s ago", now - exp)),
+                    None => Some("exp claim missing".to_string()),
+                }
+            }
+            ClaimCheck::Claim(path, predicate) => match claims.query_with_path(path) {
+                Ok(results) if results.is_empty() => Some(format!("no value selected by {}", path)),
+                Ok(results) => results
+                    .into_iter()
+                    .find(|result| !predicate.test(result.clone().val()))
+                    .map(|result| {
+                        let path = result
+                            .clone()
+                            .path();
+                        format!("value at {} was {}", path, result.val())
+                    }),
+                Err(e) => Some(format!("invalid JSONPath {}: {}", path, e)),
+            },
+        }
+    }
+}
+
+fn check_string(claims: &Value, name: &str, expected: &str) -> Option<String> {
+    match claims.get(name) {
+        Some(Value::String(actual)) if actual == expected => None,
+        Some(actual) => Some(format!("{} was {}", name, actual)),
+        None => Some(format!("{} claim missing", name)),
+    }
+}
+
+/// Collects the scopes of the `scope` claim, or of the `scp` claim used by some providers.
+fn scopes(claims: &Value) -> Vec<String> {
+    match claims
+        .get("scope")
+        .or_else(|| claims.get("scp"))
+    {
+        Some(Value::String(scopes)) => scopes
+            .split_whitespace()
+            .map(str::to_string)
+            .collect(),
+        Some(Value::Array(scopes)) => scopes
+            .iter()
+            .filter_map(Value::as_str)
+            .map(str::to_string)
+            .collect(),
+        _ => Vec::new(),
+    }
+}
+
+#[derive(Clone)]
+struct Verification {
+    algorithm: Algorithm,
+    key: DecodingKey,
+}
+
+#[derive(Clone)]
+/// A matcher that decodes the bearer JWT of the Authorization header and checks its claims.
+///
+/// # Arguments
+///
+/// * `checks` - The assertions on the claims of the token.
+/// * `verification` - The algorithm and key used to verify the signature, if any.
+///
+/// # Returns
+///
+/// * `BearerJwt` - A matcher checking the claims of the bearer token.
+///
+/// # Examples
+///
+/// ```rust
+/// use easyhttpmock::matchers::jwt_claims;
+///
+/// let matcher = jwt_claims()
+///     .subject("user-1")
+///     .not_expired();
+/// ```
+pub struct BearerJwt {
+    checks: Vec<ClaimCheck>,
+    verification: Option<Verification>,
+}
+
+impl BearerJwt {
+    /// Checks that the `iss` claim is equal to the given issuer.
+    pub fn issuer(mut self, issuer: &str) -> Self {
+        self.checks
+            .push(ClaimCheck::Issuer(issuer.to_string()));
+        self
+    }
+
+    /// Checks that the `aud` claim is, or contains, the given audience.
+    pub fn audience(mut self, audience: &str) -> Self {
+        self.checks
+            .push(ClaimCheck::Audience(audience.to_string()));
+        self
+    }
+
+    /// Checks that the `sub` claim is equal to the given subject.
+    pub fn subject(mut self, subject: &str) -> Self {
+        self.checks
+            .push(ClaimCheck::Subject(subject.to_string()));
+        self
+    }
+
+    /// Checks that the space separated `scope` claim, or the `scp` claim, contains the given
+    /// scope.
+    pub fn scope(mut self, scope: &str) -> Self {
+        self.checks
+            .push(ClaimCheck::Scope(scope.to_string()));
+        self
+    }
+
+    /// Checks that the `exp` claim is present and in the future.
+    pub fn not_expired(mut self) -> Self {
+        self.checks
+            .push(ClaimCheck::NotExpired);
+        self
+    }
+
+    /// Checks that the claims selected by the given JSONPath satisfy the predicate.
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - The JSONPath query selecting the claims to check.
+    /// * `predicate` - The assertion every selected claim must satisfy.
+    pub fn claim(mut self, path: &str, predicate: JsonPredicate) -> Self {
+        self.checks
+            .push(ClaimCheck::Claim(path.to_string(), predicate));
+        self
+    }
+
+    /// Verifies the signature of the token with the given HS256 secret.
+    pub fn verify_hs256(self, secret: &[u8]) -> Self {
+        self.verify(Algorithm::HS256, DecodingKey::from_secret(secret))
+    }
+
+    /// Verifies the signature of the token with the given RS256 public key.
+    ///
+    /// # Panics
+    ///
+    /// * Panics if the key is not a PEM encoded RSA public key.
+    pub fn verify_rs256_pem(self, pem: &[u8]) -> Self {
+        match DecodingKey::from_rsa_pem(pem) {
+            Ok(key) => self.verify(Algorithm::RS256, key),
+            Err(e) => panic!("Invalid RSA public key: {}", e),
+        }
+    }
+
+    /// Verifies the signature of the token with the given ES256 public key.
+    ///
+    /// # Panics
+    ///
+    /// * Panics if the key is not a PEM encoded EC public key.
+    pub fn verify_es256_pem(self, pem: &[u8]) -> Self {
+        match DecodingKey::from_ec_pem(pem) {
+            Ok(key) => self.verify(Algorithm::ES256, key),
+            Err(e) => panic!("Invalid EC public key: {}", e),
+        }
+    }
+
+    fn verify(mut self, algorithm: Algorithm, key: DecodingKey) -> Self {
+        self.verification = Some(Verification { algorithm, key });
+        self
+    }
+
+    /// Decodes the claims of the bearer token, verifying its signature when a key was given.
+    fn claims(&self, value: &Request) -> Result<Value, String> {
+        let token = value
+            .headers()
+            .get(http::header::AUTHORIZATION)
+            .and_then(|header| header.to_str().ok())
+            .and_then(|header| header.strip_prefix("Bearer "))
+            .ok_or_else(|| describe_authorization(value))?;
+
+        match &self.verification {
+            Some(verification) => {
+                // Expiry and audience are checked by the claim assertions instead.
+                let mut validation = Validation::new(verification.algorithm);
+                validation
+                    .required_spec_claims
+                    .clear();
+                validation.validate_exp = false;
+                validation.validate_aud = false;
+
+                decode::<Value>(token, &verification.key, &validation)
+                    .map(|data| data.claims)
+                    .map_err(|e| format!("token could not be verified: {}", e))
+            }
+            None => insecure_decode::<Value>(token)
+                .map(|data| data.claims)
+                .map_err(|e| format!("token was not a valid JWT: {}", e)),
+        }
+    }
+
+    fn failures(&self, value: &Request) -> Result<Vec<Mismatch>, String> {
+        let claims = self.claims(value)?;
+
+        Ok(self
+            .checks
+            .iter()
+            .filter_map(|check| {
+                check
+                    .check(&claims)
+                    .map(|actual| Mismatch::new(check.describe(), actual))
+            })
+            .collect())
+    }
+}
+
+impl Matcher<Request> for BearerJwt {
+    fn matches(&self, value: &Request) -> bool {
+        self.failures(value)
+            .is_ok_and(|failures| failures.is_empty())
+    }
+
+    fn description(&self) -> String {
+        let mut description = match &self.verification {
+            Some(verification) => format!("bearer JWT signed with {:?}", verification.algorithm),
+            None => "bearer JWT".to_string(),
+        };
+        if !self
+            .checks
+            .is_empty()
+        {
+            let checks = self
+                .checks
+                .iter()
+                .map(ClaimCheck::describe)
+                .collect::<Vec<_>>()
+                .join(", ");
+            description = format!("{} with {}", description, checks);
+        }
+        description
+    }
+}
+
+impl TypedMatcher<Request> for BearerJwt {
+    fn matcher_type(&self) -> caramelo::MatchType {
+        ToHave
+    }
+}
+
+impl MatcherNode<Request> for BearerJwt {
+    fn explain(&self, value: &Request) -> Option<Mismatch> {
+        match self.failures(value) {
+            Ok(failures) if failures.is_empty() => None,
+            Ok(failures) => Some(Mismatch::with_causes(
+                self.description(),
+                format!("{} claim checks failed", failures.len()),
+                failures,
+            )),
+            Err(actual) => Some(Mismatch::new(self.description(), actual)),
+        }
+    }
+}
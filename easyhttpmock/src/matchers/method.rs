@@ -2,7 +2,10 @@ use std::sync::Arc;
 
 use caramelo::{MatchType::ToHave, Matcher, TypedMatcher};
 
-use crate::mock::Request;
+use crate::{
+    matchers::{MatcherNode, Mismatch},
+    mock::Request,
+};
 
 /// Trait for converting values into http::Method.
 pub trait AsMethod {
@@ -55,7 +58,7 @@ impl AsMethod for &str {
 ///
 /// let matcher = method("GET");
 /// ```
-pub fn method<M>(value: M) -> Arc<dyn TypedMatcher<Request> + Send + Sync + 'static>
+pub fn method<M>(value: M) -> Arc<dyn MatcherNode<Request> + Send + Sync + 'static>
 where
     M: AsMethod,
 {
@@ -100,3 +103,13 @@ impl TypedMatcher<Request> for Method {
         ToHave
     }
 }
+
+impl MatcherNode<Request> for Method {
+    fn explain(&self, value: &Request) -> Option<Mismatch> {
+        if self.matches(value) {
+            None
+        } else {
+            Some(Mismatch::new(self.description(), format!("method was {}", value.method())))
+        }
+    }
+}
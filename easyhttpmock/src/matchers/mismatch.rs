@@ -0,0 +1,267 @@
+use std::{collections::HashMap, fmt};
+
+use caramelo::{MatchType, Matcher, TypedMatcher};
+
+use crate::mock::Request;
+
+/// A node of the matcher tree able to explain why a value was rejected.
+///
+/// Leaf matchers only need to describe what they expected and what the value actually
+/// contained, combinators such as [`And`](crate::matchers::And) and [`Or`](crate::matchers::Or)
+/// collect the explanations and the captured parameters of their children.
+///
+/// Mocks and combinators take matcher nodes rather than any [`TypedMatcher`]. A custom matcher
+/// becomes a node with an empty `impl MatcherNode<Request> for MyMatcher {}`, relying on the
+/// default explanation, while matchers defined in other crates are wrapped with [`node`].
+///
+/// # Examples
+///
+/// ```rust
+/// use easyhttpmock::matchers::{header, MatcherNode};
+/// use easyhttpmock::mock::Request;
+/// use http::Uri;
+///
+/// let request = Request::get(Uri::from_static("/api/users"))
+///     .header("accept", "application/json")
+///     .empty()
+///     .unwrap();
+///
+/// let mismatch = header("x-api-key").explain(&request).unwrap();
+/// assert_eq!(mismatch.to_string(), "header x-api-key: missing; present headers: accept");
+/// ```
+pub trait MatcherNode<T>: TypedMatcher<T> {
+    /// Explains why the given value does not match.
+    ///
+    /// # Arguments
+    ///
+    /// * `value` - The value to explain.
+    ///
+    /// # Returns
+    ///
+    /// * `Option<Mismatch>` - `None` when the value matches, the mismatch report otherwise.
+    fn explain(&self, value: &T) -> Option<Mismatch> {
+        if self.matches(value) {
+            None
+        } else {
+            Some(Mismatch::new(self.description(), "did not match"))
+        }
+    }
+
+    /// Returns the named parameters captured from the given value.
+    ///
+    /// # Arguments
+    ///
+    /// * `value` - The value to capture parameters from.
+    ///
+    /// # Returns
+    ///
+    /// * `PathParams` - The captured parameters, empty when the matcher captures nothing.
+    fn captures(&self, _value: &T) -> PathParams {
+        PathParams::new()
+    }
+}
+
+/// Wraps a matcher implementing only [`TypedMatcher`] into a [`MatcherNode`].
+///
+/// Mismatches of the wrapped matcher are reported with its description and it captures no
+/// parameters.
+///
+/// # Arguments
+///
+/// * `matcher` - The matcher to wrap.
+///
+/// # Returns
+///
+/// * `Node<M>` - The matcher node.
+///
+/// # Examples
+///
+/// ```rust
+/// use caramelo::matchers::eq;
+/// use easyhttpmock::matchers::node;
+/// use easyhttpmock::mock::{given, Request};
+/// use http::Uri;
+///
+/// let request = Request::get(Uri::from_static("/api/users"))
+///     .empty()
+///     .unwrap();
+/// let mock = given(node(eq(request)));
+/// ```
+pub fn node<M>(matcher: M) -> Node<M> {
+    Node(matcher)
+}
+
+/// Matcher node wrapping a matcher implementing only [`TypedMatcher`], built by [`node`].
+pub struct Node<M>(M);
+
+impl<T, M: Matcher<T>> Matcher<T> for Node<M> {
+    fn matches(&self, value: &T) -> bool {
+        self.0
+            .matches(value)
+    }
+
+    fn description(&self) -> String {
+        self.0.description()
+    }
+}
+
+impl<T, M: TypedMatcher<T>> TypedMatcher<T> for Node<M> {
+    fn matcher_type(&self) -> MatchType {
+        self.0
+            .matcher_type()
+    }
+}
+
+impl<T, M: TypedMatcher<T>> MatcherNode<T> for Node<M> {}
+
+/// Named parameters captured while matching a request, such as path template segments
+pub type PathParams = HashMap<String, String>;
+
+/// Structured report of a matcher that rejected a value.
+///
+/// # Examples
+///
+/// ```rust
+/// use easyhttpmock::matchers::Mismatch;
+///
+/// let mismatch = Mismatch::new("method GET", "was POST");
+/// assert_eq!(mismatch.to_string(), "method GET: was POST");
+/// ```
+#[derive(Clone, Debug, PartialEq)]
+pub struct Mismatch {
+    expected: String,
+    actual: String,
+    causes: Vec<Mismatch>,
+}
+
+impl Mismatch {
+    /// Creates a mismatch for a leaf matcher.
+    ///
+    /// # Arguments
+    ///
+    /// * `expected` - What the matcher expected.
+    /// * `actual` - What the value actually contained.
+    ///
+    /// # Returns
+    ///
+    /// * `Mismatch` - The mismatch report.
+    pub fn new(expected: impl Into<String>, actual: impl Into<String>) -> Self {
+        Mismatch { expected: expected.into(), actual: actual.into(), causes: Vec::new() }
+    }
+
+    /// Creates a mismatch for a combinator, caused by the mismatches of its children.
+    ///
+    /// # Arguments
+    ///
+    /// * `expected` - What the combinator expected.
+    /// * `actual` - A summary of what went wrong.
+    /// * `causes` - The mismatches reported by the children.
+    ///
+    /// # Returns
+    ///
+    /// * `Mismatch` - The mismatch report.
+    pub fn with_causes(
+        expected: impl Into<String>,
+        actual: impl Into<String>,
+        causes: Vec<Mismatch>,
+    ) -> Self {
+        Mismatch { expected: expected.into(), actual: actual.into(), causes }
+    }
+
+    #[inline]
+    /// Get what the matcher expected
+    pub fn expected(&self) -> &str {
+        &self.expected
+    }
+
+    #[inline]
+    /// Get what the value actually contained
+    pub fn actual(&self) -> &str {
+        &self.actual
+    }
+
+    #[inline]
+    /// Get the mismatches of the children matchers
+    pub fn causes(&self) -> &[Mismatch] {
+        &self.causes
+    }
+
+    /// Returns the leaf mismatches of this report, in the order they were found.
+    ///
+    /// # Returns
+    ///
+    /// * `Vec<&Mismatch>` - The mismatches with no further causes.
+    pub fn leaves(&self) -> Vec<&Mismatch> {
+        if self
+            .causes
+            .is_empty()
+        {
+            return vec![self];
+        }
+
+        self.causes
+            .iter()
+            .flat_map(|cause| cause.leaves())
+            .collect()
+    }
+
+    fn write_indented(&self, f: &mut fmt::Formatter<'_>, depth: usize) -> fmt::Result {
+        write!(f, "{}: {}", self.expected, self.actual)?;
+        for cause in &self.causes {
+            write!(f, "\n{}- ", "  ".repeat(depth + 1))?;
+            cause.write_indented(f, depth + 1)?;
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Display for Mismatch {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.write_indented(f, 0)
+    }
+}
+
+/// Truncates a value so it can be quoted in a mismatch report.
+pub(crate) fn excerpt(value: &str) -> String {
+    const MAX_LEN: usize = 200;
+
+    if value
+        .chars()
+        .count()
+        > MAX_LEN
+    {
+        let truncated = value
+            .chars()
+            .take(MAX_LEN)
+            .collect::<String>();
+        format!("{:?}...", truncated)
+    } else {
+        format!("{:?}", value)
+    }
+}
+
+/// Describes the body of a request for a mismatch report.
+pub(crate) fn describe_body(request: &Request) -> String {
+    match request.body() {
+        Some(body) if !body.is_empty() => {
+            format!("body was {}", excerpt(&String::from_utf8_lossy(body)))
+        }
+        _ => "body was empty".to_string(),
+    }
+}
+
+/// Describes the headers of a request for a mismatch report.
+pub(crate) fn describe_headers(request: &Request) -> String {
+    let mut names = request
+        .headers()
+        .keys()
+        .map(|name| name.as_str())
+        .collect::<Vec<_>>();
+
+    if names.is_empty() {
+        "no headers present".to_string()
+    } else {
+        names.sort_unstable();
+        format!("present headers: {}", names.join(", "))
+    }
+}
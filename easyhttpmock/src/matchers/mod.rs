@@ -1,15 +1,40 @@
 mod and;
+mod assignment;
 mod body;
+#[cfg(feature = "mtls")]
+mod client_cert;
+mod encoding;
+mod form;
 mod header;
+#[cfg(feature = "json")]
+mod json_path;
+#[cfg(feature = "jwt")]
+mod jwt_claims;
 mod method;
+mod mismatch;
 mod or;
 mod path;
 mod query;
+mod version;
+#[cfg(feature = "xml")]
+mod xpath;
 
 pub use and::*;
 pub use body::*;
+#[cfg(feature = "mtls")]
+pub use client_cert::*;
+pub use encoding::*;
+pub use form::*;
 pub use header::*;
+#[cfg(feature = "json")]
+pub use json_path::*;
+#[cfg(feature = "jwt")]
+pub use jwt_claims::*;
 pub use method::*;
+pub use mismatch::*;
 pub use or::*;
 pub use path::*;
 pub use query::*;
+pub use version::*;
+#[cfg(feature = "xml")]
+pub use xpath::*;
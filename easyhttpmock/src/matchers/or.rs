@@ -5,6 +5,8 @@ use caramelo::{
     Matcher, TypedMatcher,
 };
 
+use crate::matchers::{MatcherNode, Mismatch, PathParams};
+
 /// Creates a matcher that matches values that satisfy any of the given matchers
 ///
 /// # Examples
@@ -16,8 +18,8 @@ use caramelo::{
 /// expect("hello").to_match(or!(contains("ell"), contains("xyz")));
 /// ```
 pub fn or<T: Send + Sync + 'static>(
-    matchers: Vec<Arc<dyn TypedMatcher<T> + Send + Sync + 'static>>,
-) -> Arc<dyn TypedMatcher<T> + Send + Sync + 'static> {
+    matchers: Vec<Arc<dyn MatcherNode<T> + Send + Sync + 'static>>,
+) -> Arc<dyn MatcherNode<T> + Send + Sync + 'static> {
     Arc::new(Or { matchers })
 }
 
@@ -32,7 +34,7 @@ pub fn or<T: Send + Sync + 'static>(
 /// expect("hello").to_match(or!(contains("ell"), contains("xyz")));
 /// ```
 pub struct Or<T: Send + Sync + 'static> {
-    matchers: Vec<Arc<dyn TypedMatcher<T> + Send + Sync + 'static>>,
+    matchers: Vec<Arc<dyn MatcherNode<T> + Send + Sync + 'static>>,
 }
 
 unsafe impl<T: Send + Sync + 'static> Send for Or<T> {}
@@ -40,7 +42,7 @@ unsafe impl<T: Send + Sync + 'static> Sync for Or<T> {}
 
 impl<T: Send + Sync + 'static> Or<T> {
     /// Creates a new Or matcher with the given matchers
-    pub fn new(matchers: Vec<Arc<dyn TypedMatcher<T> + Send + Sync + 'static>>) -> Self {
+    pub fn new(matchers: Vec<Arc<dyn MatcherNode<T> + Send + Sync + 'static>>) -> Self {
         Or { matchers }
     }
 }
@@ -69,3 +71,25 @@ impl<T: Send + Sync + 'static> TypedMatcher<T> for Or<T> {
             .unwrap_or(To)
     }
 }
+
+impl<T: Send + Sync + 'static> MatcherNode<T> for Or<T> {
+    fn explain(&self, value: &T) -> Option<Mismatch> {
+        let mut causes = Vec::with_capacity(self.matchers.len());
+        for matcher in &self.matchers {
+            match matcher.explain(value) {
+                Some(mismatch) => causes.push(mismatch),
+                None => return None,
+            }
+        }
+
+        Some(Mismatch::with_causes(self.description(), "none of the matchers succeeded", causes))
+    }
+
+    fn captures(&self, value: &T) -> PathParams {
+        self.matchers
+            .iter()
+            .find(|m| m.matches(value))
+            .map(|m| m.captures(value))
+            .unwrap_or_default()
+    }
+}
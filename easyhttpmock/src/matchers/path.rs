@@ -2,7 +2,10 @@ use std::sync::Arc;
 
 use caramelo::{MatchType::ToHave, Matcher, TypedMatcher};
 
-use crate::mock::Request;
+use crate::{
+    matchers::{excerpt, MatcherNode, Mismatch, PathParams},
+    mock::Request,
+};
 
 /// Creates a matcher that checks if the request path matches the given regex pattern.
 ///
@@ -25,7 +28,7 @@ use crate::mock::Request;
 ///
 /// let matcher = path(r"^/api/v1/.*$");
 /// ```
-pub fn path(value: &str) -> Arc<dyn TypedMatcher<Request> + Send + Sync + 'static> {
+pub fn path(value: &str) -> Arc<dyn MatcherNode<Request> + Send + Sync + 'static> {
     let regex = regex::Regex::new(value);
     match regex {
         Ok(regex) => Arc::new(Path(regex)),
@@ -75,3 +78,228 @@ impl TypedMatcher<Request> for Path {
         ToHave
     }
 }
+
+impl MatcherNode<Request> for Path {
+    fn explain(&self, value: &Request) -> Option<Mismatch> {
+        if self.matches(value) {
+            None
+        } else {
+            Some(Mismatch::new(
+                self.description(),
+                format!(
+                    "path was {}",
+                    excerpt(
+                        &value
+                            .path()
+                            .to_string()
+                    )
+                ),
+            ))
+        }
+    }
+}
+
+/// Creates a matcher that checks if the request path matches the given route template.
+///
+/// Templates are made of literal segments and placeholders:
+///
+/// * `{name}` - captures a whole segment as the `name` parameter.
+/// * `{name:regex}` - captures the text matching `regex` as the `name` parameter.
+/// * `*` - matches a single segment without capturing it.
+/// * `**` - matches any number of segments, including none.
+///
+/// # Arguments
+///
+/// * `template` - The route template to match against.
+///
+/// # Returns
+///
+/// * `PathTemplate` - A matcher that checks if the request path matches the given template.
+///
+/// # Panics
+///
+/// * `Invalid path template` - If the template is unbalanced or contains an invalid regex.
+///
+/// # Examples
+///
+/// ```rust
+/// use easyhttpmock::matchers::path_template;
+///
+/// let matcher = path_template(r"/users/{id}/orders/{orderId:\d+}");
+/// ```
+pub fn path_template(template: &str) -> Arc<dyn MatcherNode<Request> + Send + Sync + 'static> {
+    match PathTemplate::parse(template) {
+        Some(path_template) => Arc::new(path_template),
+        None => panic!("Invalid path template"),
+    }
+}
+
+#[derive(Clone)]
+/// A matcher that checks if the request path matches a route template, capturing its parameters.
+///
+/// # Arguments
+///
+/// * `template` - The route template to match against.
+///
+/// # Returns
+///
+/// * `PathTemplate` - A matcher that checks if the request path matches the given template.
+///
+/// # Examples
+///
+/// ```rust
+/// use easyhttpmock::matchers::path_template;
+///
+/// let matcher = path_template("/users/{id}/orders");
+/// ```
+pub struct PathTemplate {
+    template: String,
+    regex: regex::Regex,
+}
+
+impl PathTemplate {
+    /// Compiles a route template into a path matcher.
+    ///
+    /// # Arguments
+    ///
+    /// * `template` - The route template to compile.
+    ///
+    /// # Returns
+    ///
+    /// * `Option<PathTemplate>` - The compiled template, or `None` if it is invalid.
+    pub fn parse(template: &str) -> Option<PathTemplate> {
+        let mut pattern = String::from("^");
+        let mut literal = String::new();
+        let mut chars = template
+            .chars()
+            .peekable();
+
+        while let Some(c) = chars.next() {
+            match c {
+                '{' => {
+                    pattern.push_str(&regex::escape(&literal));
+                    literal.clear();
+
+                    let mut placeholder = String::new();
+                    let mut depth = 1;
+                    for c in chars.by_ref() {
+                        match c {
+                            '{' => depth += 1,
+                            '}' => depth -= 1,
+                            _ => {}
+                        }
+                        if depth == 0 {
+                            break;
+                        }
+                        placeholder.push(c);
+                    }
+                    if depth != 0 {
+                        return None;
+                    }
+
+                    let (name, segment) = placeholder
+                        .split_once(':')
+                        .unwrap_or((placeholder.as_str(), "[^/]+"));
+                    if !is_param_name(name) {
+                        return None;
+                    }
+                    pattern.push_str(&format!("(?P<{}>{})", name, segment));
+                }
+                '}' => return None,
+                '*' => {
+                    pattern.push_str(&regex::escape(&literal));
+                    literal.clear();
+
+                    if chars
+                        .next_if_eq(&'*')
+                        .is_some()
+                    {
+                        pattern.push_str(".*");
+                    } else {
+                        pattern.push_str("[^/]+");
+                    }
+                }
+                c => literal.push(c),
+            }
+        }
+
+        pattern.push_str(&regex::escape(&literal));
+        pattern.push('$');
+
+        regex::Regex::new(&pattern)
+            .ok()
+            .map(|regex| PathTemplate { template: template.to_string(), regex })
+    }
+
+    /// Returns the template this matcher was compiled from.
+    ///
+    /// # Returns
+    ///
+    /// * `&str` - The route template.
+    pub fn template(&self) -> &str {
+        &self.template
+    }
+}
+
+fn is_param_name(name: &str) -> bool {
+    let mut chars = name.chars();
+    chars
+        .next()
+        .is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_')
+}
+
+impl Matcher<Request> for PathTemplate {
+    fn matches(&self, value: &Request) -> bool {
+        self.regex
+            .is_match(value.path().path())
+    }
+
+    fn description(&self) -> String {
+        format!("path matching template {}", self.template)
+    }
+}
+
+impl TypedMatcher<Request> for PathTemplate {
+    fn matcher_type(&self) -> caramelo::MatchType {
+        ToHave
+    }
+}
+
+impl MatcherNode<Request> for PathTemplate {
+    fn explain(&self, value: &Request) -> Option<Mismatch> {
+        if self.matches(value) {
+            None
+        } else {
+            Some(Mismatch::new(
+                self.description(),
+                format!("path was {}", excerpt(value.path().path())),
+            ))
+        }
+    }
+
+    fn captures(&self, value: &Request) -> PathParams {
+        let Some(captures) = self
+            .regex
+            .captures(value.path().path())
+        else {
+            return PathParams::new();
+        };
+
+        self.regex
+            .capture_names()
+            .flatten()
+            .filter_map(|name| {
+                captures
+                    .name(name)
+                    .map(|m| {
+                        (
+                            name.to_string(),
+                            m.as_str()
+                                .to_string(),
+                        )
+                    })
+            })
+            .collect()
+    }
+}
@@ -1,6 +1,9 @@
 use caramelo::{MatchType::ToHave, Matcher, TypedMatcher};
 
-use crate::mock::Request;
+use crate::{
+    matchers::{MatcherNode, Mismatch},
+    mock::{QueryParams, Request},
+};
 
 /// Creates a matcher that checks if the request query matches the given regex pattern.
 ///
@@ -73,6 +76,16 @@ impl TypedMatcher<Request> for QueryParam {
     }
 }
 
+impl MatcherNode<Request> for QueryParam {
+    fn explain(&self, value: &Request) -> Option<Mismatch> {
+        if self.matches(value) {
+            None
+        } else {
+            Some(Mismatch::new(self.description(), describe_query(value)))
+        }
+    }
+}
+
 /// Creates a matcher that checks if the request query matches the given regex pattern.
 ///
 /// # Arguments
@@ -146,3 +159,348 @@ impl TypedMatcher<Request> for QueryValue {
         ToHave
     }
 }
+
+impl MatcherNode<Request> for QueryValue {
+    fn explain(&self, value: &Request) -> Option<Mismatch> {
+        if self.matches(value) {
+            None
+        } else {
+            Some(Mismatch::new(self.description(), describe_query(value)))
+        }
+    }
+}
+
+/// Creates a matcher that checks if the request has the given query param with the given value.
+///
+/// Both the key and the value are compared after percent-decoding, repeated keys match if any
+/// of their values is equal to the given one.
+///
+/// # Arguments
+///
+/// * `name` - The query param name to match against.
+/// * `value` - The decoded value to match against.
+///
+/// # Returns
+///
+/// * `QueryParamValue` - A matcher that checks if the request has the given query param value.
+///
+/// # Examples
+///
+/// ```rust
+/// use easyhttpmock::matchers::query_param_value;
+///
+/// let matcher = query_param_value("name", "John Doe");
+/// ```
+pub fn query_param_value(name: &str, value: &str) -> QueryParamValue {
+    QueryParamValue { name: name.to_string(), value: value.to_string() }
+}
+
+#[derive(Clone)]
+/// A matcher that checks if the request has a query param with a given value.
+///
+/// # Arguments
+///
+/// * `name` - The query param name to match against.
+/// * `value` - The decoded value to match against.
+///
+/// # Returns
+///
+/// * `QueryParamValue` - A matcher that checks if the request has the given query param value.
+///
+/// # Examples
+///
+/// ```rust
+/// use easyhttpmock::matchers::query_param_value;
+///
+/// let matcher = query_param_value("tag", "rust");
+/// ```
+pub struct QueryParamValue {
+    name: String,
+    value: String,
+}
+
+impl Matcher<Request> for QueryParamValue {
+    fn matches(&self, value: &Request) -> bool {
+        value
+            .query_params()
+            .as_ref()
+            .is_some_and(|query_params| query_params.contains(&self.name, &self.value))
+    }
+
+    fn description(&self) -> String {
+        format!("query param {} with value {:?}", self.name, self.value)
+    }
+}
+
+impl TypedMatcher<Request> for QueryParamValue {
+    fn matcher_type(&self) -> caramelo::MatchType {
+        ToHave
+    }
+}
+
+impl MatcherNode<Request> for QueryParamValue {
+    fn explain(&self, value: &Request) -> Option<Mismatch> {
+        if self.matches(value) {
+            return None;
+        }
+
+        let values = value
+            .query_params()
+            .as_ref()
+            .map(|query_params| query_params.get_all(&self.name))
+            .unwrap_or_default();
+
+        let actual = if values.is_empty() {
+            format!("missing; {}", describe_query(value))
+        } else {
+            format!("values were {:?}", values)
+        };
+
+        Some(Mismatch::new(self.description(), actual))
+    }
+}
+
+/// Creates a matcher that checks if the request has all the given query params.
+///
+/// Other query params may be present as well, use [`exact_query_params`] to reject them.
+///
+/// # Arguments
+///
+/// * `params` - The decoded key and value pairs that must be present.
+///
+/// # Returns
+///
+/// * `PartialQueryParams` - A matcher that checks if the request has all the given query params.
+///
+/// # Examples
+///
+/// ```rust
+/// use easyhttpmock::matchers::partial_query_params;
+///
+/// let matcher = partial_query_params(&[("page", "2"), ("tag", "a"), ("tag", "b")]);
+/// ```
+pub fn partial_query_params(params: &[(&str, &str)]) -> PartialQueryParams {
+    PartialQueryParams(QueryParams::from(params))
+}
+
+#[derive(Clone)]
+/// A matcher that checks if the request has all the given query params.
+///
+/// # Arguments
+///
+/// * `params` - The decoded key and value pairs that must be present.
+///
+/// # Returns
+///
+/// * `PartialQueryParams` - A matcher that checks if the request has all the given query params.
+///
+/// # Examples
+///
+/// ```rust
+/// use easyhttpmock::matchers::partial_query_params;
+///
+/// let matcher = partial_query_params(&[("page", "2")]);
+/// ```
+pub struct PartialQueryParams(QueryParams);
+
+impl Matcher<Request> for PartialQueryParams {
+    fn matches(&self, value: &Request) -> bool {
+        missing_query_params(&self.0, value).is_empty()
+    }
+
+    fn description(&self) -> String {
+        format!("query params including {}", format_query_params(self.0.iter()))
+    }
+}
+
+impl TypedMatcher<Request> for PartialQueryParams {
+    fn matcher_type(&self) -> caramelo::MatchType {
+        ToHave
+    }
+}
+
+impl MatcherNode<Request> for PartialQueryParams {
+    fn explain(&self, value: &Request) -> Option<Mismatch> {
+        let missing = missing_query_params(&self.0, value);
+        if missing.is_empty() {
+            return None;
+        }
+
+        Some(Mismatch::new(
+            self.description(),
+            format!(
+                "missing {}; {}",
+                format_query_params(missing.into_iter()),
+                describe_query(value)
+            ),
+        ))
+    }
+}
+
+/// Creates a matcher that checks if the request has exactly the given query params.
+///
+/// The order of the params does not matter, but every param of the request must be expected
+/// and every expected param must be present, as many times as it was given.
+///
+/// # Arguments
+///
+/// * `params` - The decoded key and value pairs of the query.
+///
+/// # Returns
+///
+/// * `ExactQueryParams` - A matcher that checks if the request has exactly the given query params.
+///
+/// # Examples
+///
+/// ```rust
+/// use easyhttpmock::matchers::exact_query_params;
+///
+/// let matcher = exact_query_params(&[("page", "2"), ("tag", "a"), ("tag", "b")]);
+/// ```
+pub fn exact_query_params(params: &[(&str, &str)]) -> ExactQueryParams {
+    ExactQueryParams(QueryParams::from(params))
+}
+
+#[derive(Clone)]
+/// A matcher that checks if the request has exactly the given query params.
+///
+/// # Arguments
+///
+/// * `params` - The decoded key and value pairs of the query.
+///
+/// # Returns
+///
+/// * `ExactQueryParams` - A matcher that checks if the request has exactly the given query params.
+///
+/// # Examples
+///
+/// ```rust
+/// use easyhttpmock::matchers::exact_query_params;
+///
+/// let matcher = exact_query_params(&[("page", "2")]);
+/// ```
+pub struct ExactQueryParams(QueryParams);
+
+impl ExactQueryParams {
+    fn extra<'a>(&self, value: &'a Request) -> Vec<(&'a str, &'a str)> {
+        let Some(query_params) = value.query_params() else {
+            return Vec::new();
+        };
+
+        let mut expected = self
+            .0
+            .iter()
+            .collect::<Vec<_>>();
+        query_params
+            .iter()
+            .filter(|pair| {
+                match expected
+                    .iter()
+                    .position(|expected| expected == pair)
+                {
+                    Some(index) => {
+                        expected.swap_remove(index);
+                        false
+                    }
+                    None => true,
+                }
+            })
+            .collect()
+    }
+}
+
+impl Matcher<Request> for ExactQueryParams {
+    fn matches(&self, value: &Request) -> bool {
+        missing_query_params(&self.0, value).is_empty()
+            && self
+                .extra(value)
+                .is_empty()
+    }
+
+    fn description(&self) -> String {
+        format!("query params exactly {}", format_query_params(self.0.iter()))
+    }
+}
+
+impl TypedMatcher<Request> for ExactQueryParams {
+    fn matcher_type(&self) -> caramelo::MatchType {
+        ToHave
+    }
+}
+
+impl MatcherNode<Request> for ExactQueryParams {
+    fn explain(&self, value: &Request) -> Option<Mismatch> {
+        let missing = missing_query_params(&self.0, value);
+        let extra = self.extra(value);
+
+        let mut problems = Vec::new();
+        if !missing.is_empty() {
+            problems.push(format!("missing {}", format_query_params(missing.into_iter())));
+        }
+        if !extra.is_empty() {
+            problems.push(format!("unexpected {}", format_query_params(extra.into_iter())));
+        }
+
+        if problems.is_empty() {
+            None
+        } else {
+            problems.push(describe_query(value));
+            Some(Mismatch::new(self.description(), problems.join("; ")))
+        }
+    }
+}
+
+/// Returns the expected params not found in the request, honouring repeated pairs.
+fn missing_query_params<'a>(
+    expected: &'a QueryParams,
+    request: &Request,
+) -> Vec<(&'a str, &'a str)> {
+    match request.query_params() {
+        Some(query_params) => missing_pairs(expected, query_params),
+        None => missing_pairs(expected, &QueryParams::default()),
+    }
+}
+
+/// Returns the expected pairs not found in the received ones, honouring repeated pairs.
+pub(crate) fn missing_pairs<'a>(
+    expected: &'a QueryParams,
+    received: &QueryParams,
+) -> Vec<(&'a str, &'a str)> {
+    let mut received = received
+        .iter()
+        .collect::<Vec<_>>();
+
+    expected
+        .iter()
+        .filter(|pair| {
+            match received
+                .iter()
+                .position(|received| received == pair)
+            {
+                Some(index) => {
+                    received.swap_remove(index);
+                    false
+                }
+                None => true,
+            }
+        })
+        .collect()
+}
+
+/// Formats decoded key and value pairs for a matcher description.
+pub(crate) fn format_query_params<'a>(params: impl Iterator<Item = (&'a str, &'a str)>) -> String {
+    params
+        .map(|(key, value)| format!("{}={:?}", key, value))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// Describes the decoded query params of a request for a mismatch report.
+fn describe_query(request: &Request) -> String {
+    match request.query_params() {
+        Some(query_params) if !query_params.is_empty() => {
+            format!("query params were {}", format_query_params(query_params.iter()))
+        }
+        _ => "no query string".to_string(),
+    }
+}
@@ -0,0 +1,114 @@
+use std::sync::Arc;
+
+use caramelo::{MatchType::ToHave, Matcher, TypedMatcher};
+
+use crate::{
+    matchers::{MatcherNode, Mismatch},
+    mock::Request,
+};
+
+/// Trait for converting values into http::Version.
+pub trait AsVersion {
+    /// Converts the value into a http::Version.
+    fn into_version(self) -> http::Version;
+}
+
+impl AsVersion for http::Version {
+    fn into_version(self) -> http::Version {
+        self
+    }
+}
+
+impl AsVersion for String {
+    fn into_version(self) -> http::Version {
+        self.as_str()
+            .into_version()
+    }
+}
+
+impl AsVersion for &str {
+    fn into_version(self) -> http::Version {
+        match self {
+            "HTTP/0.9" => http::Version::HTTP_09,
+            "HTTP/1.0" => http::Version::HTTP_10,
+            "HTTP/1.1" | "http/1.1" => http::Version::HTTP_11,
+            "HTTP/2" | "HTTP/2.0" | "h2" | "h2c" => http::Version::HTTP_2,
+            "HTTP/3" | "HTTP/3.0" | "h3" => http::Version::HTTP_3,
+            _ => panic!("Invalid version"),
+        }
+    }
+}
+
+/// Creates a matcher that checks if the request was sent with the given protocol version.
+///
+/// Versions can be given as `http::Version` or as a string, either the version name such as
+/// `HTTP/2` or the ALPN protocol identifier such as `h2`.
+///
+/// # Arguments
+///
+/// * `value` - The protocol version to match against.
+///
+/// # Returns
+///
+/// * `Version` - A matcher that checks if the request version matches the given version.
+///
+/// # Examples
+///
+/// ```rust
+/// use easyhttpmock::matchers::version;
+///
+/// let matcher = version(http::Version::HTTP_2);
+/// let matcher = version("h3");
+/// ```
+pub fn version<V>(value: V) -> Arc<dyn MatcherNode<Request> + Send + Sync + 'static>
+where
+    V: AsVersion,
+{
+    Arc::new(Version(value.into_version()))
+}
+
+#[derive(Clone)]
+/// A matcher that checks if the request was sent with a specific HTTP protocol version.
+///
+/// # Arguments
+///
+/// * `version` - The HTTP protocol version to match against.
+///
+/// # Returns
+///
+/// * `Version` - A matcher that checks if the request version matches the given version.
+///
+/// # Examples
+///
+/// ```rust
+/// use easyhttpmock::matchers::version;
+///
+/// let matcher = version("HTTP/1.1");
+/// ```
+pub struct Version(http::Version);
+
+impl Matcher<Request> for Version {
+    fn matches(&self, value: &Request) -> bool {
+        self.0 == *value.version()
+    }
+
+    fn description(&self) -> String {
+        format!("version matching {:?}", self.0)
+    }
+}
+
+impl TypedMatcher<Request> for Version {
+    fn matcher_type(&self) -> caramelo::MatchType {
+        ToHave
+    }
+}
+
+impl MatcherNode<Request> for Version {
+    fn explain(&self, value: &Request) -> Option<Mismatch> {
+        if self.matches(value) {
+            None
+        } else {
+            Some(Mismatch::new(self.description(), format!("version was {:?}", value.version())))
+        }
+    }
+}
@@ -0,0 +1,225 @@
+use caramelo::{MatchType::ToHave, Matcher, TypedMatcher};
+use simdxml::{parse, xpath::XPathNode, XmlIndex};
+
+use crate::{
+    matchers::{describe_body, MatcherNode, Mismatch},
+    mock::Request,
+};
+
+/// Creates a selector for the string values of the request body at the given XPath.
+///
+/// The selector is turned into a matcher by one of its assertions. Value assertions require at
+/// least one node to be selected and every selected value to satisfy them, values are trimmed
+/// before being checked.
+///
+/// # Arguments
+///
+/// * `expr` - The XPath expression selecting the nodes to check.
+///
+/// # Returns
+///
+/// * `XPathSelector` - A selector to build the matcher from.
+///
+/// # Examples
+///
+/// ```rust
+/// use easyhttpmock::matchers::xpath;
+///
+/// let name = xpath("//user/name").equals("John");
+/// let id = xpath("//user/@id").matches(r"^\d+$");
+/// let items = xpath("//order/item").count(3);
+/// ```
+pub fn xpath(expr: &str) -> XPathSelector {
+    XPathSelector { expr: expr.to_string() }
+}
+
+/// Selector for the string values of the request body at an XPath.
+///
+/// # Examples
+///
+/// ```rust
+/// use easyhttpmock::matchers::{xpath, XPathPredicate};
+///
+/// let matcher = xpath("//user/name").satisfies(XPathPredicate::Equals("John".to_string()));
+/// ```
+pub struct XPathSelector {
+    expr: String,
+}
+
+impl XPathSelector {
+    /// Checks that the selected values satisfy the given predicate.
+    pub fn satisfies(self, predicate: XPathPredicate) -> BodyWithXPath {
+        BodyWithXPath { expr: self.expr, predicate }
+    }
+
+    /// Checks that the selected values are equal to the given string.
+    pub fn equals(self, value: &str) -> BodyWithXPath {
+        self.satisfies(XPathPredicate::Equals(value.to_string()))
+    }
+
+    /// Checks that the selected values match the given regex pattern.
+    ///
+    /// # Panics
+    ///
+    /// * Panics if the regex pattern is invalid.
+    pub fn matches(self, pattern: &str) -> BodyWithXPath {
+        match regex::Regex::new(pattern) {
+            Ok(regex) => self.satisfies(XPathPredicate::Matches(regex)),
+            Err(_) => panic!("Invalid regex pattern"),
+        }
+    }
+
+    /// Checks that the expression selects exactly the given number of nodes.
+    pub fn count(self, count: usize) -> BodyWithXPath {
+        self.satisfies(XPathPredicate::Count(count))
+    }
+}
+
+/// Assertion applied to the values selected by an XPath
+#[derive(Clone, Debug)]
+pub enum XPathPredicate {
+    /// Every selected value is equal to the given string
+    Equals(String),
+    /// Every selected value matches the regex
+    Matches(regex::Regex),
+    /// The expression selects exactly the given number of nodes
+    Count(usize),
+}
+
+impl XPathPredicate {
+    fn describe(&self) -> String {
+        match self {
+            XPathPredicate::Equals(expected) => format!("equal to {:?}", expected),
+            XPathPredicate::Matches(regex) => format!("matching {:?}", regex),
+            XPathPredicate::Count(count) => format!("selecting {} nodes", count),
+        }
+    }
+}
+
+#[derive(Clone)]
+/// A matcher that checks the values of the request body selected by an XPath.
+///
+/// # Arguments
+///
+/// * `expr` - The XPath expression selecting the nodes to check.
+/// * `predicate` - The assertion the selected values must satisfy.
+///
+/// # Returns
+///
+/// * `BodyWithXPath` - A matcher that checks the selected values.
+///
+/// # Examples
+///
+/// ```rust
+/// use easyhttpmock::matchers::xpath;
+///
+/// let matcher = xpath("//user/name").equals("John");
+/// ```
+pub struct BodyWithXPath {
+    expr: String,
+    predicate: XPathPredicate,
+}
+
+impl BodyWithXPath {
+    /// Returns the selected values that fail the predicate.
+    fn failures(&self, value: &Request) -> Result<Vec<String>, String> {
+        let Some(body) = value.body() else {
+            return Err(describe_body(value));
+        };
+
+        let xml = parse(body).map_err(|e| format!("body was not valid XML: {}", e))?;
+        let values = xml
+            .xpath(self.expr.as_str())
+            .map_err(|e| format!("invalid XPath {}: {}", self.expr, e))?
+            .into_iter()
+            .filter_map(|node| string_value(&xml, node))
+            .map(|value| {
+                value
+                    .trim()
+                    .to_string()
+            })
+            .collect::<Vec<_>>();
+
+        match &self.predicate {
+            XPathPredicate::Count(count) if values.len() == *count => Ok(Vec::new()),
+            XPathPredicate::Count(_) => Err(format!("{} nodes were selected", values.len())),
+            _ if values.is_empty() => Err(format!("no node selected by {}", self.expr)),
+            XPathPredicate::Equals(expected) => Ok(values
+                .into_iter()
+                .filter(|value| value != expected)
+                .collect()),
+            XPathPredicate::Matches(regex) => Ok(values
+                .into_iter()
+                .filter(|value| !regex.is_match(value))
+                .collect()),
+        }
+    }
+}
+
+/// Returns the string value of a selected node, `None` for namespace nodes.
+///
+/// `XmlIndex::xpath_string` returns the start tag of the owner element for attribute nodes, so
+/// attributes are resolved to their value here.
+fn string_value(xml: &XmlIndex, node: XPathNode) -> Option<String> {
+    match node {
+        XPathNode::Element(index) => Some(xml.all_text(index)),
+        XPathNode::Text(index) => Some(
+            xml.text_content(&xml.text_ranges[index])
+                .to_string(),
+        ),
+        XPathNode::Attribute(index, name) => xml
+            .attributes(index)
+            .into_iter()
+            .find(|(attribute, _)| attribute_name_hash(attribute) == name)
+            .map(|(_, value)| XmlIndex::decode_entities(value).into_owned()),
+        XPathNode::Namespace(..) => None,
+    }
+}
+
+/// Hashes an attribute name the way simdxml identifies attribute nodes, with FNV-1a.
+fn attribute_name_hash(name: &str) -> u64 {
+    name.bytes()
+        .fold(0xcbf29ce484222325, |hash, byte| (hash ^ u64::from(byte)).wrapping_mul(0x100000001b3))
+}
+
+impl Matcher<Request> for BodyWithXPath {
+    fn matches(&self, value: &Request) -> bool {
+        self.failures(value)
+            .is_ok_and(|failures| failures.is_empty())
+    }
+
+    fn description(&self) -> String {
+        format!(
+            "body values at {} {}",
+            self.expr,
+            self.predicate
+                .describe()
+        )
+    }
+}
+
+impl TypedMatcher<Request> for BodyWithXPath {
+    fn matcher_type(&self) -> caramelo::MatchType {
+        ToHave
+    }
+}
+
+impl MatcherNode<Request> for BodyWithXPath {
+    fn explain(&self, value: &Request) -> Option<Mismatch> {
+        match self.failures(value) {
+            Ok(failures) if failures.is_empty() => None,
+            Ok(failures) => {
+                let causes = failures
+                    .into_iter()
+                    .map(|value| Mismatch::new(&self.expr, format!("value was {:?}", value)))
+                    .collect::<Vec<_>>();
+                Some(Mismatch::with_causes(
+                    self.description(),
+                    format!("{} selected values failed", causes.len()),
+                    causes,
+                ))
+            }
+            Err(actual) => Some(Mismatch::new(self.description(), actual)),
+        }
+    }
+}
@@ -1,12 +1,27 @@
 use crate::{
-    matchers::{and, or},
+    compression::{decode_body, negotiate_encoding, Encoding},
+    errors::RequestError,
+    matchers::{and, basic_credentials, or, MatcherNode, Mismatch, PathParams},
+    namespace::Namespaces,
     server::ServerAdapter,
+    tls::ClientCertificate,
     EasyHttpMock, HttpMockResult,
 };
 use bytes::Bytes;
 use caramelo::{MatchType, Matcher, TypedMatcher};
-use http::{request::Parts, HeaderMap, Method, StatusCode, Uri};
-use std::{collections::HashMap, fmt::Debug, sync::Arc};
+use http::{
+    header::{CONTENT_ENCODING, VARY},
+    request::Parts,
+    HeaderMap, Method, StatusCode, Uri,
+};
+use std::{
+    collections::HashMap,
+    fmt::{self, Debug},
+    sync::Arc,
+};
+
+#[cfg(feature = "oauth")]
+use crate::oauth::OAuthProvider;
 /// State container for mock data
 pub struct MockState {
     inner: Arc<Mock>,
@@ -59,23 +74,51 @@ impl Mock {
 
 #[inline]
 /// Add a matcher to this request
-pub fn given(matcher: impl TypedMatcher<Request> + Send + Sync + 'static) -> RequestMock {
-    RequestMock { matcher: Arc::from(matcher), respond: None }
+///
+/// Matchers implementing only caramelo's `TypedMatcher` are wrapped with
+/// [`node`](crate::matchers::node) first.
+pub fn given(matcher: impl MatcherNode<Request> + Send + Sync + 'static) -> RequestMock {
+    RequestMock {
+        matcher: Arc::from(matcher),
+        respond: None,
+        basic_realm: None,
+        namespaces: None,
+        #[cfg(feature = "oauth")]
+        oauth: None,
+    }
 }
 
 /// Represents a mock request
 pub struct RequestMock {
-    matcher: Arc<dyn TypedMatcher<Request> + Send + Sync + 'static>,
+    matcher: Arc<dyn MatcherNode<Request> + Send + Sync + 'static>,
     respond: Option<Respond>,
+    basic_realm: Option<String>,
+    namespaces: Option<Namespaces>,
+    #[cfg(feature = "oauth")]
+    oauth: Option<OAuthProvider>,
 }
 
 impl RequestMock {
     #[inline]
     /// Get the matcher for this request
-    pub fn matcher(&self) -> &Arc<dyn TypedMatcher<Request> + Send + Sync + 'static> {
+    pub fn matcher(&self) -> &Arc<dyn MatcherNode<Request> + Send + Sync + 'static> {
         &self.matcher
     }
 
+    #[inline]
+    /// Explain why the given request does not match this mock
+    pub fn explain(&self, request: &Request) -> Option<Mismatch> {
+        self.matcher
+            .explain(request)
+    }
+
+    #[inline]
+    /// Get the path parameters captured from the given request
+    pub fn captures(&self, request: &Request) -> PathParams {
+        self.matcher
+            .captures(request)
+    }
+
     #[inline]
     /// Get the respond for this request
     pub fn respond(&self) -> Option<&Respond> {
@@ -89,6 +132,95 @@ impl RequestMock {
         self.respond = Some(respond);
         self
     }
+
+    #[inline]
+    /// Answer requests without Basic credentials with a `401 Unauthorized` challenge for the
+    /// given realm, instead of matching them
+    pub fn challenge_basic(mut self, realm: &str) -> Self {
+        self.basic_realm = Some(realm.to_string());
+        self
+    }
+
+    #[inline]
+    /// Route the requests to the mocks of the given namespaces, by the prefix of their path
+    pub(crate) fn with_namespaces(mut self, namespaces: Namespaces) -> Self {
+        self.namespaces = Some(namespaces);
+        self
+    }
+
+    #[inline]
+    /// Get the namespaces the requests are routed to, if this request is a namespace router
+    pub(crate) fn namespaces(&self) -> Option<&Namespaces> {
+        self.namespaces
+            .as_ref()
+    }
+
+    #[cfg(feature = "oauth")]
+    #[inline]
+    /// Serve the endpoints of the given OAuth2 provider alongside this request
+    pub fn with_oauth(mut self, provider: OAuthProvider) -> Self {
+        self.oauth = Some(provider);
+        self
+    }
+
+    /// Get the response to answer the given request with before matching it, if any
+    ///
+    /// Requests to a namespaced server are answered by the mock of their namespace, requests to
+    /// the endpoints of the OAuth2 provider are answered by the provider and unauthenticated
+    /// requests are answered with the Basic challenge, when set.
+    ///
+    /// # Arguments
+    ///
+    /// * `request` - The received request
+    ///
+    /// # Returns
+    ///
+    /// * `Option<Respond>` - The response to send, `None` when the request must be matched
+    pub fn intercept(&self, request: &Request) -> Option<Respond> {
+        if let Some(namespaces) = &self.namespaces {
+            return Some(namespaces.route(request));
+        }
+
+        #[cfg(feature = "oauth")]
+        if let Some(respond) = self
+            .oauth
+            .as_ref()
+            .and_then(|provider| provider.handle(request))
+        {
+            return Some(respond);
+        }
+
+        self.challenge(request)
+    }
+
+    /// Get the challenge to answer the given request with, if it is unauthenticated
+    ///
+    /// # Arguments
+    ///
+    /// * `request` - The received request
+    ///
+    /// # Returns
+    ///
+    /// * `Option<Respond>` - The `401 Unauthorized` response carrying the `WWW-Authenticate`
+    ///   header, `None` when no challenge is set or the request has Basic credentials
+    pub fn challenge(&self, request: &Request) -> Option<Respond> {
+        let realm = self
+            .basic_realm
+            .as_ref()?;
+        if basic_credentials(request).is_some() {
+            return None;
+        }
+
+        Some(
+            StatusCode::UNAUTHORIZED
+                .respond()
+                .with_header(
+                    http::header::WWW_AUTHENTICATE.as_str(),
+                    &format!("Basic realm=\"{}\"", realm.replace('"', "\\\"")),
+                )
+                .empty(),
+        )
+    }
 }
 
 impl Matcher<Request> for Arc<dyn TypedMatcher<Request> + Send + Sync + 'static> {
@@ -109,34 +241,65 @@ impl TypedMatcher<Request> for Arc<dyn TypedMatcher<Request> + Send + Sync + 'st
             .matcher_type()
     }
 }
+
+impl Matcher<Request> for Arc<dyn MatcherNode<Request> + Send + Sync + 'static> {
+    fn matches(&self, request: &Request) -> bool {
+        self.as_ref()
+            .matches(request)
+    }
+
+    fn description(&self) -> String {
+        self.as_ref()
+            .description()
+    }
+}
+
+impl TypedMatcher<Request> for Arc<dyn MatcherNode<Request> + Send + Sync + 'static> {
+    fn matcher_type(&self) -> MatchType {
+        self.as_ref()
+            .matcher_type()
+    }
+}
+
+impl MatcherNode<Request> for Arc<dyn MatcherNode<Request> + Send + Sync + 'static> {
+    fn explain(&self, request: &Request) -> Option<Mismatch> {
+        self.as_ref()
+            .explain(request)
+    }
+
+    fn captures(&self, request: &Request) -> PathParams {
+        self.as_ref()
+            .captures(request)
+    }
+}
 /// Extension trait for adding AND/OR combinators to matchers
-pub trait AsyncMatcherExt<T>: TypedMatcher<T> + Sized + 'static
+pub trait AsyncMatcherExt<T>: MatcherNode<T> + Sized + 'static
 where
     T: Send + Sync + 'static,
 {
     /// Combines this matcher with another using AND logic
-    fn and<M>(self, matcher: M) -> Arc<dyn TypedMatcher<T> + Send + Sync + 'static>
+    fn and<M>(self, matcher: M) -> Arc<dyn MatcherNode<T> + Send + Sync + 'static>
     where
         Self: Send + Sync,
-        M: TypedMatcher<T> + Send + Sync + 'static,
+        M: MatcherNode<T> + Send + Sync + 'static,
     {
         and(vec![Arc::new(self), Arc::new(matcher)])
     }
 
     /// Combines this matcher with another using OR logic
-    fn or<M>(self, matcher: M) -> Arc<dyn TypedMatcher<T> + Send + Sync + 'static>
+    fn or<M>(self, matcher: M) -> Arc<dyn MatcherNode<T> + Send + Sync + 'static>
     where
         Self: Send + Sync,
-        M: TypedMatcher<T> + Send + Sync + 'static,
+        M: MatcherNode<T> + Send + Sync + 'static,
     {
         or(vec![Arc::new(self), Arc::new(matcher)])
     }
 }
 
-/// Implementation of AsyncMatcherExt for any type that implements TypedMatcher
+/// Implementation of AsyncMatcherExt for any type that implements MatcherNode
 impl<M, T> AsyncMatcherExt<T> for M
 where
-    M: TypedMatcher<T> + Send + Sync + 'static,
+    M: MatcherNode<T> + Send + Sync + 'static,
     T: Send + Sync + 'static,
 {
 }
@@ -150,7 +313,7 @@ pub trait StatusCodeExt {
 impl StatusCodeExt for StatusCode {
     /// Create a response builder with this status code
     fn respond(self) -> RespondBuilder {
-        RespondBuilder { status_code: self, headers: HashMap::new() }
+        RespondBuilder { status_code: self, headers: HashMap::new(), encoding: None }
     }
 }
 
@@ -158,11 +321,10 @@ impl StatusCodeExt for StatusCode {
 #[derive(Debug)]
 pub struct RequestBuilder {
     uri: Uri,
-    query_params: Option<HashMap<String, String>>,
+    query_params: Option<QueryParams>,
     method: http::Method,
     version: http::Version,
     headers: http::HeaderMap,
-    body: Option<Bytes>,
 }
 
 impl RequestBuilder {
@@ -172,8 +334,8 @@ impl RequestBuilder {
     }
 
     /// Sets the request query parameters
-    pub fn query_params(self, query_params: HashMap<String, String>) -> Self {
-        Self { query_params: Some(query_params), ..self }
+    pub fn query_params(self, query_params: impl Into<QueryParams>) -> Self {
+        Self { query_params: Some(query_params.into()), ..self }
     }
 
     /// Sets the request method
@@ -210,31 +372,40 @@ impl RequestBuilder {
             uri: self.uri,
             headers: self.headers,
             body: None,
+            raw_body: None,
+            client_certificate: None,
+            path_params: PathParams::new(),
         })
     }
 
-    /// Builds the request
-    pub fn body(self) -> Result<Request, http::Error> {
+    /// Builds the request with the given body
+    pub fn body(self, body: impl Into<Bytes>) -> Result<Request, http::Error> {
         Ok(Request {
             method: self.method,
             version: self.version,
             query_params: self.query_params,
             uri: self.uri,
             headers: self.headers,
-            body: self.body,
+            body: Some(body.into()),
+            raw_body: None,
+            client_certificate: None,
+            path_params: PathParams::new(),
         })
     }
 }
 
 /// Represents a mock HTTP request
-#[derive(Clone, Debug, PartialEq)]
+#[derive(Clone, PartialEq)]
 pub struct Request {
     method: Method,
     uri: Uri,
     version: http::Version,
     headers: HeaderMap,
-    query_params: Option<HashMap<String, String>>,
+    query_params: Option<QueryParams>,
     body: Option<Bytes>,
+    raw_body: Option<Bytes>,
+    client_certificate: Option<ClientCertificate>,
+    path_params: PathParams,
 }
 
 impl Request {
@@ -244,14 +415,7 @@ impl Request {
         let query_params = parts
             .uri
             .query()
-            .map(|q| {
-                q.split('&')
-                    .filter_map(|pair| {
-                        pair.split_once('=')
-                            .map(|(k, v)| (k.to_string(), v.to_string()))
-                    })
-                    .collect()
-            });
+            .map(QueryParams::parse);
 
         Request {
             method: parts.method,
@@ -260,16 +424,135 @@ impl Request {
             headers: parts.headers,
             query_params,
             body: None,
+            raw_body: None,
+            client_certificate: parts
+                .extensions
+                .get::<ClientCertificate>()
+                .cloned(),
+            path_params: PathParams::new(),
         }
     }
 
+    #[inline]
+    /// Set the certificate the client presented during the TLS handshake
+    pub fn with_client_certificate(mut self, certificate: ClientCertificate) -> Request {
+        self.client_certificate = Some(certificate);
+        self
+    }
+
+    /// Set the body of the request, an empty body is treated as no body
+    ///
+    /// Bodies encoded as listed in the `Content-Encoding` header are decoded when the features
+    /// of their encodings are enabled, so matchers see the decoded body. The body as received is
+    /// kept as the raw body.
+    pub fn with_body(self, body: impl Into<Bytes>) -> Request {
+        let body = body.into();
+        let decoded = decode_body(&self.headers, &body, None).unwrap_or_default();
+        self.set_body(body, decoded)
+    }
+
+    /// Set the body of the request like [`Request::with_body`], limiting the size of the decoded
+    /// body
+    ///
+    /// Adapters set received bodies with this function, so an encoded body within the maximum
+    /// body size cannot expand past it once decoded.
+    ///
+    /// # Arguments
+    ///
+    /// * `body` - The body as received
+    /// * `limit` - The maximum size of the decoded body, `None` for no limit
+    ///
+    /// # Returns
+    ///
+    /// * `Result<Request, RequestError>` - The request, or `RequestError::BodyTooLarge` when the
+    ///   decoded body exceeds the limit
+    pub fn with_limited_body(
+        self,
+        body: impl Into<Bytes>,
+        limit: Option<usize>,
+    ) -> Result<Request, RequestError> {
+        let body = body.into();
+        let decoded = decode_body(&self.headers, &body, limit)?;
+        Ok(self.set_body(body, decoded))
+    }
+
+    /// Set the body as received and its decoded form, if it was encoded
+    fn set_body(mut self, body: Bytes, decoded: Option<Bytes>) -> Request {
+        if body.is_empty() {
+            self.body = None;
+            self.raw_body = None;
+            return self;
+        }
+
+        match decoded {
+            Some(decoded) => {
+                self.body = Some(decoded);
+                self.raw_body = Some(body);
+            }
+            None => {
+                self.body = Some(body);
+                self.raw_body = None;
+            }
+        }
+        self
+    }
+
+    #[inline]
+    /// Set the parameters captured from the path by the path template of the mock
+    pub(crate) fn with_path_params(mut self, path_params: PathParams) -> Request {
+        self.path_params = path_params;
+        self
+    }
+
+    #[inline]
+    /// Replace the URI of the request, keeping its query parameters
+    pub(crate) fn with_uri(mut self, uri: Uri) -> Request {
+        self.uri = uri;
+        self
+    }
+
+    /// Get a request carrying the headers and the contents of a part of a multipart body
+    ///
+    /// Body matchers run against the returned request to match a single part.
+    pub(crate) fn for_part(&self, headers: HeaderMap, contents: Bytes) -> Request {
+        Request {
+            headers,
+            body: Some(contents).filter(|contents| !contents.is_empty()),
+            raw_body: None,
+            ..self.clone()
+        }
+    }
+
+    /// Truncate the body to the given size, followed by a marker telling how many bytes were
+    /// removed
+    ///
+    /// # Arguments
+    ///
+    /// * `limit` - The size the body is truncated to
+    ///
+    /// # Returns
+    ///
+    /// * `usize` - The number of bytes removed from the body
+    pub(crate) fn truncate_body(&mut self, limit: usize) -> usize {
+        if let Some(raw_body) = &self.raw_body {
+            self.raw_body = Some(truncate(raw_body, limit).0);
+        }
+
+        let Some(body) = &self.body else {
+            return 0;
+        };
+        let (body, removed) = truncate(body, limit);
+        self.body = Some(body);
+
+        removed
+    }
+
     fn builder(method: http::Method, uri: Uri) -> RequestBuilder {
         RequestBuilder {
             method,
             version: http::Version::HTTP_11,
             uri,
             headers: http::HeaderMap::new(),
-            body: None,
             query_params: None,
         }
     }
@@ -345,7 +628,7 @@ impl Request {
 
     #[inline]
     /// Get the query params
-    pub fn query_params(&self) -> &Option<HashMap<String, String>> {
+    pub fn query_params(&self) -> &Option<QueryParams> {
         &self.query_params
     }
 
@@ -354,11 +637,213 @@ impl Request {
     pub fn body(&self) -> &Option<Bytes> {
         &self.body
     }
+
+    #[inline]
+    /// Get the body as received, before it was decoded according to its `Content-Encoding`
+    pub fn raw_body(&self) -> Option<&Bytes> {
+        self.raw_body
+            .as_ref()
+            .or(self.body.as_ref())
+    }
+
+    #[inline]
+    /// Get the certificate the client presented during the TLS handshake
+    pub fn client_certificate(&self) -> Option<&ClientCertificate> {
+        self.client_certificate
+            .as_ref()
+    }
+
+    #[inline]
+    /// Get the parameters captured from the path by the path template of the mock
+    ///
+    /// The parameters are set once the server matched the request against its mock, they are
+    /// empty before.
+    pub fn path_params(&self) -> &PathParams {
+        &self.path_params
+    }
+
+    #[inline]
+    /// Get a parameter captured from the path by the path template of the mock
+    pub fn path_param(&self, name: &str) -> Option<&str> {
+        self.path_params
+            .get(name)
+            .map(String::as_str)
+    }
+}
+
+/// Leaves out the raw body, the client certificate and the path parameters when the request has
+/// none, so failure messages stay short
+impl Debug for Request {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut debug = f.debug_struct("Request");
+        debug
+            .field("method", &self.method)
+            .field("uri", &self.uri)
+            .field("version", &self.version)
+            .field("headers", &self.headers)
+            .field("query_params", &self.query_params)
+            .field("body", &self.body);
+
+        if let Some(raw_body) = &self.raw_body {
+            debug.field("raw_body", raw_body);
+        }
+        if let Some(client_certificate) = &self.client_certificate {
+            debug.field("client_certificate", client_certificate);
+        }
+        if !self
+            .path_params
+            .is_empty()
+        {
+            debug.field("path_params", &self.path_params);
+        }
+        debug.finish()
+    }
+}
+
+/// Truncate a body to the given size, followed by a marker telling how many bytes were removed
+///
+/// # Arguments
+///
+/// * `body` - The body to truncate
+/// * `limit` - The size the body is truncated to
+///
+/// # Returns
+///
+/// * `(Bytes, usize)` - The truncated body and the number of bytes removed from it
+fn truncate(body: &Bytes, limit: usize) -> (Bytes, usize) {
+    if body.len() <= limit {
+        return (body.clone(), 0);
+    }
+
+    let removed = body.len() - limit;
+    let mut truncated = body[..limit].to_vec();
+    truncated.extend_from_slice(format!("...[{} bytes truncated]", removed).as_bytes());
+
+    (Bytes::from(truncated), removed)
+}
+/// Decoded query parameters of a request, in the order they were received
+///
+/// Keys and values are percent-decoded and repeated keys keep all of their values.
+///
+/// # Examples
+///
+/// ```rust
+/// use easyhttpmock::mock::QueryParams;
+///
+/// let params = QueryParams::parse("tag=a&tag=b&name=John%20Doe");
+/// assert_eq!(params.get("name"), Some("John Doe"));
+/// assert_eq!(params.get_all("tag"), vec!["a", "b"]);
+/// ```
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct QueryParams(Vec<(String, String)>);
+
+impl QueryParams {
+    /// Parses and decodes a raw query string
+    pub fn parse(query: &str) -> Self {
+        QueryParams(
+            form_urlencoded::parse(query.as_bytes())
+                .into_owned()
+                .collect(),
+        )
+    }
+
+    #[inline]
+    /// Get the first value of the given key
+    pub fn get(&self, key: &str) -> Option<&str> {
+        self.iter()
+            .find(|(k, _)| *k == key)
+            .map(|(_, v)| v)
+    }
+
+    #[inline]
+    /// Get all values of the given key
+    pub fn get_all(&self, key: &str) -> Vec<&str> {
+        self.iter()
+            .filter(|(k, _)| *k == key)
+            .map(|(_, v)| v)
+            .collect()
+    }
+
+    #[inline]
+    /// Check whether the given key is present
+    pub fn contains_key(&self, key: &str) -> bool {
+        self.iter()
+            .any(|(k, _)| k == key)
+    }
+
+    #[inline]
+    /// Check whether the given key is present with the given value
+    pub fn contains(&self, key: &str, value: &str) -> bool {
+        self.iter()
+            .any(|(k, v)| k == key && v == value)
+    }
+
+    #[inline]
+    /// Iterate over the key and value pairs
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &str)> {
+        self.0
+            .iter()
+            .map(|(k, v)| (k.as_str(), v.as_str()))
+    }
+
+    #[inline]
+    /// Get the number of key and value pairs
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    #[inline]
+    /// Check whether there are no key and value pairs
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+}
+
+impl FromIterator<(String, String)> for QueryParams {
+    fn from_iter<I: IntoIterator<Item = (String, String)>>(iter: I) -> Self {
+        QueryParams(
+            iter.into_iter()
+                .collect(),
+        )
+    }
+}
+
+impl From<Vec<(String, String)>> for QueryParams {
+    fn from(pairs: Vec<(String, String)>) -> Self {
+        QueryParams(pairs)
+    }
+}
+
+impl From<HashMap<String, String>> for QueryParams {
+    fn from(map: HashMap<String, String>) -> Self {
+        map.into_iter()
+            .collect()
+    }
+}
+
+impl From<&[(&str, &str)]> for QueryParams {
+    fn from(pairs: &[(&str, &str)]) -> Self {
+        pairs
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect()
+    }
+}
+
+/// How the body of a response is compressed
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum RespondEncoding {
+    /// Always compressed with the encoding
+    Fixed(Encoding),
+    /// Compressed with the encoding negotiated with the `Accept-Encoding` header of the request
+    Negotiated,
 }
+
 /// Builder for what represents a response for a request
 pub struct RespondBuilder {
     status_code: StatusCode,
     headers: HashMap<String, String>,
+    encoding: Option<RespondEncoding>,
 }
 
 impl RespondBuilder {
@@ -387,6 +872,38 @@ impl RespondBuilder {
         self
     }
 
+    /// Compress the body of this response with the given encoding and set `Content-Encoding`
+    ///
+    /// The body is compressed whatever the `Accept-Encoding` header of the request, to test how
+    /// clients handle an encoding they did not ask for.
+    ///
+    /// # Arguments
+    ///
+    /// * `encoding` - The encoding to compress the body with
+    ///
+    /// # Panics
+    ///
+    /// Panics if the cargo feature of the encoding is not enabled
+    pub fn compressed(mut self, encoding: Encoding) -> Self {
+        assert!(
+            encoding.is_enabled(),
+            "{} encoding is not enabled, enable the feature of the same name",
+            encoding
+        );
+        self.encoding = Some(RespondEncoding::Fixed(encoding));
+        self
+    }
+
+    #[inline]
+    /// Compress the body of this response with the encoding preferred by the `Accept-Encoding`
+    /// header of each request, among the enabled encodings
+    ///
+    /// The body is sent as is when the request accepts none of them.
+    pub fn negotiate_encoding(mut self) -> Self {
+        self.encoding = Some(RespondEncoding::Negotiated);
+        self
+    }
+
     #[inline]
     /// Create an empty response
     pub fn empty(self) -> Respond {
@@ -396,16 +913,58 @@ impl RespondBuilder {
     #[inline]
     /// Create a response with no body
     pub fn no_body(self) -> Respond {
-        Respond { status_code: self.status_code, headers: self.headers, body: Bytes::new() }
+        Respond {
+            status_code: self.status_code,
+            headers: self.headers,
+            body: Bytes::new(),
+            encoding: None,
+            templated: false,
+        }
     }
 
-    #[inline]
     /// Create a response with a body
     pub fn with_body(self, body: &[u8]) -> Respond {
-        Respond {
+        let respond = Respond {
             status_code: self.status_code,
             headers: self.headers,
             body: Bytes::from(body.to_vec()),
+            encoding: None,
+            templated: false,
+        };
+
+        match self.encoding {
+            Some(RespondEncoding::Fixed(encoding)) => respond.compress(encoding),
+            encoding => Respond { encoding, ..respond },
+        }
+    }
+
+    /// Create a response whose body and header values are templates, rendered for each request
+    ///
+    /// Placeholders such as `{id}` are replaced with the parameter of the same name captured by
+    /// the path template of the mock. Placeholders naming no captured parameter are kept as is.
+    ///
+    /// # Arguments
+    ///
+    /// * `template` - The template of the body
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use easyhttpmock::mock::StatusCodeExt;
+    /// use http::StatusCode;
+    ///
+    /// let respond = StatusCode::OK
+    ///     .respond()
+    ///     .with_header("Location", "/users/{id}")
+    ///     .with_template(r#"{"id": "{id}"}"#);
+    /// ```
+    pub fn with_template(self, template: &str) -> Respond {
+        Respond {
+            status_code: self.status_code,
+            headers: self.headers,
+            body: Bytes::from(template.to_string()),
+            encoding: self.encoding,
+            templated: true,
         }
     }
 }
@@ -416,6 +975,10 @@ pub struct Respond {
     status_code: StatusCode,
     headers: HashMap<String, String>,
     body: Bytes,
+    /// Encoding applied for each request, fixed encodings are applied when building unless the
+    /// body is a template
+    encoding: Option<RespondEncoding>,
+    templated: bool,
 }
 
 impl Respond {
@@ -423,7 +986,7 @@ impl Respond {
     #[inline]
     /// Initialize respond builder
     pub fn builder() -> RespondBuilder {
-        RespondBuilder { status_code: StatusCode::OK, headers: HashMap::new() }
+        RespondBuilder { status_code: StatusCode::OK, headers: HashMap::new(), encoding: None }
     }
 
     #[inline]
@@ -443,4 +1006,166 @@ impl Respond {
     pub fn body(&self) -> Bytes {
         self.body.clone()
     }
+
+    /// Get the response to send for the given request
+    ///
+    /// Templates are rendered with the path parameters of the request, then responses negotiating
+    /// their encoding are compressed with the encoding preferred by the request.
+    ///
+    /// # Arguments
+    ///
+    /// * `request` - The received request
+    ///
+    /// # Returns
+    ///
+    /// * `Respond` - The response to send
+    pub(crate) fn for_request(&self, request: &Request) -> Respond {
+        if self
+            .encoding
+            .is_none()
+            && !self.templated
+        {
+            return self.clone();
+        }
+
+        let mut respond = self.clone();
+        if respond.templated {
+            respond = respond.render(request.path_params());
+        }
+
+        match respond
+            .encoding
+            .take()
+        {
+            Some(RespondEncoding::Fixed(encoding)) => respond.compress(encoding),
+            Some(RespondEncoding::Negotiated) => {
+                let mut respond = match negotiate_encoding(request.headers()) {
+                    Some(encoding) => respond.compress(encoding),
+                    None => respond,
+                };
+                respond.vary(http::header::ACCEPT_ENCODING.as_str());
+                respond
+            }
+            None => respond,
+        }
+    }
+
+    /// Replace the placeholders of the body and the header values with the given parameters
+    ///
+    /// # Arguments
+    ///
+    /// * `params` - The parameters captured from the path of the request
+    ///
+    /// # Returns
+    ///
+    /// * `Respond` - The rendered response
+    fn render(mut self, params: &PathParams) -> Respond {
+        self.templated = false;
+        self.body = Bytes::from(render_template(&String::from_utf8_lossy(&self.body), params));
+        for value in self
+            .headers
+            .values_mut()
+        {
+            *value = render_template(value, params);
+        }
+        self
+    }
+
+    /// Compress the body with the given encoding and set `Content-Encoding`
+    ///
+    /// # Arguments
+    ///
+    /// * `encoding` - The encoding to compress the body with
+    ///
+    /// # Panics
+    ///
+    /// Panics if the cargo feature of the encoding is not enabled
+    fn compress(mut self, encoding: Encoding) -> Respond {
+        self.body = match encoding.encode(&self.body) {
+            Ok(body) => Bytes::from(body),
+            Err(err) => panic!("Failed to compress respond body: {}", err),
+        };
+        self.set_header(CONTENT_ENCODING.as_str(), encoding.to_string());
+        self
+    }
+
+    /// Set a header, replacing the values set under the same name in any case
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - The name of the header
+    /// * `value` - The value of the header
+    fn set_header(&mut self, name: &str, value: String) {
+        self.headers
+            .retain(|key, _| !key.eq_ignore_ascii_case(name));
+        self.headers
+            .insert(name.to_string(), value);
+    }
+
+    /// Add a request header the response varies on to the `Vary` header, keeping the ones set
+    /// under any case
+    ///
+    /// # Arguments
+    ///
+    /// * `header` - The name of the request header
+    fn vary(&mut self, header: &str) {
+        let mut values = self
+            .headers
+            .iter()
+            .filter(|(key, _)| key.eq_ignore_ascii_case(VARY.as_str()))
+            .flat_map(|(_, value)| value.split(','))
+            .map(str::trim)
+            .filter(|value| !value.is_empty())
+            .map(str::to_string)
+            .collect::<Vec<_>>();
+        if !values
+            .iter()
+            .any(|value| value == "*" || value.eq_ignore_ascii_case(header))
+        {
+            values.push(header.to_string());
+        }
+
+        self.set_header(VARY.as_str(), values.join(", "));
+    }
+}
+
+/// Replace the `{name}` placeholders of a template with the parameters of the same name
+///
+/// # Arguments
+///
+/// * `template` - The template to render
+/// * `params` - The parameters to replace the placeholders with
+///
+/// # Returns
+///
+/// * `String` - The rendered template, placeholders naming no parameter are kept as is
+fn render_template(template: &str, params: &PathParams) -> String {
+    let mut rendered = String::with_capacity(template.len());
+    let mut rest = template;
+
+    while let Some(start) = rest.find('{') {
+        rendered.push_str(&rest[..start]);
+        rest = &rest[start..];
+
+        let param = rest
+            .find('}')
+            .and_then(|end| {
+                params
+                    .get(&rest[1..end])
+                    .map(|value| (value, end))
+            });
+        match param {
+            Some((value, end)) => {
+                rendered.push_str(value);
+                rest = &rest[end + 1..];
+            }
+            None => {
+                rendered.push('{');
+                rest = &rest[1..];
+            }
+        }
+    }
+    rendered.push_str(rest);
+
+    rendered
 }
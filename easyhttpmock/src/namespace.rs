@@ -0,0 +1,303 @@
+use crate::{
+    errors::MockError,
+    journal::Journal,
+    matchers::path,
+    mock::{given, Mock, MockState, Request, Respond, StatusCodeExt},
+    server::handle_request,
+};
+use http::{uri::PathAndQuery, StatusCode, Uri};
+use std::{
+    collections::HashMap,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc, RwLock,
+    },
+};
+
+/// Counter naming the namespaces, unique across the process
+static NEXT_NAMESPACE: AtomicU64 = AtomicU64::new(1);
+
+/// Mock and journal of a single namespace
+#[derive(Default)]
+struct Namespace {
+    mock: Option<Arc<Mock>>,
+    journal: Journal,
+}
+
+/// Namespaces sharing a single mock server
+///
+/// Each namespace owns the requests whose path starts with its prefix. The prefix is stripped
+/// before the request is matched against the mock of the namespace and recorded in its journal,
+/// so tests sharing a server never see each other's mocks or requests. Cloning is cheap, every
+/// clone shares the same namespaces.
+///
+/// # Examples
+///
+/// ```rust
+/// use easyhttpmock::{
+///     matchers::path,
+///     mock::{given, Mock, StatusCodeExt},
+///     namespace::Namespaces,
+/// };
+/// use http::StatusCode;
+///
+/// let namespaces = Namespaces::new();
+/// // Register `namespaces.mock()` once on the shared server
+/// let _root = namespaces.mock();
+///
+/// let namespace = namespaces.create("http://localhost:8080");
+/// namespace.register_mock(Mock::of(
+///     given(path("/test")).will_return(StatusCode::OK.respond().empty()),
+/// ));
+/// assert!(namespace.url("/test").ends_with("/test"));
+/// ```
+#[derive(Clone, Default)]
+pub struct Namespaces {
+    entries: Arc<RwLock<HashMap<String, Namespace>>>,
+}
+
+impl Namespaces {
+    /// Creates a set of namespaces without any namespace
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the mock routing the requests to the namespaces, to register on the shared server
+    ///
+    /// # Returns
+    ///
+    /// * `MockState` - The mock answering every request with the mock of its namespace
+    pub fn mock(&self) -> MockState {
+        Mock::of(given(path("^/")).with_namespaces(self.clone()))
+    }
+
+    /// Creates a new namespace, removed when the returned handle is dropped
+    ///
+    /// # Arguments
+    ///
+    /// * `base_url` - The base URL of the shared server
+    ///
+    /// # Returns
+    ///
+    /// * `NamespaceHandle` - The handle registering the mock of the namespace
+    pub fn create(&self, base_url: &str) -> NamespaceHandle {
+        let name = format!("ns-{}", NEXT_NAMESPACE.fetch_add(1, Ordering::Relaxed));
+        let journal = Journal::new();
+
+        if let Ok(mut entries) = self.entries.write() {
+            entries.insert(name.clone(), Namespace { mock: None, journal: journal.clone() });
+        }
+
+        NamespaceHandle {
+            base_url: format!("{}/{}", base_url.trim_end_matches('/'), name),
+            name,
+            journal,
+            namespaces: self.clone(),
+        }
+    }
+
+    /// Returns the number of namespaces alive
+    pub fn len(&self) -> usize {
+        self.entries
+            .read()
+            .map(|entries| entries.len())
+            .unwrap_or_default()
+    }
+
+    /// Returns whether no namespace is alive
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Answers a request with the mock of the namespace its path starts with
+    ///
+    /// # Arguments
+    ///
+    /// * `request` - The request received by the shared server
+    ///
+    /// # Returns
+    ///
+    /// * `Respond` - The respond of the namespace, or `404 Not Found` when the namespace does not
+    ///   exist or has no mock
+    ///
+    /// # Panics
+    ///
+    /// Panics if the request does not match the mock of its namespace
+    pub(crate) fn route(&self, request: &Request) -> Respond {
+        let uri = request.path();
+        let (name, path) = split_namespace(uri.path());
+
+        let namespace = self
+            .entries
+            .read()
+            .ok()
+            .and_then(|entries| {
+                entries
+                    .get(name)
+                    .map(|namespace| {
+                        (
+                            namespace
+                                .mock
+                                .clone(),
+                            namespace
+                                .journal
+                                .clone(),
+                        )
+                    })
+            });
+
+        let Some((mock, journal)) = namespace else {
+            return not_found("Namespace not found");
+        };
+        let Some(mock) = mock else {
+            return not_found(&MockError::Notfound.to_string());
+        };
+        let Some(uri) = strip_namespace(uri, path) else {
+            return not_found("Namespace not found");
+        };
+
+        match handle_request(
+            &mock,
+            &journal,
+            request
+                .clone()
+                .with_uri(uri),
+        ) {
+            Some(respond) => respond,
+            None => StatusCode::INTERNAL_SERVER_ERROR
+                .respond()
+                .with_body(b"Missing respond mock"),
+        }
+    }
+}
+
+/// Handle of a namespace on a shared mock server
+///
+/// The namespace, its mock and its journal are removed from the shared server when the handle is
+/// dropped.
+pub struct NamespaceHandle {
+    name: String,
+    base_url: String,
+    journal: Journal,
+    namespaces: Namespaces,
+}
+
+impl NamespaceHandle {
+    #[inline]
+    /// Get the name of the namespace
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Get the path prefix of the requests to the namespace
+    pub fn prefix(&self) -> String {
+        format!("/{}", self.name)
+    }
+
+    #[inline]
+    /// Get the base URL of the namespace, including its prefix
+    pub fn base_url(&self) -> &str {
+        &self.base_url
+    }
+
+    /// Returns the full URL for a given path in the namespace
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - The path to append to the base URL of the namespace
+    ///
+    /// # Returns
+    ///
+    /// * `String` - The full URL for the given path
+    pub fn url(&self, path: &str) -> String {
+        format!("{}{}", self.base_url, path)
+    }
+
+    #[inline]
+    /// Get the journal of the requests received by the namespace, without its prefix
+    pub fn journal(&self) -> &Journal {
+        &self.journal
+    }
+
+    /// Sets the mock answering the requests to the namespace
+    ///
+    /// # Arguments
+    ///
+    /// * `mock` - The mock answering the requests to the namespace
+    pub fn register_mock(&self, mock: MockState) {
+        if let Ok(mut entries) = self
+            .namespaces
+            .entries
+            .write()
+        {
+            if let Some(namespace) = entries.get_mut(&self.name) {
+                namespace.mock = Some(mock.inner());
+            }
+        }
+    }
+}
+
+impl Drop for NamespaceHandle {
+    fn drop(&mut self) {
+        if let Ok(mut entries) = self
+            .namespaces
+            .entries
+            .write()
+        {
+            entries.remove(&self.name);
+        }
+    }
+}
+
+/// Splits a path into the name of its namespace and the rest of the path
+///
+/// # Arguments
+///
+/// * `path` - The path of the request received by the shared server
+///
+/// # Returns
+///
+/// * `(&str, &str)` - The name of the namespace and the path within the namespace, which is empty
+///   when the path is the namespace itself
+fn split_namespace(path: &str) -> (&str, &str) {
+    let path = path.trim_start_matches('/');
+    match path.find('/') {
+        Some(index) => (&path[..index], &path[index..]),
+        None => (path, ""),
+    }
+}
+
+/// Builds the URI of a request within its namespace
+///
+/// # Arguments
+///
+/// * `uri` - The URI received by the shared server
+/// * `path` - The path within the namespace
+///
+/// # Returns
+///
+/// * `Option<Uri>` - The URI without the prefix of the namespace, keeping its query
+fn strip_namespace(uri: &Uri, path: &str) -> Option<Uri> {
+    let path = if path.is_empty() { "/" } else { path };
+    let path_and_query = match uri.query() {
+        Some(query) => format!("{}?{}", path, query),
+        None => path.to_string(),
+    };
+
+    let mut parts = uri
+        .clone()
+        .into_parts();
+    parts.path_and_query = Some(PathAndQuery::try_from(path_and_query).ok()?);
+    Uri::from_parts(parts).ok()
+}
+
+/// Builds the respond sent when a request does not belong to a namespace with a mock
+///
+/// # Arguments
+///
+/// * `message` - The body of the respond
+fn not_found(message: &str) -> Respond {
+    StatusCode::NOT_FOUND
+        .respond()
+        .with_body(message.as_bytes())
+}
@@ -0,0 +1,707 @@
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex},
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine};
+use http::{Method, StatusCode};
+use jsonwebtoken::{encode, Algorithm, EncodingKey, Header};
+use rcgen::KeyPair;
+use serde_json::{json, Value};
+use sha2::{Digest, Sha256};
+
+use crate::{
+    matchers::basic_credentials,
+    mock::{QueryParams, Request, Respond, StatusCodeExt},
+};
+
+/// Path of the OpenID Connect discovery document
+pub const DISCOVERY_PATH: &str = "/.well-known/openid-configuration";
+/// Path of the authorization endpoint
+pub const AUTHORIZE_PATH: &str = "/oauth/authorize";
+/// Path of the token endpoint
+pub const TOKEN_PATH: &str = "/oauth/token";
+/// Path of the JSON Web Key Set endpoint
+pub const JWKS_PATH: &str = "/oauth/jwks";
+
+/// Builder for OAuthProvider
+pub struct OAuthProviderBuilder {
+    issuer: String,
+    audience: Option<String>,
+    subject: String,
+    token_ttl: u64,
+    clients: HashMap<String, String>,
+    users: HashMap<String, String>,
+}
+
+impl OAuthProviderBuilder {
+    /// Sets the issuer of the tokens, usually the base URL of the mock server.
+    ///
+    /// # Arguments
+    /// * `issuer` - The issuer to set.
+    ///
+    /// # Returns
+    /// A new `OAuthProviderBuilder` instance with the issuer set.
+    pub fn issuer(mut self, issuer: &str) -> Self {
+        self.issuer = issuer
+            .trim_end_matches('/')
+            .to_string();
+        self
+    }
+
+    /// Sets the audience of the tokens.
+    ///
+    /// # Arguments
+    /// * `audience` - The audience to set.
+    ///
+    /// # Returns
+    /// A new `OAuthProviderBuilder` instance with the audience set.
+    pub fn audience(mut self, audience: &str) -> Self {
+        self.audience = Some(audience.to_string());
+        self
+    }
+
+    /// Sets the subject of the tokens issued by the authorization code flow.
+    ///
+    /// # Arguments
+    /// * `subject` - The subject to set.
+    ///
+    /// # Returns
+    /// A new `OAuthProviderBuilder` instance with the subject set.
+    pub fn subject(mut self, subject: &str) -> Self {
+        self.subject = subject.to_string();
+        self
+    }
+
+    /// Sets how long the issued tokens are valid, in seconds.
+    ///
+    /// # Arguments
+    /// * `token_ttl` - The lifetime of the tokens.
+    ///
+    /// # Returns
+    /// A new `OAuthProviderBuilder` instance with the lifetime set.
+    pub fn token_ttl(mut self, token_ttl: u64) -> Self {
+        self.token_ttl = token_ttl;
+        self
+    }
+
+    /// Registers a client allowed to request tokens.
+    ///
+    /// # Arguments
+    /// * `client_id` - The client identifier.
+    /// * `client_secret` - The client secret.
+    ///
+    /// # Returns
+    /// A new `OAuthProviderBuilder` instance with the client registered.
+    pub fn client(mut self, client_id: &str, client_secret: &str) -> Self {
+        self.clients
+            .insert(client_id.to_string(), client_secret.to_string());
+        self
+    }
+
+    /// Registers a user for the password grant.
+    ///
+    /// # Arguments
+    /// * `username` - The username, also used as the subject of the tokens.
+    /// * `password` - The password.
+    ///
+    /// # Returns
+    /// A new `OAuthProviderBuilder` instance with the user registered.
+    pub fn user(mut self, username: &str, password: &str) -> Self {
+        self.users
+            .insert(username.to_string(), password.to_string());
+        self
+    }
+
+    /// Builds the provider, generating a new ES256 signing key.
+    ///
+    /// # Returns
+    /// A new `OAuthProvider` instance.
+    ///
+    /// # Panics
+    /// Panics if the signing key cannot be generated.
+    pub fn build(self) -> OAuthProvider {
+        let key_pair = match KeyPair::generate() {
+            Ok(key_pair) => key_pair,
+            Err(e) => panic!("Failed to generate signing key: {}", e),
+        };
+        let encoding_key = match EncodingKey::from_ec_pem(
+            key_pair
+                .serialize_pem()
+                .as_bytes(),
+        ) {
+            Ok(encoding_key) => encoding_key,
+            Err(e) => panic!("Failed to load signing key: {}", e),
+        };
+
+        // The raw public key is an uncompressed P-256 point: 0x04 || x || y
+        let point = key_pair.public_key_raw();
+        let jwk = json!({
+            "kty": "EC",
+            "crv": "P-256",
+            "use": "sig",
+            "alg": "ES256",
+            "kid": KEY_ID,
+            "x": URL_SAFE_NO_PAD.encode(&point[1..33]),
+            "y": URL_SAFE_NO_PAD.encode(&point[33..65]),
+        });
+
+        OAuthProvider {
+            inner: Arc::new(OAuthState {
+                issuer: self.issuer,
+                audience: self.audience,
+                subject: self.subject,
+                token_ttl: self.token_ttl,
+                clients: self.clients,
+                users: self.users,
+                encoding_key,
+                public_key_pem: key_pair.public_key_pem(),
+                jwk,
+                codes: Mutex::new(HashMap::new()),
+                refresh_tokens: Mutex::new(HashMap::new()),
+            }),
+        }
+    }
+}
+
+const KEY_ID: &str = "easyhttpmock";
+
+/// Mock OAuth2 and OpenID Connect provider
+///
+/// The provider answers the discovery, authorization, token and JWKS endpoints of the mock
+/// server and issues ES256 signed JWTs, so clients fetching tokens can be tested end-to-end.
+/// It supports the `client_credentials`, `password`, `refresh_token` and `authorization_code`
+/// grants, the latter with PKCE. The authorization endpoint approves every request and redirects
+/// back immediately.
+///
+/// Cloning a provider is cheap, every clone shares the same key and issued grants.
+///
+/// # Examples
+///
+/// ```rust,ignore
+/// let provider = OAuthProvider::builder()
+///     .issuer(&server.base_url())
+///     .client("my-client", "my-secret")
+///     .build();
+///
+/// let mock = Mock::of(
+///     given(method(Method::GET).and(jwt_claims().verify_es256_pem(provider.public_key_pem().as_bytes())))
+///         .with_oauth(provider.clone())
+///         .will_return(StatusCode::OK.respond().empty()),
+/// );
+/// ```
+#[derive(Clone)]
+pub struct OAuthProvider {
+    inner: Arc<OAuthState>,
+}
+
+struct OAuthState {
+    issuer: String,
+    audience: Option<String>,
+    subject: String,
+    token_ttl: u64,
+    clients: HashMap<String, String>,
+    users: HashMap<String, String>,
+    encoding_key: EncodingKey,
+    public_key_pem: String,
+    jwk: Value,
+    codes: Mutex<HashMap<String, AuthorizationCode>>,
+    refresh_tokens: Mutex<HashMap<String, Grant>>,
+}
+
+/// Subject and scope a token was granted for
+#[derive(Clone)]
+struct Grant {
+    client_id: String,
+    subject: String,
+    scope: Option<String>,
+}
+
+struct AuthorizationCode {
+    grant: Grant,
+    redirect_uri: String,
+    code_challenge: Option<(String, String)>,
+}
+
+/// OAuth2 error response, as defined by RFC 6749 section 5.2
+struct OAuthError {
+    status: StatusCode,
+    error: &'static str,
+    description: String,
+}
+
+impl OAuthError {
+    fn new(status: StatusCode, error: &'static str, description: impl Into<String>) -> Self {
+        OAuthError { status, error, description: description.into() }
+    }
+
+    fn into_respond(self) -> Respond {
+        json_respond(
+            self.status,
+            &json!({"error": self.error, "error_description": self.description}),
+        )
+    }
+}
+
+impl OAuthProvider {
+    /// Creates a new builder for the provider.
+    ///
+    /// The builder defaults to the `http://localhost` issuer, the `user` subject and tokens valid
+    /// for one hour.
+    ///
+    /// # Returns
+    /// A new `OAuthProviderBuilder` instance.
+    pub fn builder() -> OAuthProviderBuilder {
+        OAuthProviderBuilder {
+            issuer: "http://localhost".to_string(),
+            audience: None,
+            subject: "user".to_string(),
+            token_ttl: 3600,
+            clients: HashMap::new(),
+            users: HashMap::new(),
+        }
+    }
+
+    /// Returns the issuer of the tokens.
+    pub fn issuer(&self) -> &str {
+        &self.inner.issuer
+    }
+
+    /// Returns the PEM encoded public key the tokens are signed with.
+    pub fn public_key_pem(&self) -> &str {
+        &self
+            .inner
+            .public_key_pem
+    }
+
+    /// Issues a signed access token for the given subject, without going through a grant.
+    ///
+    /// # Arguments
+    /// * `subject` - The subject of the token.
+    /// * `scope` - The space separated scopes of the token, if any.
+    ///
+    /// # Returns
+    /// The signed JWT.
+    pub fn issue_token(&self, subject: &str, scope: Option<&str>) -> String {
+        self.sign(&Grant {
+            client_id: subject.to_string(),
+            subject: subject.to_string(),
+            scope: scope.map(str::to_string),
+        })
+    }
+
+    /// Handles the given request if it targets one of the provider endpoints.
+    ///
+    /// # Arguments
+    /// * `request` - The received request.
+    ///
+    /// # Returns
+    /// The response of the endpoint, `None` when the request does not target the provider.
+    pub fn handle(&self, request: &Request) -> Option<Respond> {
+        let path = request
+            .path()
+            .path();
+        let respond = match (request.method(), path) {
+            (&Method::GET, DISCOVERY_PATH) => self.discovery(),
+            (&Method::GET, JWKS_PATH) => {
+                json_respond(StatusCode::OK, &json!({"keys": [self.inner.jwk.clone()]}))
+            }
+            (&Method::GET, AUTHORIZE_PATH) => self
+                .authorize(request)
+                .unwrap_or_else(OAuthError::into_respond),
+            (&Method::POST, TOKEN_PATH) => self
+                .token(request)
+                .unwrap_or_else(OAuthError::into_respond),
+            _ => return None,
+        };
+
+        Some(respond)
+    }
+
+    fn discovery(&self) -> Respond {
+        let issuer = &self.inner.issuer;
+        json_respond(
+            StatusCode::OK,
+            &json!({
+                "issuer": issuer,
+                "authorization_endpoint": format!("{}{}", issuer, AUTHORIZE_PATH),
+                "token_endpoint": format!("{}{}", issuer, TOKEN_PATH),
+                "jwks_uri": format!("{}{}", issuer, JWKS_PATH),
+                "response_types_supported": ["code"],
+                "subject_types_supported": ["public"],
+                "id_token_signing_alg_values_supported": ["ES256"],
+                "grant_types_supported": [
+                    "authorization_code",
+                    "client_credentials",
+                    "password",
+                    "refresh_token",
+                ],
+                "token_endpoint_auth_methods_supported": [
+                    "client_secret_basic",
+                    "client_secret_post",
+                ],
+                "code_challenge_methods_supported": ["S256", "plain"],
+            }),
+        )
+    }
+
+    fn authorize(&self, request: &Request) -> Result<Respond, OAuthError> {
+        // Requests built by hand carry their query in the URI only
+        let params = request
+            .path()
+            .query()
+            .map(QueryParams::parse)
+            .unwrap_or_default();
+
+        let client_id = params
+            .get("client_id")
+            .filter(|client_id| {
+                self.inner
+                    .clients
+                    .contains_key(*client_id)
+            })
+            .ok_or_else(|| {
+                OAuthError::new(StatusCode::BAD_REQUEST, "unauthorized_client", "unknown client")
+            })?;
+        if params.get("response_type") != Some("code") {
+            return Err(OAuthError::new(
+                StatusCode::BAD_REQUEST,
+                "unsupported_response_type",
+                "only the code response type is supported",
+            ));
+        }
+        let redirect_uri = params
+            .get("redirect_uri")
+            .ok_or_else(|| {
+                OAuthError::new(StatusCode::BAD_REQUEST, "invalid_request", "missing redirect_uri")
+            })?;
+
+        let code_challenge = params
+            .get("code_challenge")
+            .map(|challenge| {
+                let method = params
+                    .get("code_challenge_method")
+                    .unwrap_or("plain");
+                (challenge.to_string(), method.to_string())
+            });
+
+        let code = random_token();
+        if let Ok(mut codes) = self
+            .inner
+            .codes
+            .lock()
+        {
+            codes.insert(
+                code.clone(),
+                AuthorizationCode {
+                    grant: Grant {
+                        client_id: client_id.to_string(),
+                        subject: self
+                            .inner
+                            .subject
+                            .clone(),
+                        scope: params
+                            .get("scope")
+                            .map(str::to_string),
+                    },
+                    redirect_uri: redirect_uri.to_string(),
+                    code_challenge,
+                },
+            );
+        }
+
+        let mut query = form_urlencoded::Serializer::new(String::new());
+        query.append_pair("code", &code);
+        if let Some(state) = params.get("state") {
+            query.append_pair("state", state);
+        }
+        let separator = if redirect_uri.contains('?') { '&' } else { '?' };
+        let location = format!("{}{}{}", redirect_uri, separator, query.finish());
+
+        Ok(StatusCode::FOUND
+            .respond()
+            .with_header(http::header::LOCATION.as_str(), &location)
+            .empty())
+    }
+
+    fn token(&self, request: &Request) -> Result<Respond, OAuthError> {
+        let params = request
+            .body()
+            .as_ref()
+            .map(|body| QueryParams::parse(&String::from_utf8_lossy(body)))
+            .unwrap_or_default();
+
+        let client_id = self.authenticate_client(request, &params)?;
+
+        let grant = match params.get("grant_type") {
+            Some("client_credentials") => Grant {
+                client_id: client_id.clone(),
+                subject: client_id,
+                scope: params
+                    .get("scope")
+                    .map(str::to_string),
+            },
+            Some("password") => {
+                let username = params
+                    .get("username")
+                    .unwrap_or_default();
+                let valid = self
+                    .inner
+                    .users
+                    .get(username)
+                    .is_some_and(|password| Some(password.as_str()) == params.get("password"));
+                if !valid {
+                    return Err(OAuthError::new(
+                        StatusCode::BAD_REQUEST,
+                        "invalid_grant",
+                        "invalid username or password",
+                    ));
+                }
+                Grant {
+                    client_id,
+                    subject: username.to_string(),
+                    scope: params
+                        .get("scope")
+                        .map(str::to_string),
+                }
+            }
+            Some("refresh_token") => params
+                .get("refresh_token")
+                .and_then(|token| {
+                    self.inner
+                        .refresh_tokens
+                        .lock()
+                        .ok()?
+                        .remove(token)
+                })
+                .filter(|grant| grant.client_id == client_id)
+                .ok_or_else(|| {
+                    OAuthError::new(
+                        StatusCode::BAD_REQUEST,
+                        "invalid_grant",
+                        "unknown refresh token",
+                    )
+                })?,
+            Some("authorization_code") => self.exchange_code(&client_id, &params)?,
+            Some(grant_type) => {
+                return Err(OAuthError::new(
+                    StatusCode::BAD_REQUEST,
+                    "unsupported_grant_type",
+                    format!("grant type {} is not supported", grant_type),
+                ))
+            }
+            None => {
+                return Err(OAuthError::new(
+                    StatusCode::BAD_REQUEST,
+                    "invalid_request",
+                    "missing grant_type",
+                ))
+            }
+        };
+
+        let mut response = json!({
+            "access_token": self.sign(&grant),
+            "token_type": "Bearer",
+            "expires_in": self.inner.token_ttl,
+        });
+        if let Some(scope) = &grant.scope {
+            response["scope"] = json!(scope);
+            if scope
+                .split_whitespace()
+                .any(|scope| scope == "openid")
+            {
+                response["id_token"] = json!(self.sign_id_token(&grant));
+            }
+        }
+        // Tokens obtained with the client's own credentials are not refreshable, as per RFC 6749
+        if params.get("grant_type") != Some("client_credentials") {
+            let refresh_token = random_token();
+            if let Ok(mut refresh_tokens) = self
+                .inner
+                .refresh_tokens
+                .lock()
+            {
+                refresh_tokens.insert(refresh_token.clone(), grant);
+                response["refresh_token"] = json!(refresh_token);
+            }
+        }
+
+        Ok(json_respond(StatusCode::OK, &response))
+    }
+
+    /// Authenticates the client with HTTP Basic credentials or the request parameters.
+    fn authenticate_client(
+        &self,
+        request: &Request,
+        params: &QueryParams,
+    ) -> Result<String, OAuthError> {
+        let (client_id, client_secret) = match basic_credentials(request) {
+            Some(credentials) => credentials,
+            None => (
+                params
+                    .get("client_id")
+                    .unwrap_or_default()
+                    .to_string(),
+                params
+                    .get("client_secret")
+                    .unwrap_or_default()
+                    .to_string(),
+            ),
+        };
+
+        let valid = self
+            .inner
+            .clients
+            .get(&client_id)
+            .is_some_and(|secret| {
+                *secret == client_secret
+                    // Public clients do not send a secret, PKCE authenticates their code exchanges
+                    || (client_secret.is_empty() && self.is_pkce_exchange(params))
+            });
+
+        if valid {
+            Ok(client_id)
+        } else {
+            Err(OAuthError::new(
+                StatusCode::UNAUTHORIZED,
+                "invalid_client",
+                "client authentication failed",
+            ))
+        }
+    }
+
+    /// Returns whether the request exchanges an authorization code bound to a PKCE challenge.
+    ///
+    /// Unknown codes are accepted, exchanging them fails with `invalid_grant` anyway.
+    fn is_pkce_exchange(&self, params: &QueryParams) -> bool {
+        if params.get("grant_type") != Some("authorization_code") {
+            return false;
+        }
+
+        let (Some(code), Ok(codes)) = (
+            params.get("code"),
+            self.inner
+                .codes
+                .lock(),
+        ) else {
+            return false;
+        };
+
+        codes
+            .get(code)
+            .map_or(true, |code| {
+                code.code_challenge
+                    .is_some()
+            })
+    }
+
+    fn exchange_code(&self, client_id: &str, params: &QueryParams) -> Result<Grant, OAuthError> {
+        let invalid_grant = |description: &str| {
+            OAuthError::new(StatusCode::BAD_REQUEST, "invalid_grant", description)
+        };
+
+        let code = params
+            .get("code")
+            .and_then(|code| {
+                self.inner
+                    .codes
+                    .lock()
+                    .ok()?
+                    .remove(code)
+            })
+            .ok_or_else(|| invalid_grant("unknown authorization code"))?;
+
+        if code.grant.client_id != client_id {
+            return Err(invalid_grant("authorization code was issued to another client"));
+        }
+        if params.get("redirect_uri")
+            != Some(
+                code.redirect_uri
+                    .as_str(),
+            )
+        {
+            return Err(invalid_grant("redirect_uri does not match the authorization request"));
+        }
+        if let Some((challenge, method)) = &code.code_challenge {
+            let verifier = params
+                .get("code_verifier")
+                .ok_or_else(|| invalid_grant("missing code_verifier"))?;
+            let computed = match method.as_str() {
+                "S256" => URL_SAFE_NO_PAD.encode(Sha256::digest(verifier.as_bytes())),
+                _ => verifier.to_string(),
+            };
+            if computed != *challenge {
+                return Err(invalid_grant("code_verifier does not match the code_challenge"));
+            }
+        }
+
+        Ok(code.grant)
+    }
+
+    fn claims(&self, grant: &Grant) -> Value {
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|now| now.as_secs())
+            .unwrap_or_default();
+
+        let mut claims = json!({
+            "iss": self.inner.issuer,
+            "sub": grant.subject,
+            "client_id": grant.client_id,
+            "iat": now,
+            "exp": now + self.inner.token_ttl,
+            "jti": random_token(),
+        });
+        if let Some(audience) = &self.inner.audience {
+            claims["aud"] = json!(audience);
+        }
+        if let Some(scope) = &grant.scope {
+            claims["scope"] = json!(scope);
+        }
+        claims
+    }
+
+    fn sign(&self, grant: &Grant) -> String {
+        self.encode(&self.claims(grant))
+    }
+
+    fn sign_id_token(&self, grant: &Grant) -> String {
+        let mut claims = self.claims(grant);
+        claims["aud"] = json!(grant.client_id);
+        self.encode(&claims)
+    }
+
+    fn encode(&self, claims: &Value) -> String {
+        let mut header = Header::new(Algorithm::ES256);
+        header.kid = Some(KEY_ID.to_string());
+
+        match encode(
+            &header,
+            claims,
+            &self
+                .inner
+                .encoding_key,
+        ) {
+            Ok(token) => token,
+            Err(e) => panic!("Failed to sign token: {}", e),
+        }
+    }
+}
+
+fn json_respond(status: StatusCode, value: &Value) -> Respond {
+    status
+        .respond()
+        .with_header(http::header::CONTENT_TYPE.as_str(), "application/json")
+        .with_header(http::header::CACHE_CONTROL.as_str(), "no-store")
+        .with_body(
+            value
+                .to_string()
+                .as_bytes(),
+        )
+}
+
+/// Generates an opaque token for authorization codes and refresh tokens.
+fn random_token() -> String {
+    URL_SAFE_NO_PAD.encode(rand::random::<[u8; 24]>())
+}
@@ -1,13 +1,43 @@
-use crate::{errors::EasyHttpMockError, mock::Mock};
+use crate::{
+    errors::{EasyHttpMockError, RequestError, ServerError},
+    journal::{Journal, JournalEntry},
+    mock::{Mock, Request, Respond, StatusCodeExt},
+};
+use bytes::Bytes;
+use caramelo::expect;
+use event_listener::Event;
+use http::StatusCode;
+use http_body_util::{BodyExt, LengthLimitError, Limited};
+use hyper::body::Body;
 use once_cell::sync::OnceCell;
 use std::{
     collections::HashSet,
-    future::Future,
-    sync::{Arc, Mutex},
+    future::{poll_fn, Future},
+    io,
+    net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr},
+    pin::pin,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc, Mutex,
+    },
+    task::Poll,
+    time::{Duration, Instant},
 };
 
 static PORTS_IN_USE: OnceCell<Mutex<HashSet<u16>>> = OnceCell::new();
 
+/// Time a listener has to accept connections once the server started
+pub const READY_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Default time in-flight requests have to complete once the server is stopping
+pub const DEFAULT_DRAIN_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Time a single connection attempt has while waiting for a listener
+const CONNECT_TIMEOUT: Duration = Duration::from_millis(100);
+
+/// Time between two connection attempts while waiting for a listener
+const RETRY_INTERVAL: Duration = Duration::from_millis(10);
+
 /// Server adapter trait to allow different http server implementations
 pub trait ServerAdapter {
     /// The configuration for the server adapter
@@ -41,6 +71,29 @@ pub trait ServerAdapter {
     ///
     fn base_url(&self) -> String;
 
+    /// Get the base URL of the plain HTTP listener
+    ///
+    /// Servers listening on both HTTP and HTTPS override this and `https_base_url`, by default the
+    /// scheme of `base_url` decides which one is available.
+    ///
+    /// # Returns
+    ///
+    /// * `Option<String>` - The base URL of the HTTP listener, if the server serves plain HTTP
+    ///
+    fn http_base_url(&self) -> Option<String> {
+        Some(self.base_url()).filter(|base_url| base_url.starts_with("http://"))
+    }
+
+    /// Get the base URL of the HTTPS listener
+    ///
+    /// # Returns
+    ///
+    /// * `Option<String>` - The base URL of the HTTPS listener, if the server serves HTTPS
+    ///
+    fn https_base_url(&self) -> Option<String> {
+        Some(self.base_url()).filter(|base_url| base_url.starts_with("https://"))
+    }
+
     /// Get the configuration of the server
     ///
     /// # Returns
@@ -57,6 +110,14 @@ pub trait ServerAdapter {
     ///
     fn config_mut(&mut self) -> &mut Self::Config;
 
+    /// Get the journal of the requests received by the server
+    ///
+    /// # Returns
+    ///
+    /// * `&Journal` - The journal of the server
+    ///
+    fn journal(&self) -> &Journal;
+
     /// Set the mocker to handle incoming requests
     ///
     /// # Arguments
@@ -84,6 +145,12 @@ pub trait ServerAdapter {
     /// * `Result<(), EasyHttpMockError>` - The result of the operation
     ///
     fn stop(&mut self) -> impl Future<Output = Result<(), EasyHttpMockError>>;
+
+    /// Stop the server without waiting for it, called when the mock is dropped
+    ///
+    /// Adapters signal their listeners to close, so a test that does not call `stop` does not
+    /// keep the port taken. It must be safe to call after `stop` and on a server never started.
+    fn shutdown(&mut self) {}
 }
 
 /// Port generator trait to allow different port generation strategies
@@ -117,3 +184,340 @@ pub fn generate_randon_port() -> u16 {
         Err(_) => rand::random_range(9000..65535),
     }
 }
+
+/// Handle a request received by a server adapter
+///
+/// Lets the mock intercept the request (OAuth endpoints, Basic auth challenges), otherwise records
+/// it in the journal and checks it against the matcher of the mock. Adapters only have to convert
+/// the request and the respond from and to their own types. Intercepted requests are answered
+/// without being recorded, requests to a namespace router are recorded by their namespace.
+///
+/// # Arguments
+///
+/// * `mock` - The mock registered on the server
+/// * `journal` - The journal of the server
+/// * `request` - The request received, with its body
+///
+/// # Returns
+///
+/// * `Option<Respond>` - The respond to send, `None` when the mock has no respond
+///
+/// # Panics
+///
+/// Panics if the request does not match the mock
+pub fn handle_request(mock: &Mock, journal: &Journal, request: Request) -> Option<Respond> {
+    // Namespaces journal the requests they receive, the journal of the server routing to them
+    // would only grow
+    if let Some(namespaces) = mock
+        .request()
+        .namespaces()
+    {
+        return Some(namespaces.route(&request));
+    }
+
+    // Intercepted requests never reach the matcher, journaling them would report them as
+    // mismatches
+    if let Some(respond) = mock
+        .request()
+        .intercept(&request)
+    {
+        return Some(respond);
+    }
+
+    journal.record(JournalEntry::new(request.clone(), mock.request()));
+
+    let path_params = mock
+        .request()
+        .captures(&request);
+    let request = request.with_path_params(path_params);
+
+    let respond = mock
+        .request()
+        .respond()
+        .map(|respond| respond.for_request(&request));
+
+    expect(request).to_match(
+        mock.request()
+            .matcher()
+            .clone(),
+    );
+
+    respond
+}
+
+/// What a server does with a request whose body exceeds the maximum size
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum OversizedBody {
+    /// Answer with `413 Payload Too Large`
+    #[default]
+    Reject,
+    /// Reset the connection without answering
+    ///
+    /// Only the hyper adapter supports it. Vetis cannot abort a connection from a handler, so the
+    /// Vetis adapters reject this policy when the server starts.
+    Reset,
+}
+
+/// Collect the body of a request, up to the given size
+///
+/// Adapters collect bodies with this function so the limit behaves the same on every server.
+///
+/// # Arguments
+///
+/// * `body` - The body of the request
+/// * `limit` - The maximum size of the body, `None` for no limit
+///
+/// # Returns
+///
+/// * `Result<Bytes, RequestError>` - The body, `RequestError::BodyTooLarge` when it exceeds the
+///   limit or `RequestError::Failed` when it could not be read
+pub async fn collect_body<B>(body: B, limit: Option<usize>) -> Result<Bytes, RequestError>
+where
+    B: Body,
+    B::Error: Into<Box<dyn std::error::Error + Send + Sync>>,
+{
+    let Some(limit) = limit else {
+        return body
+            .collect()
+            .await
+            .map(|collected| collected.to_bytes())
+            .map_err(|e| RequestError::Failed(e.into().to_string()));
+    };
+
+    Limited::new(body, limit)
+        .collect()
+        .await
+        .map(|collected| collected.to_bytes())
+        .map_err(|e| {
+            if e.is::<LengthLimitError>() {
+                RequestError::BodyTooLarge(limit)
+            } else {
+                RequestError::Failed(e.to_string())
+            }
+        })
+}
+
+/// Build the respond sent to a request whose body exceeds the maximum size
+///
+/// # Arguments
+///
+/// * `limit` - The maximum size of the body
+///
+/// # Returns
+///
+/// * `Respond` - The `413 Payload Too Large` respond
+pub fn payload_too_large(limit: usize) -> Respond {
+    StatusCode::PAYLOAD_TOO_LARGE
+        .respond()
+        .with_body(
+            RequestError::BodyTooLarge(limit)
+                .to_string()
+                .as_bytes(),
+        )
+}
+
+/// Wait until a listener accepts connections
+///
+/// Servers binding their listeners in a background task return from `start` before they accept
+/// connections, adapters wait for them so tests never race the listener. The adapter passes the
+/// async connect and sleep of its runtime, so waiting never blocks the runtime the listener runs
+/// on.
+///
+/// # Arguments
+///
+/// * `interface` - The interface the listener is bound to, the loopback address is used for the
+///   unspecified address
+/// * `port` - The port of the listener
+/// * `timeout` - The time the listener has to accept a connection
+/// * `connect` - Opens a connection to an address with the runtime of the adapter
+/// * `sleep` - Sleeps for a duration with the runtime of the adapter
+///
+/// # Returns
+///
+/// * `Result<(), EasyHttpMockError>` - An error if the listener did not accept a connection in
+///   time
+pub async fn wait_until_ready<C, CF, S, SF>(
+    interface: &str,
+    port: u16,
+    timeout: Duration,
+    connect: C,
+    sleep: S,
+) -> Result<(), EasyHttpMockError>
+where
+    C: Fn(SocketAddr) -> CF,
+    CF: Future<Output = io::Result<()>>,
+    S: Fn(Duration) -> SF,
+    SF: Future<Output = ()>,
+{
+    let ip = match interface.parse::<IpAddr>() {
+        Ok(IpAddr::V4(ip)) if ip.is_unspecified() => IpAddr::V4(Ipv4Addr::LOCALHOST),
+        Ok(IpAddr::V6(ip)) if ip.is_unspecified() => IpAddr::V6(Ipv6Addr::LOCALHOST),
+        Ok(ip) => ip,
+        Err(_) => IpAddr::V4(Ipv4Addr::LOCALHOST),
+    };
+    let address = SocketAddr::new(ip, port);
+
+    let deadline = Instant::now() + timeout;
+    loop {
+        let remaining = deadline.saturating_duration_since(Instant::now());
+        let attempt = race(connect(address), sleep(remaining.min(CONNECT_TIMEOUT))).await;
+        if let Some(Ok(())) = attempt {
+            return Ok(());
+        }
+
+        if Instant::now() >= deadline {
+            return Err(ServerError::Start(format!(
+                "Listener on {} is not accepting connections",
+                address
+            ))
+            .into());
+        }
+
+        sleep(RETRY_INTERVAL).await;
+    }
+}
+
+/// Requests being handled by a server, so it can let them complete before stopping
+///
+/// Cloning the tracker is cheap, every clone counts the same requests.
+///
+/// # Examples
+///
+/// ```rust,ignore
+/// let in_flight = InFlight::new();
+/// let _guard = in_flight.enter();
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct InFlight {
+    requests: Arc<AtomicUsize>,
+    completed: Arc<Event>,
+}
+
+impl InFlight {
+    /// Creates a tracker without any request
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Counts a request until the returned guard is dropped
+    ///
+    /// # Returns
+    ///
+    /// * `InFlightGuard` - The guard of the request
+    pub fn enter(&self) -> InFlightGuard {
+        self.requests
+            .fetch_add(1, Ordering::SeqCst);
+
+        InFlightGuard {
+            requests: self
+                .requests
+                .clone(),
+            completed: self
+                .completed
+                .clone(),
+        }
+    }
+
+    /// Returns the number of requests being handled
+    pub fn len(&self) -> usize {
+        self.requests
+            .load(Ordering::SeqCst)
+    }
+
+    /// Returns whether no request is being handled
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Wait for the requests being handled to complete
+    ///
+    /// The wait is woken when the last request completes, it does not poll.
+    ///
+    /// # Arguments
+    ///
+    /// * `timeout` - A sleep of the runtime of the adapter, for the time the requests have to
+    ///   complete
+    ///
+    /// # Returns
+    ///
+    /// * `bool` - Whether every request completed in time
+    pub async fn drain<T>(&self, timeout: T) -> bool
+    where
+        T: Future<Output = ()>,
+    {
+        let mut timeout = pin!(timeout);
+        loop {
+            if self.is_empty() {
+                return true;
+            }
+
+            let completed = self
+                .completed
+                .listen();
+            if self.is_empty() {
+                return true;
+            }
+
+            if race(completed, timeout.as_mut())
+                .await
+                .is_none()
+            {
+                return self.is_empty();
+            }
+        }
+    }
+}
+
+/// Guard of a request counted by `InFlight`, the request completes when it is dropped
+#[derive(Debug)]
+pub struct InFlightGuard {
+    requests: Arc<AtomicUsize>,
+    completed: Arc<Event>,
+}
+
+impl Drop for InFlightGuard {
+    fn drop(&mut self) {
+        if self
+            .requests
+            .fetch_sub(1, Ordering::SeqCst)
+            == 1
+        {
+            self.completed
+                .notify(usize::MAX);
+        }
+    }
+}
+
+/// Runs a future until it completes or a timer elapses
+///
+/// # Arguments
+///
+/// * `future` - The future to run
+/// * `timer` - The timer bounding the future
+///
+/// # Returns
+///
+/// * `Option<F::Output>` - The output of the future, `None` if the timer elapsed first
+async fn race<F, T>(future: F, timer: T) -> Option<F::Output>
+where
+    F: Future,
+    T: Future<Output = ()>,
+{
+    let mut future = pin!(future);
+    let mut timer = pin!(timer);
+
+    poll_fn(|cx| {
+        if let Poll::Ready(output) = future
+            .as_mut()
+            .poll(cx)
+        {
+            return Poll::Ready(Some(output));
+        }
+
+        timer
+            .as_mut()
+            .poll(cx)
+            .map(|()| None)
+    })
+    .await
+}
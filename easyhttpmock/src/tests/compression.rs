@@ -0,0 +1,245 @@
+use caramelo::Matcher;
+use flate2::{write::GzEncoder, Compression};
+use http::{
+    header::{ACCEPT_ENCODING, CONTENT_ENCODING},
+    StatusCode, Uri,
+};
+use std::io::Write;
+
+use crate::{
+    compression::{negotiate_encoding, Encoding},
+    errors::RequestError,
+    journal::{Journal, JournalEntry},
+    matchers::{and, body, content_encoding, path, MatcherNode},
+    mock::{given, Mock, Request, StatusCodeExt},
+    server::handle_request,
+};
+
+fn gzip(body: &[u8]) -> Vec<u8> {
+    let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+    encoder
+        .write_all(body)
+        .unwrap();
+    encoder
+        .finish()
+        .unwrap()
+}
+
+fn request(content_encoding: &str, body: Vec<u8>) -> Request {
+    Request::post(Uri::from_static("/upload"))
+        .header(CONTENT_ENCODING, content_encoding)
+        .empty()
+        .unwrap()
+        .with_body(body)
+}
+
+#[test]
+fn test_gzip_body_decoded_before_matching() {
+    let compressed = gzip(b"{\"name\":\"John\"}");
+    let request = request("gzip", compressed.clone());
+
+    assert_eq!(
+        request
+            .body()
+            .as_deref(),
+        Some(&b"{\"name\":\"John\"}"[..])
+    );
+    assert_eq!(
+        request
+            .raw_body()
+            .map(|body| body.to_vec()),
+        Some(compressed.clone())
+    );
+
+    let mock = given(and(vec![body("John"), content_encoding(Encoding::Gzip)]));
+    assert!(mock
+        .explain(&request)
+        .is_none());
+
+    let journal = Journal::new();
+    journal.record(JournalEntry::new(request, &mock));
+    assert!(journal
+        .verify()
+        .is_ok());
+    assert_eq!(
+        journal.entries()[0]
+            .request()
+            .raw_body()
+            .map(|body| body.to_vec()),
+        Some(compressed)
+    );
+}
+
+#[test]
+fn test_invalid_body_kept_raw() {
+    let request = request("gzip", b"not gzip".to_vec());
+
+    assert_eq!(
+        request
+            .body()
+            .as_deref(),
+        Some(&b"not gzip"[..])
+    );
+    assert!(content_encoding(Encoding::Gzip).matches(&request));
+}
+
+#[test]
+fn test_content_encoding_mismatch() {
+    let request = request("identity", b"plain".to_vec());
+    let Some(mismatch) = content_encoding(Encoding::Brotli).explain(&request) else {
+        panic!("Request without encoding should not match");
+    };
+
+    assert!(mismatch
+        .to_string()
+        .contains("body was not encoded"));
+    assert_eq!("x-gzip".parse::<Encoding>(), Ok(Encoding::Gzip));
+}
+
+#[test]
+fn test_compressed_respond() {
+    let respond = StatusCode::OK
+        .respond()
+        .compressed(Encoding::Gzip)
+        .with_body(b"hello");
+
+    assert_eq!(
+        respond
+            .headers()
+            .get("content-encoding")
+            .map(String::as_str),
+        Some("gzip")
+    );
+    assert_eq!(
+        Encoding::Gzip
+            .decode(&respond.body())
+            .unwrap(),
+        b"hello"
+    );
+}
+
+#[test]
+fn test_negotiated_respond() {
+    let mock = Mock::of(
+        given(path("/hello")).will_return(
+            StatusCode::OK
+                .respond()
+                .negotiate_encoding()
+                .with_body(b"hello"),
+        ),
+    )
+    .inner();
+    let journal = Journal::new();
+
+    let accepting = Request::get(Uri::from_static("/hello"))
+        .header(ACCEPT_ENCODING, "identity, gzip;q=0.8, unknown")
+        .empty()
+        .unwrap();
+    let respond = handle_request(&mock, &journal, accepting).unwrap();
+    assert_eq!(
+        respond
+            .headers()
+            .get("content-encoding")
+            .map(String::as_str),
+        Some("gzip")
+    );
+    assert_eq!(
+        Encoding::Gzip
+            .decode(&respond.body())
+            .unwrap(),
+        b"hello"
+    );
+
+    let refusing = Request::get(Uri::from_static("/hello"))
+        .header(ACCEPT_ENCODING, "gzip;q=0")
+        .empty()
+        .unwrap();
+    let respond = handle_request(&mock, &journal, refusing).unwrap();
+    assert!(!respond
+        .headers()
+        .contains_key("content-encoding"));
+    assert_eq!(respond.body(), &b"hello"[..]);
+}
+
+#[test]
+fn test_respond_encoding_headers_ignore_case() {
+    let respond = StatusCode::OK
+        .respond()
+        .with_header("Content-Encoding", "identity")
+        .compressed(Encoding::Gzip)
+        .with_body(b"hello");
+
+    let headers = respond.headers();
+    let encodings = headers
+        .iter()
+        .filter(|(name, _)| name.eq_ignore_ascii_case("content-encoding"))
+        .map(|(_, value)| value.as_str())
+        .collect::<Vec<_>>();
+    assert_eq!(encodings, vec!["gzip"]);
+
+    let mock = Mock::of(
+        given(path("/hello")).will_return(
+            StatusCode::OK
+                .respond()
+                .with_header("Vary", "Origin")
+                .negotiate_encoding()
+                .with_body(b"hello"),
+        ),
+    )
+    .inner();
+    let request = Request::get(Uri::from_static("/hello"))
+        .header(ACCEPT_ENCODING, "gzip")
+        .empty()
+        .unwrap();
+    let respond = handle_request(&mock, &Journal::new(), request).unwrap();
+
+    let headers = respond.headers();
+    let vary = headers
+        .iter()
+        .filter(|(name, _)| name.eq_ignore_ascii_case("vary"))
+        .map(|(_, value)| value.as_str())
+        .collect::<Vec<_>>();
+    assert_eq!(vary, vec!["Origin, accept-encoding"]);
+}
+
+#[test]
+fn test_negotiate_encoding() {
+    let request = Request::get(Uri::from_static("/"))
+        .header(ACCEPT_ENCODING, "*;q=0.5, gzip")
+        .empty()
+        .unwrap();
+    assert_eq!(negotiate_encoding(request.headers()), Some(Encoding::Gzip));
+
+    let request = Request::get(Uri::from_static("/"))
+        .empty()
+        .unwrap();
+    assert_eq!(negotiate_encoding(request.headers()), None);
+}
+
+#[test]
+fn test_decoded_body_limited() {
+    let compressed = gzip(&[0; 64 * 1024]);
+    assert!(compressed.len() < 1024);
+    let request = Request::post(Uri::from_static("/upload"))
+        .header(CONTENT_ENCODING, "gzip")
+        .empty()
+        .unwrap();
+
+    assert!(matches!(
+        request
+            .clone()
+            .with_limited_body(compressed.clone(), Some(1024)),
+        Err(RequestError::BodyTooLarge(1024))
+    ));
+
+    let request = request
+        .with_limited_body(compressed, Some(64 * 1024))
+        .unwrap();
+    assert_eq!(
+        request
+            .body()
+            .as_ref()
+            .map(|body| body.len()),
+        Some(64 * 1024)
+    );
+}
@@ -0,0 +1,129 @@
+use caramelo::{expect, Matcher};
+use http::{header::CONTENT_TYPE, Uri};
+
+use crate::{
+    matchers::{form_fields, multipart, part, MatcherNode},
+    mock::Request,
+};
+
+const UPLOAD: &str = "--boundary\r\n\
+Content-Disposition: form-data; name=\"title\"\r\n\
+\r\n\
+Holidays\r\n\
+--boundary\r\n\
+Content-Disposition: form-data; name=\"photo\"; filename=\"beach.png\"\r\n\
+Content-Type: image/png\r\n\
+\r\n\
+PNG\r\n\
+--boundary--\r\n";
+
+fn form_request(content_type: &str, body: &'static str) -> Request {
+    Request::post(Uri::from_static("/upload"))
+        .header(CONTENT_TYPE, content_type)
+        .body(body)
+        .unwrap()
+}
+
+#[test]
+fn test_form_fields() {
+    let request =
+        form_request("application/x-www-form-urlencoded", "name=John+Doe&tag=a&tag=b&age=30");
+
+    expect(request).to_have(form_fields(&[("name", "John Doe"), ("tag", "b"), ("tag", "a")]));
+}
+
+#[test]
+fn test_form_fields_mismatch() {
+    let request = form_request("application/x-www-form-urlencoded", "name=John");
+    let mismatch = form_fields(&[("name", "Jane")])
+        .explain(&request)
+        .unwrap();
+
+    assert_eq!(mismatch.actual(), "missing name=\"Jane\"; form fields were name=\"John\"");
+
+    let request = form_request("application/json", "name=Jane");
+    assert!(!form_fields(&[("name", "Jane")]).matches(&request));
+}
+
+#[test]
+fn test_multipart_parts() {
+    let request = form_request("multipart/form-data; boundary=boundary", UPLOAD);
+
+    expect(request).to_have(multipart(vec![
+        part("photo")
+            .filename("beach.png")
+            .content_type("image/png"),
+        part("title").contents("^Holidays$"),
+    ]));
+}
+
+#[test]
+fn test_multipart_mismatch() {
+    let request = form_request("multipart/form-data; boundary=\"boundary\"", UPLOAD);
+    let mismatch = multipart(vec![part("photo").content_type("image/jpeg"), part("video")])
+        .explain(&request)
+        .unwrap();
+
+    let causes = mismatch
+        .causes()
+        .iter()
+        .map(|cause| cause.actual())
+        .collect::<Vec<_>>();
+    assert_eq!(causes, vec!["content type was image/png", "missing"]);
+    assert!(mismatch
+        .actual()
+        .ends_with("parts were \"title\", \"photo\" (\"beach.png\", image/png)"));
+}
+
+#[test]
+fn test_multipart_parts_assignment() {
+    let request = form_request(
+        "multipart/form-data; boundary=boundary",
+        "--boundary\r\n\
+Content-Disposition: form-data; name=\"f\"; filename=\"a.png\"\r\n\
+\r\n\
+A\r\n\
+--boundary\r\n\
+Content-Disposition: form-data; name=\"f\"; filename=\"b.png\"\r\n\
+\r\n\
+B\r\n\
+--boundary--\r\n",
+    );
+
+    expect(request.clone()).to_have(multipart(vec![part("f"), part("f").filename("a.png")]));
+    assert!(!multipart(vec![part("f").filename("b.png"), part("f").filename("b.png")])
+        .matches(&request));
+}
+
+#[cfg(feature = "json")]
+#[test]
+fn test_multipart_part_with_json_body() {
+    use crate::matchers::{exact_json_body, json_path};
+    use serde_json::json;
+
+    let request = Request::post(Uri::from_static("/api/users"))
+        .header("content-type", "multipart/form-data; boundary=b")
+        .body(
+            "--b\r\n\
+Content-Disposition: form-data; name=\"profile\"\r\n\
+Content-Type: application/json\r\n\
+\r\n\
+{\"name\": \"John\", \"age\": 30}\r\n\
+--b--\r\n",
+        )
+        .unwrap();
+
+    expect(request.clone()).to_have(multipart(vec![
+        part("profile").matching(exact_json_body(&json!({"age": 30, "name": "John"})))
+    ]));
+
+    let mismatch = multipart(vec![part("profile").matching(json_path("$.name").equals(&"Jane"))])
+        .explain(&request)
+        .unwrap();
+    assert_eq!(
+        mismatch.causes()[0]
+            .causes()
+            .len(),
+        1
+    );
+}
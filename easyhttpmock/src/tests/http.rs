@@ -1,9 +1,14 @@
-use caramelo::{expect, matchers::eq, MatcherExt};
-use http::{header::CONTENT_TYPE, Method, Uri, Version};
+use caramelo::{expect, matchers::eq, Matcher, MatcherExt};
+use http::{header::CONTENT_TYPE, Method, StatusCode, Uri, Version};
 
 use crate::{
-    matchers::{header, header_value, method, path},
-    mock::Request,
+    journal::{Journal, JournalEntry},
+    matchers::{
+        and, basic_auth, exact_query_params, header, header_value, method, node,
+        partial_query_params, path, path_template, query_param_value, version, MatcherNode,
+    },
+    mock::{given, Mock, Request, StatusCodeExt},
+    server::handle_request,
 };
 
 #[test]
@@ -137,6 +142,22 @@ fn test_version_matcher() {
     expect(request.version()).to_be(eq(&Version::HTTP_11));
 }
 
+#[test]
+fn test_protocol_version_matcher() {
+    let request = Request::get(Uri::from_static("/api/users"))
+        .version(Version::HTTP_2)
+        .empty()
+        .unwrap();
+
+    expect(request.clone()).to_have(version(Version::HTTP_2).and(version("h2")));
+
+    let mismatch = version("HTTP/3")
+        .explain(&request)
+        .unwrap();
+    assert_eq!(mismatch.expected(), "version matching HTTP/3.0");
+    assert_eq!(mismatch.actual(), "version was HTTP/2.0");
+}
+
 #[test]
 fn test_req_method_matcher() {
     let request = Request::get(Uri::from_static("/api/users"))
@@ -178,3 +199,308 @@ fn test_header_value_regex_failure() {
 
     expect(request).to_have(header_value("content-type", r"^text/.*"));
 }
+
+#[test]
+fn test_path_template_matcher() {
+    let request = Request::get(Uri::from_static("/users/42/orders/7?expand=items"))
+        .empty()
+        .unwrap();
+
+    let matcher = path_template(r"/users/{id}/orders/{orderId:\d+}");
+    expect(request.clone()).to_have(matcher.clone());
+
+    let params = matcher.captures(&request);
+    assert_eq!(params.get("id"), Some(&"42".to_string()));
+    assert_eq!(params.get("orderId"), Some(&"7".to_string()));
+}
+
+#[test]
+#[should_panic = "to have path matching template /users/{id}/orders/{orderId:\\d+}"]
+fn test_path_template_matcher_failure() {
+    let request = Request::get(Uri::from_static("/users/42/orders/latest"))
+        .empty()
+        .unwrap();
+
+    expect(request).to_have(path_template(r"/users/{id}/orders/{orderId:\d+}"));
+}
+
+#[test]
+fn test_path_template_wildcards() {
+    let request = Request::get(Uri::from_static("/static/v2/css/site.css"))
+        .empty()
+        .unwrap();
+
+    expect(request.clone()).to_have(path_template("/static/*/**"));
+    expect(request.clone()).to_have(path_template("/static/**/{file}"));
+    assert!(!path_template("/static/*/{file}").matches(&request));
+}
+
+#[test]
+#[should_panic = "Invalid path template"]
+fn test_path_template_unbalanced() {
+    path_template("/users/{id/orders");
+}
+
+#[test]
+fn test_journal_entry_path_params() {
+    let mock = given(and(vec![path_template("/users/{id}"), method("GET")]));
+    let journal = Journal::new();
+
+    journal.record(JournalEntry::new(
+        Request::get(Uri::from_static("/users/42"))
+            .empty()
+            .unwrap(),
+        &mock,
+    ));
+    journal.record(JournalEntry::new(
+        Request::post(Uri::from_static("/users/42"))
+            .empty()
+            .unwrap(),
+        &mock,
+    ));
+
+    let entries = journal.entries();
+    assert_eq!(entries.len(), 2);
+    assert!(entries[0].is_matched());
+    assert_eq!(
+        entries[0]
+            .path_params()
+            .get("id"),
+        Some(&"42".to_string())
+    );
+    assert!(!entries[1].is_matched());
+    assert!(entries[1]
+        .path_params()
+        .is_empty());
+}
+
+#[test]
+fn test_journal_verify() {
+    let mock = given(path("/users"));
+    let journal = Journal::new();
+
+    journal.record(JournalEntry::new(
+        Request::get(Uri::from_static("/users"))
+            .empty()
+            .unwrap(),
+        &mock,
+    ));
+    assert!(journal
+        .verify()
+        .is_ok());
+
+    journal.record(JournalEntry::new(
+        Request::get(Uri::from_static("/orders"))
+            .empty()
+            .unwrap(),
+        &mock,
+    ));
+    let Err(error) = journal.verify() else {
+        panic!("Journal with an unmatched request should not verify");
+    };
+    assert!(error
+        .to_string()
+        .contains("GET /orders did not match the mock"));
+}
+
+#[test]
+fn test_journal_body_limit() {
+    let mock = given(path("/upload"));
+    let journal = Journal::with_body_limit(Some(4));
+
+    journal.record(JournalEntry::new(
+        Request::post(Uri::from_static("/upload"))
+            .body("0123456789")
+            .unwrap(),
+        &mock,
+    ));
+    journal.record(JournalEntry::new(
+        Request::post(Uri::from_static("/upload"))
+            .body("0123")
+            .unwrap(),
+        &mock,
+    ));
+
+    let entries = journal.entries();
+    assert!(entries[0].is_truncated());
+    assert_eq!(entries[0].truncated_bytes(), 6);
+    assert_eq!(
+        entries[0]
+            .request()
+            .body()
+            .as_deref(),
+        Some(&b"0123...[6 bytes truncated]"[..])
+    );
+    assert!(!entries[1].is_truncated());
+    assert_eq!(
+        entries[1]
+            .request()
+            .body()
+            .as_deref(),
+        Some(&b"0123"[..])
+    );
+}
+
+fn request_with_query(uri: &'static str) -> Request {
+    let (parts, _) = http::Request::get(uri)
+        .body(())
+        .unwrap()
+        .into_parts();
+
+    Request::from_parts(parts)
+}
+
+#[test]
+fn test_query_params_decoding() {
+    let request =
+        request_with_query("/search?tag=a&tag=b&name=John%20Doe&city=S%C3%A3o+Paulo&flag");
+
+    let query_params = request
+        .query_params()
+        .as_ref()
+        .unwrap();
+
+    assert_eq!(query_params.get_all("tag"), vec!["a", "b"]);
+    assert_eq!(query_params.get("name"), Some("John Doe"));
+    assert_eq!(query_params.get("city"), Some("São Paulo"));
+    assert_eq!(query_params.get("flag"), Some(""));
+}
+
+#[test]
+fn test_query_param_value_matcher() {
+    let request = request_with_query("/search?tag=a&tag=b&name=John%20Doe");
+
+    expect(request.clone()).to_have(query_param_value("name", "John Doe"));
+    expect(request.clone()).to_have(query_param_value("tag", "b"));
+    assert!(!query_param_value("tag", "c").matches(&request));
+    assert_eq!(
+        query_param_value("tag", "c")
+            .explain(&request)
+            .unwrap()
+            .actual(),
+        "values were [\"a\", \"b\"]"
+    );
+}
+
+#[test]
+fn test_partial_query_params_matcher() {
+    let request = request_with_query("/search?tag=a&tag=b&page=2");
+
+    expect(request.clone()).to_have(partial_query_params(&[("tag", "b"), ("tag", "a")]));
+    assert!(!partial_query_params(&[("tag", "a"), ("tag", "a")]).matches(&request));
+}
+
+#[test]
+fn test_exact_query_params_matcher() {
+    let request = request_with_query("/search?tag=a&tag=b&page=2");
+
+    expect(request.clone()).to_have(exact_query_params(&[
+        ("page", "2"),
+        ("tag", "b"),
+        ("tag", "a"),
+    ]));
+
+    let mismatch = exact_query_params(&[("tag", "a"), ("tag", "b"), ("sort", "asc")])
+        .explain(&request)
+        .unwrap();
+    assert_eq!(
+        mismatch.actual(),
+        "missing sort=\"asc\"; unexpected page=\"2\"; query params were tag=\"a\", tag=\"b\", page=\"2\""
+    );
+}
+
+fn request_with_authorization(authorization: &str) -> Request {
+    Request::get(Uri::from_static("/api/users"))
+        .header("authorization", authorization)
+        .empty()
+        .unwrap()
+}
+
+#[test]
+fn test_basic_auth_matcher_decodes_credentials() {
+    expect(request_with_authorization("Basic dXNlcjpwYXNz")).to_have(basic_auth("user", "pass"));
+
+    let mismatch = basic_auth("user", "pass")
+        .explain(&request_with_authorization("Basic dXNlcjp3cm9uZw=="))
+        .unwrap();
+    assert_eq!(mismatch.actual(), "password did not match");
+}
+
+#[test]
+fn test_basic_auth_challenge() {
+    let mock = given(basic_auth("user", "pass")).challenge_basic("api");
+
+    let challenge = mock
+        .challenge(
+            &Request::get(Uri::from_static("/api/users"))
+                .empty()
+                .unwrap(),
+        )
+        .unwrap();
+    assert_eq!(challenge.status_code(), StatusCode::UNAUTHORIZED);
+    assert_eq!(
+        challenge
+            .headers()
+            .get("www-authenticate"),
+        Some(&"Basic realm=\"api\"".to_string())
+    );
+
+    assert!(mock
+        .challenge(&request_with_authorization("Basic dXNlcjp3cm9uZw=="))
+        .is_none());
+}
+
+#[test]
+fn test_basic_auth_challenge_then_retry_verifies() {
+    let mock = Mock::of(
+        given(basic_auth("user", "pass"))
+            .challenge_basic("api")
+            .will_return(
+                StatusCode::OK
+                    .respond()
+                    .empty(),
+            ),
+    )
+    .inner();
+    let journal = Journal::new();
+
+    let challenge = handle_request(
+        &mock,
+        &journal,
+        Request::get(Uri::from_static("/api/users"))
+            .empty()
+            .unwrap(),
+    )
+    .unwrap();
+    assert_eq!(challenge.status_code(), StatusCode::UNAUTHORIZED);
+
+    // user:pass
+    let retry =
+        handle_request(&mock, &journal, request_with_authorization("Basic dXNlcjpwYXNz")).unwrap();
+    assert_eq!(retry.status_code(), StatusCode::OK);
+
+    assert_eq!(journal.len(), 1);
+    assert!(journal
+        .verify()
+        .is_ok());
+}
+
+#[test]
+fn test_node_wraps_typed_matcher() {
+    let request = Request::get(Uri::from_static("/api/users"))
+        .empty()
+        .unwrap();
+    let matcher = node(eq(request.clone()));
+
+    assert!(matcher
+        .explain(&request)
+        .is_none());
+
+    let other = Request::get(Uri::from_static("/api/orders"))
+        .empty()
+        .unwrap();
+    let mismatch = matcher
+        .explain(&other)
+        .unwrap();
+    assert_eq!(mismatch.to_string(), format!("{}: did not match", matcher.description()));
+}
@@ -0,0 +1,172 @@
+use caramelo::{expect, Matcher};
+use http::Uri;
+use serde_json::json;
+
+use crate::{
+    matchers::{exact_json_body, json_path, JsonPredicate, JsonType, MatcherNode},
+    mock::Request,
+};
+
+fn json_request(body: &'static str) -> Request {
+    Request::post(Uri::from_static("/api/users"))
+        .header("content-type", "application/json")
+        .body(body)
+        .unwrap()
+}
+
+#[test]
+fn test_exact_json_body_ignores_formatting() {
+    let request = json_request(
+        r#"{
+            "age": 30.0,
+            "name": "John",
+            "tags": ["a", "b"]
+        }"#,
+    );
+
+    expect(request)
+        .to_have(exact_json_body(&json!({"name": "John", "age": 30, "tags": ["a", "b"]})));
+}
+
+#[test]
+fn test_exact_json_body_reports_pointers() {
+    let request = json_request(r#"{"name": "Jane", "age": 30, "address": {"city": "Lisbon"}}"#);
+
+    let mismatch =
+        exact_json_body(&json!({"name": "John", "age": 30, "email": "john@example.com"}))
+            .explain(&request)
+            .unwrap();
+
+    let mut leaves = mismatch
+        .leaves()
+        .iter()
+        .map(|leaf| leaf.to_string())
+        .collect::<Vec<_>>();
+    leaves.sort();
+
+    assert!(mismatch
+        .actual()
+        .starts_with("body differs at "));
+    assert_eq!(
+        leaves,
+        vec![
+            "/address: unexpected field with value {\"city\":\"Lisbon\"}",
+            "/email: missing, expected \"john@example.com\"",
+            "/name: expected \"John\", got \"Jane\"",
+        ]
+    );
+}
+
+#[test]
+fn test_exact_json_body_options() {
+    let request = json_request(
+        r#"{"id": "9f1c", "tags": ["b", "a"], "items": [{"id": 1, "sku": "x"}], "extra": true}"#,
+    );
+
+    let expected = json!({"id": "", "tags": ["a", "b"], "items": [{"id": 0, "sku": "x"}]});
+
+    assert!(!exact_json_body(&expected).matches(&request));
+    expect(request).to_have(
+        exact_json_body(&expected)
+            .ignore_array_order()
+            .ignore_extra_fields()
+            .wildcard("/id")
+            .wildcard("/items/*/id"),
+    );
+}
+
+#[test]
+fn test_exact_json_body_unordered_arrays() {
+    let request = json_request(r#"[{"a": 1, "b": 2}, {"a": 1}]"#);
+
+    expect(request.clone()).to_have(
+        exact_json_body(&json!([{"a": 1}, {"a": 1, "b": 2}]))
+            .ignore_array_order()
+            .ignore_extra_fields(),
+    );
+
+    let mismatch = exact_json_body(&json!([{"a": 1, "b": 2}, {"a": 1, "b": 2}]))
+        .ignore_array_order()
+        .explain(&request)
+        .unwrap();
+    let leaves = mismatch
+        .leaves()
+        .iter()
+        .map(|leaf| leaf.to_string())
+        .collect::<Vec<_>>();
+
+    assert_eq!(
+        leaves,
+        vec!["/1: no element matching {\"a\":1,\"b\":2}", "(root): unexpected element {\"a\":1}",]
+    );
+}
+
+#[test]
+fn test_exact_json_body_invalid_json() {
+    let request = json_request("name=John");
+
+    let mismatch = exact_json_body(&json!({"name": "John"}))
+        .explain(&request)
+        .unwrap();
+
+    assert!(mismatch
+        .actual()
+        .starts_with("body was not valid JSON"));
+}
+
+#[test]
+fn test_json_path_value_assertions() {
+    let request = json_request(
+        r#"{"id": "a1", "name": "John", "email": "john@example.com", "items": [{"price": 10}, {"price": 2.5}]}"#,
+    );
+
+    expect(request.clone()).to_have(json_path("$.name").equals(&"John"));
+    expect(request.clone()).to_have(json_path("$.email").matches(r"^[^@]+@example\.com$"));
+    expect(request.clone()).to_have(json_path("$.items[*].price").gt(0));
+    expect(request.clone()).to_have(json_path("$.items[*].price").le(10));
+    expect(request.clone()).to_have(json_path("$.items").len(2));
+    expect(request.clone()).to_have(json_path("$.id").is(JsonType::String));
+    expect(request.clone()).to_have(json_path("$.items[0].price").is(JsonType::Integer));
+    expect(request).to_have(json_path("$.items").every(JsonPredicate::Type(JsonType::Object)));
+}
+
+#[test]
+fn test_json_path_numeric_precision() {
+    let request = json_request(
+        r#"{"id": 9007199254740993, "max": 18446744073709551615, "min": -9223372036854775808, "count": 3}"#,
+    );
+
+    expect(request.clone()).to_have(json_path("$.id").gt(9_007_199_254_740_992_u64));
+    expect(request.clone()).to_have(json_path("$.id").lt(9_007_199_254_740_994_i64));
+    expect(request.clone()).to_have(json_path("$.max").ge(u64::MAX));
+    expect(request.clone()).to_have(json_path("$.min").le(i64::MIN));
+    expect(request.clone()).to_have(json_path("$.min").lt(0_u64));
+    expect(request.clone()).to_have(json_path("$.count").ge(3_usize));
+    expect(request).to_have(json_path("$.count").lt(3.5));
+}
+
+#[test]
+fn test_json_path_value_mismatch() {
+    let request = json_request(r#"{"items": [{"price": 10}, {"price": 0}, {"price": -1}]}"#);
+
+    let mismatch = json_path("$.items[*].price")
+        .gt(0)
+        .explain(&request)
+        .unwrap();
+
+    assert_eq!(mismatch.actual(), "2 selected values failed");
+    assert_eq!(
+        mismatch
+            .causes()
+            .iter()
+            .map(|cause| cause.actual())
+            .collect::<Vec<_>>(),
+        vec!["value was 0", "value was -1"]
+    );
+
+    let mismatch = json_path("$.missing")
+        .equals(&1)
+        .explain(&request)
+        .unwrap();
+    assert_eq!(mismatch.actual(), "no value selected by $.missing");
+}
@@ -0,0 +1,92 @@
+use caramelo::{expect, Matcher};
+use http::Uri;
+use jsonwebtoken::{encode, EncodingKey, Header};
+use serde_json::{json, Value};
+
+use crate::{
+    matchers::{jwt, jwt_claims, JsonPredicate, MatcherNode},
+    mock::Request,
+};
+
+fn token(claims: &Value, secret: &[u8]) -> String {
+    encode(&Header::default(), claims, &EncodingKey::from_secret(secret)).unwrap()
+}
+
+fn bearer_request(token: &str) -> Request {
+    Request::get(Uri::from_static("/api/orders"))
+        .header("authorization", &format!("Bearer {}", token))
+        .empty()
+        .unwrap()
+}
+
+fn claims() -> Value {
+    json!({
+        "iss": "https://auth.example.com",
+        "sub": "user-1",
+        "aud": ["orders-api", "billing-api"],
+        "scope": "orders:read orders:write",
+        "exp": 4102444800u64,
+        "tenant": "acme",
+    })
+}
+
+#[test]
+fn test_jwt_compares_token() {
+    let request = bearer_request("abc.def.ghi");
+
+    assert!(jwt("abc.def.ghi").matches(&request));
+    assert!(!jwt("other.token.value").matches(&request));
+}
+
+#[test]
+fn test_jwt_claims() {
+    let request = bearer_request(&token(&claims(), b"secret"));
+
+    expect(request).to_have(
+        jwt_claims()
+            .issuer("https://auth.example.com")
+            .subject("user-1")
+            .audience("billing-api")
+            .scope("orders:write")
+            .not_expired()
+            .claim("$.tenant", JsonPredicate::Equals(json!("acme")))
+            .verify_hs256(b"secret"),
+    );
+}
+
+#[test]
+fn test_jwt_claims_reports_failed_checks() {
+    let mut expired = claims();
+    expired["exp"] = json!(1000);
+    let request = bearer_request(&token(&expired, b"secret"));
+
+    let mismatch = jwt_claims()
+        .issuer("https://auth.example.com")
+        .scope("admin")
+        .not_expired()
+        .explain(&request)
+        .unwrap();
+
+    let leaves = mismatch
+        .leaves()
+        .iter()
+        .map(|leaf| {
+            leaf.expected()
+                .to_string()
+        })
+        .collect::<Vec<_>>();
+    assert_eq!(leaves, vec!["scope \"admin\"", "not expired"]);
+}
+
+#[test]
+fn test_jwt_claims_rejects_invalid_signature() {
+    let request = bearer_request(&token(&claims(), b"other"));
+
+    assert!(jwt_claims()
+        .subject("user-1")
+        .matches(&request));
+    assert!(!jwt_claims()
+        .subject("user-1")
+        .verify_hs256(b"secret")
+        .matches(&request));
+}
@@ -0,0 +1,78 @@
+use http::Uri;
+
+use crate::{
+    matchers::{header, method, path, MatcherNode},
+    mock::{AsyncMatcherExt, Request},
+};
+
+#[test]
+fn test_header_mismatch_report() {
+    let request = Request::get(Uri::from_static("/api/users"))
+        .header("content-type", "application/json")
+        .header("accept", "application/json")
+        .empty()
+        .unwrap();
+
+    let mismatch = header("x-api-key")
+        .explain(&request)
+        .unwrap();
+
+    assert_eq!(mismatch.expected(), "header x-api-key");
+    assert_eq!(mismatch.actual(), "missing; present headers: accept, content-type");
+}
+
+#[test]
+fn test_and_mismatch_report() {
+    let request = Request::post(Uri::from_static("/api/users"))
+        .empty()
+        .unwrap();
+
+    let matcher = path(r"^/api/users$")
+        .and(method("GET"))
+        .and(header("x-api-key"));
+
+    let mismatch = matcher
+        .explain(&request)
+        .unwrap();
+    let leaves = mismatch.leaves();
+
+    assert_eq!(leaves.len(), 2);
+    assert_eq!(leaves[0].expected(), "method matching GET");
+    assert_eq!(leaves[0].actual(), "method was POST");
+    assert_eq!(leaves[1].expected(), "header x-api-key");
+    assert_eq!(
+        mismatch.to_string(),
+        "path matching Regex(\"^/api/users$\") and method matching GET and header matching x-api-key: 2 of 2 matchers failed\n  \
+         - path matching Regex(\"^/api/users$\") and method matching GET: 1 of 2 matchers failed\n    \
+         - method matching GET: method was POST\n  \
+         - header x-api-key: missing; no headers present"
+    );
+}
+
+#[test]
+fn test_or_mismatch_report() {
+    let request = Request::delete(Uri::from_static("/api/users"))
+        .empty()
+        .unwrap();
+
+    let matcher = method("GET").or(method("POST"));
+
+    let mismatch = matcher
+        .explain(&request)
+        .unwrap();
+
+    assert_eq!(mismatch.actual(), "none of the matchers succeeded");
+    assert_eq!(
+        mismatch
+            .causes()
+            .len(),
+        2
+    );
+    assert!(matcher
+        .explain(
+            &Request::post(Uri::from_static("/api/users"))
+                .empty()
+                .unwrap()
+        )
+        .is_none());
+}
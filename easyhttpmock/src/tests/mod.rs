@@ -1,2 +1,19 @@
+#[cfg(feature = "gzip")]
+mod compression;
+mod form;
 mod http;
+#[cfg(feature = "json")]
+mod json;
+#[cfg(feature = "jwt")]
+mod jwt;
+mod mismatch;
+mod namespace;
+#[cfg(feature = "oauth")]
+mod oauth;
+#[cfg(feature = "self-signed")]
+mod self_signed;
 mod server;
+#[cfg(feature = "mtls")]
+mod tls;
+#[cfg(feature = "xml")]
+mod xml;
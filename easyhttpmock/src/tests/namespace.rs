@@ -0,0 +1,96 @@
+use crate::{
+    journal::Journal,
+    matchers::path,
+    mock::{given, Mock, Request, StatusCodeExt},
+    namespace::Namespaces,
+    server::handle_request,
+};
+use http::{StatusCode, Uri};
+
+fn request(uri: &str) -> Request {
+    Request::get(Uri::try_from(uri).unwrap())
+        .empty()
+        .unwrap()
+}
+
+#[test]
+fn test_namespaces_are_isolated() {
+    let namespaces = Namespaces::new();
+    let root = namespaces
+        .mock()
+        .inner();
+    let journal = Journal::new();
+
+    let first = namespaces.create("http://localhost:8080");
+    let second = namespaces.create("http://localhost:8080/");
+    // Routing strips the prefix of the namespace and keeps the query
+    first.register_mock(Mock::of(
+        given(path(r"^/test\?id=1$")).will_return(
+            StatusCode::OK
+                .respond()
+                .with_body(b"first"),
+        ),
+    ));
+    second.register_mock(Mock::of(
+        given(path("^/test$")).will_return(
+            StatusCode::CREATED
+                .respond()
+                .empty(),
+        ),
+    ));
+
+    assert_eq!(first.url("/test"), format!("http://localhost:8080/{}/test", first.name()));
+    assert_eq!(second.url("/test"), format!("http://localhost:8080/{}/test", second.name()));
+
+    let respond =
+        handle_request(&root, &journal, request(&format!("{}/test?id=1", first.prefix()))).unwrap();
+    assert_eq!(respond.status_code(), StatusCode::OK);
+    assert_eq!(respond.body(), "first");
+
+    let respond =
+        handle_request(&root, &journal, request(&format!("{}/test", second.prefix()))).unwrap();
+    assert_eq!(respond.status_code(), StatusCode::CREATED);
+
+    let entries = first
+        .journal()
+        .entries();
+    assert_eq!(entries.len(), 1);
+    assert_eq!(
+        entries[0]
+            .request()
+            .path(),
+        "/test?id=1"
+    );
+    assert!(entries[0].is_matched());
+    assert_eq!(
+        second
+            .journal()
+            .len(),
+        1
+    );
+    assert!(journal.is_empty());
+}
+
+#[test]
+fn test_namespace_removed_on_drop() {
+    let namespaces = Namespaces::new();
+    let root = namespaces
+        .mock()
+        .inner();
+
+    let namespace = namespaces.create("http://localhost:8080");
+    let prefix = namespace.prefix();
+    assert_eq!(namespaces.len(), 1);
+
+    let respond =
+        handle_request(&root, &Journal::new(), request(&format!("{}/test", prefix))).unwrap();
+    assert_eq!(respond.status_code(), StatusCode::NOT_FOUND);
+
+    drop(namespace);
+    assert!(namespaces.is_empty());
+
+    let respond =
+        handle_request(&root, &Journal::new(), request(&format!("{}/test", prefix))).unwrap();
+    assert_eq!(respond.status_code(), StatusCode::NOT_FOUND);
+    assert_eq!(respond.body(), "Namespace not found");
+}
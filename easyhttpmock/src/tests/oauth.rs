@@ -0,0 +1,300 @@
+use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine};
+use caramelo::{expect, Matcher};
+use http::{StatusCode, Uri};
+use serde_json::Value;
+use sha2::{Digest, Sha256};
+
+use crate::{
+    journal::Journal,
+    matchers::{jwt_claims, path},
+    mock::{given, Mock, QueryParams, Request, Respond, StatusCodeExt},
+    oauth::OAuthProvider,
+    server::handle_request,
+};
+
+fn provider() -> OAuthProvider {
+    OAuthProvider::builder()
+        .issuer("http://localhost:8080/")
+        .audience("orders-api")
+        .client("client", "secret")
+        .user("alice", "wonderland")
+        .build()
+}
+
+fn token_request(form: &'static str) -> Request {
+    Request::post(Uri::from_static("/oauth/token"))
+        .header("content-type", "application/x-www-form-urlencoded")
+        .body(form)
+        .unwrap()
+}
+
+fn json(respond: &Respond) -> Value {
+    serde_json::from_slice(&respond.body()).unwrap()
+}
+
+fn bearer_request(token: &str) -> Request {
+    Request::get(Uri::from_static("/api/orders"))
+        .header("authorization", &format!("Bearer {}", token))
+        .empty()
+        .unwrap()
+}
+
+#[test]
+fn test_discovery_and_jwks() {
+    let provider = provider();
+
+    let discovery = provider
+        .handle(
+            &Request::get(Uri::from_static("/.well-known/openid-configuration"))
+                .empty()
+                .unwrap(),
+        )
+        .unwrap();
+    let discovery = json(&discovery);
+    assert_eq!(discovery["issuer"], "http://localhost:8080");
+    assert_eq!(discovery["token_endpoint"], "http://localhost:8080/oauth/token");
+
+    let jwks = provider
+        .handle(
+            &Request::get(Uri::from_static("/oauth/jwks"))
+                .empty()
+                .unwrap(),
+        )
+        .unwrap();
+    assert_eq!(json(&jwks)["keys"][0]["crv"], "P-256");
+
+    assert!(provider
+        .handle(
+            &Request::get(Uri::from_static("/api/orders"))
+                .empty()
+                .unwrap()
+        )
+        .is_none());
+}
+
+#[test]
+fn test_client_credentials_grant() {
+    let provider = provider();
+
+    let respond = provider
+        .handle(&token_request(
+            "grant_type=client_credentials&client_id=client&client_secret=secret&scope=orders%3Aread",
+        ))
+        .unwrap();
+    assert_eq!(respond.status_code(), StatusCode::OK);
+
+    let body = json(&respond);
+    assert!(body
+        .get("refresh_token")
+        .is_none());
+
+    expect(bearer_request(
+        body["access_token"]
+            .as_str()
+            .unwrap(),
+    ))
+    .to_have(
+        jwt_claims()
+            .issuer("http://localhost:8080")
+            .audience("orders-api")
+            .subject("client")
+            .scope("orders:read")
+            .not_expired()
+            .verify_es256_pem(
+                provider
+                    .public_key_pem()
+                    .as_bytes(),
+            ),
+    );
+
+    let rejected = provider
+        .handle(&token_request("grant_type=client_credentials&client_id=client&client_secret=bad"))
+        .unwrap();
+    assert_eq!(rejected.status_code(), StatusCode::UNAUTHORIZED);
+    assert_eq!(json(&rejected)["error"], "invalid_client");
+}
+
+#[test]
+fn test_password_and_refresh_token_grants() {
+    let provider = provider();
+
+    let body = json(
+        &provider
+            .handle(&token_request(
+                "grant_type=password&client_id=client&client_secret=secret&username=alice&password=wonderland",
+            ))
+            .unwrap(),
+    );
+    assert!(jwt_claims()
+        .subject("alice")
+        .matches(&bearer_request(
+            body["access_token"]
+                .as_str()
+                .unwrap()
+        )));
+
+    let refresh_token = body["refresh_token"]
+        .as_str()
+        .unwrap()
+        .to_string();
+    let form = format!(
+        "grant_type=refresh_token&client_id=client&client_secret=secret&refresh_token={}",
+        refresh_token
+    );
+    let refresh = |form: &str| {
+        provider
+            .handle(
+                &Request::post(Uri::from_static("/oauth/token"))
+                    .body(form.to_string())
+                    .unwrap(),
+            )
+            .unwrap()
+    };
+
+    assert_eq!(refresh(&form).status_code(), StatusCode::OK);
+    // Refresh tokens are rotated, so they can only be used once
+    assert_eq!(json(&refresh(&form))["error"], "invalid_grant");
+}
+
+#[test]
+fn test_authorization_code_grant_with_pkce() {
+    let provider = provider();
+    let verifier = "dBjftJeZ4CVP-mB92K27uhbUJU1p1r_wW1gFWFOEjXk";
+    let challenge = URL_SAFE_NO_PAD.encode(Sha256::digest(verifier.as_bytes()));
+
+    let authorize = format!(
+        "/oauth/authorize?response_type=code&client_id=client&redirect_uri=http%3A%2F%2Fapp%2Fcallback&state=xyz&scope=openid&code_challenge={}&code_challenge_method=S256",
+        challenge
+    );
+    let redirect = provider
+        .handle(
+            &Request::get(
+                authorize
+                    .parse()
+                    .unwrap(),
+            )
+            .empty()
+            .unwrap(),
+        )
+        .unwrap();
+    assert_eq!(redirect.status_code(), StatusCode::FOUND);
+
+    let location = redirect.headers()["location"].clone();
+    let (callback, query) = location
+        .split_once('?')
+        .unwrap();
+    let params = QueryParams::parse(query);
+    assert_eq!(callback, "http://app/callback");
+    assert_eq!(params.get("state"), Some("xyz"));
+
+    let exchange = |verifier: &str| {
+        let form = format!(
+            "grant_type=authorization_code&client_id=client&redirect_uri=http%3A%2F%2Fapp%2Fcallback&code={}&code_verifier={}",
+            params.get("code").unwrap(),
+            verifier
+        );
+        provider
+            .handle(
+                &Request::post(Uri::from_static("/oauth/token"))
+                    .body(form)
+                    .unwrap(),
+            )
+            .unwrap()
+    };
+
+    let body = json(&exchange(verifier));
+    assert!(body["id_token"].is_string());
+    assert!(jwt_claims()
+        .subject("user")
+        .matches(&bearer_request(
+            body["access_token"]
+                .as_str()
+                .unwrap()
+        )));
+
+    // Authorization codes can only be exchanged once
+    assert_eq!(json(&exchange(verifier))["error"], "invalid_grant");
+}
+
+#[test]
+fn test_public_client_requires_pkce() {
+    let provider = provider();
+
+    let rejected = provider
+        .handle(&token_request("grant_type=client_credentials&client_id=client&code_verifier=x"))
+        .unwrap();
+    assert_eq!(rejected.status_code(), StatusCode::UNAUTHORIZED);
+    assert_eq!(json(&rejected)["error"], "invalid_client");
+
+    let redirect = provider
+        .handle(
+            &Request::get(Uri::from_static(
+                "/oauth/authorize?response_type=code&client_id=client&redirect_uri=http%3A%2F%2Fapp%2Fcallback",
+            ))
+            .empty()
+            .unwrap(),
+        )
+        .unwrap();
+    let location = redirect.headers()["location"].clone();
+    let params = QueryParams::parse(
+        location
+            .split_once('?')
+            .unwrap()
+            .1,
+    );
+
+    let exchange = |credentials: &str| {
+        let form = format!(
+            "grant_type=authorization_code&client_id=client&redirect_uri=http%3A%2F%2Fapp%2Fcallback&code={}&{}",
+            params.get("code").unwrap(),
+            credentials
+        );
+        provider
+            .handle(
+                &Request::post(Uri::from_static("/oauth/token"))
+                    .body(form)
+                    .unwrap(),
+            )
+            .unwrap()
+    };
+
+    let rejected = exchange("code_verifier=x");
+    assert_eq!(rejected.status_code(), StatusCode::UNAUTHORIZED);
+    assert_eq!(json(&rejected)["error"], "invalid_client");
+
+    let accepted = exchange("client_secret=secret");
+    assert_eq!(accepted.status_code(), StatusCode::OK);
+}
+
+#[test]
+fn test_provider_requests_are_not_journaled() {
+    let mock = Mock::of(
+        given(path("^/api/orders$"))
+            .with_oauth(provider())
+            .will_return(
+                StatusCode::OK
+                    .respond()
+                    .empty(),
+            ),
+    )
+    .inner();
+    let journal = Journal::new();
+
+    let token = handle_request(
+        &mock,
+        &journal,
+        token_request("grant_type=client_credentials&client_id=client&client_secret=secret"),
+    )
+    .unwrap();
+    let access_token = json(&token)["access_token"]
+        .as_str()
+        .unwrap()
+        .to_string();
+    let respond = handle_request(&mock, &journal, bearer_request(&access_token)).unwrap();
+    assert_eq!(respond.status_code(), StatusCode::OK);
+
+    assert_eq!(journal.len(), 1);
+    assert!(journal
+        .verify()
+        .is_ok());
+}
@@ -0,0 +1,55 @@
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr};
+
+use crate::tls::SelfSignedTls;
+
+fn generate() -> SelfSignedTls {
+    SelfSignedTls::generate(
+        "localhost",
+        &[IpAddr::V4(Ipv4Addr::LOCALHOST), IpAddr::V6(Ipv6Addr::LOCALHOST)],
+    )
+    .expect("Failed to generate self-signed certificate")
+}
+
+#[test]
+fn test_self_signed_tls_generates_ca_and_server_certificate() {
+    let tls = generate();
+
+    assert!(tls
+        .ca_pem()
+        .starts_with("-----BEGIN CERTIFICATE-----"));
+    assert!(!tls
+        .ca_der()
+        .is_empty());
+    assert!(!tls
+        .cert_der()
+        .is_empty());
+    assert!(!tls
+        .key_der()
+        .is_empty());
+    assert_ne!(tls.ca_der(), tls.cert_der());
+}
+
+#[test]
+fn test_self_signed_tls_is_ephemeral() {
+    assert_ne!(generate().ca_der(), generate().ca_der());
+}
+
+#[cfg(feature = "mtls")]
+#[test]
+fn test_self_signed_tls_server_certificate_names() {
+    use crate::tls::ClientCertificate;
+
+    let tls = generate();
+    let server = ClientCertificate::new(
+        tls.cert_der()
+            .to_vec(),
+    );
+    assert_eq!(server.common_name(), Some("localhost".to_string()));
+    assert_eq!(server.subject_alt_names(), vec!["localhost", "127.0.0.1", "::1"]);
+
+    let ca = ClientCertificate::new(
+        tls.ca_der()
+            .to_vec(),
+    );
+    assert_eq!(ca.common_name(), Some("EasyHttpMock CA".to_string()));
+}
@@ -2,8 +2,10 @@
 use crate::{
     config::EasyHttpMockConfig,
     errors::EasyHttpMockError,
-    mock::Mock,
-    server::{PortGenerator, ServerAdapter},
+    journal::Journal,
+    matchers::{path, path_template},
+    mock::{given, Mock, Request, StatusCodeExt},
+    server::{handle_request, InFlight, PortGenerator, ServerAdapter},
     EasyHttpMock,
 };
 use caramelo::{
@@ -11,7 +13,21 @@ use caramelo::{
     matchers::{ge, lt},
     MatcherExt,
 };
-use std::{error::Error, sync::Arc};
+use http::{StatusCode, Uri};
+use std::{
+    error::Error,
+    future::{pending, ready, Future},
+    pin::pin,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    },
+    task::{Context, Poll, Wake, Waker},
+    thread::{self, Thread},
+    time::Duration,
+};
+
+static SHUTDOWNS: AtomicUsize = AtomicUsize::new(0);
 
 #[derive(Debug, Clone)]
 pub struct TestServerConfig {
@@ -38,13 +54,14 @@ impl TestServerConfig {
 pub struct TestServer {
     config: TestServerConfig,
     mock: Option<Arc<Mock>>,
+    journal: Journal,
 }
 
 impl ServerAdapter for TestServer {
     type Config = TestServerConfig;
 
     fn new(config: Self::Config) -> Result<Self, EasyHttpMockError> {
-        Ok(Self { config, mock: None })
+        Ok(Self { config, mock: None, journal: Journal::new() })
     }
 
     fn hostname(&self) -> String {
@@ -63,6 +80,10 @@ impl ServerAdapter for TestServer {
         &mut self.config
     }
 
+    fn journal(&self) -> &Journal {
+        &self.journal
+    }
+
     fn register_mock(&mut self, mock: Arc<Mock>) {
         self.mock = Some(mock);
     }
@@ -74,6 +95,10 @@ impl ServerAdapter for TestServer {
     async fn stop(&mut self) -> Result<(), EasyHttpMockError> {
         todo!()
     }
+
+    fn shutdown(&mut self) {
+        SHUTDOWNS.fetch_add(1, Ordering::SeqCst);
+    }
 }
 
 impl PortGenerator<TestServer> for TestServerConfig {
@@ -118,3 +143,151 @@ fn test_random_port() -> Result<(), Box<dyn Error>> {
 
     Ok(())
 }
+
+#[test]
+fn test_http_and_https_urls() -> Result<(), Box<dyn Error>> {
+    let mock_server = EasyHttpMock::<TestServer>::new(EasyHttpMockConfig {
+        server_config: TestServerConfig { port: 8080, interface: "127.0.0.1".to_string() },
+        base_url: None,
+    })?;
+
+    assert_eq!(mock_server.http_url("/test"), "http://localhost:8080/test");
+    assert_eq!(mock_server.https_base_url(), None);
+
+    Ok(())
+}
+
+#[test]
+#[should_panic = "Mock server has no HTTPS listener"]
+fn test_https_url_without_https_listener() {
+    let mock_server = EasyHttpMock::<TestServer>::new(EasyHttpMockConfig {
+        server_config: TestServerConfig { port: 8080, interface: "127.0.0.1".to_string() },
+        base_url: None,
+    })
+    .unwrap();
+
+    mock_server.https_url("/test");
+}
+
+#[test]
+fn test_handle_request() {
+    let mock = Mock::of(
+        given(path("/test")).will_return(
+            StatusCode::CREATED
+                .respond()
+                .with_body(b"created"),
+        ),
+    )
+    .inner();
+    let journal = Journal::new();
+
+    let request = Request::get(Uri::from_static("/test"))
+        .empty()
+        .unwrap();
+    let respond = handle_request(&mock, &journal, request).unwrap();
+
+    assert_eq!(respond.status_code(), StatusCode::CREATED);
+    assert_eq!(journal.len(), 1);
+    assert!(journal.entries()[0].is_matched());
+}
+
+#[test]
+fn test_handle_request_with_path_params() {
+    let mock = Mock::of(
+        given(path_template(r"/users/{id}/orders/{orderId:\d+}")).will_return(
+            StatusCode::OK
+                .respond()
+                .with_header("Location", "/users/{id}/orders/{orderId}")
+                .with_template(r#"{"user": "{id}", "order": {orderId}, "item": "{itemId}"}"#),
+        ),
+    )
+    .inner();
+    let journal = Journal::new();
+
+    let request = Request::get(Uri::from_static("/users/john/orders/42"))
+        .empty()
+        .unwrap();
+    let respond = handle_request(&mock, &journal, request).unwrap();
+
+    assert_eq!(respond.body(), &br#"{"user": "john", "order": 42, "item": "{itemId}"}"#[..]);
+    assert_eq!(
+        respond
+            .headers()
+            .get("Location")
+            .map(String::as_str),
+        Some("/users/john/orders/42")
+    );
+}
+
+#[test]
+fn test_shutdown_on_drop() -> Result<(), Box<dyn Error>> {
+    let mock_server = EasyHttpMock::<TestServer>::new(EasyHttpMockConfig {
+        server_config: TestServerConfig::default(),
+        base_url: None,
+    })?;
+
+    let shutdowns = SHUTDOWNS.load(Ordering::SeqCst);
+    drop(mock_server);
+
+    assert!(SHUTDOWNS.load(Ordering::SeqCst) > shutdowns);
+
+    Ok(())
+}
+
+#[test]
+fn test_in_flight_requests() {
+    let in_flight = InFlight::new();
+
+    let first = in_flight.enter();
+    let second = in_flight
+        .clone()
+        .enter();
+    assert_eq!(in_flight.len(), 2);
+
+    drop(first);
+    drop(second);
+    assert!(in_flight.is_empty());
+}
+
+#[test]
+fn test_in_flight_drain() {
+    let in_flight = InFlight::new();
+    assert!(block_on(in_flight.drain(pending())));
+
+    let guard = in_flight.enter();
+    assert!(!block_on(in_flight.drain(ready(()))));
+
+    let completing = thread::spawn(move || {
+        thread::sleep(Duration::from_millis(50));
+        drop(guard);
+    });
+    assert!(block_on(in_flight.drain(pending())));
+
+    completing
+        .join()
+        .unwrap();
+}
+
+struct ThreadWaker(Thread);
+
+impl Wake for ThreadWaker {
+    fn wake(self: Arc<Self>) {
+        self.0.unpark();
+    }
+}
+
+fn block_on<F: Future>(future: F) -> F::Output {
+    let mut future = pin!(future);
+    let waker = Waker::from(Arc::new(ThreadWaker(thread::current())));
+    let mut cx = Context::from_waker(&waker);
+
+    loop {
+        if let Poll::Ready(output) = future
+            .as_mut()
+            .poll(&mut cx)
+        {
+            return output;
+        }
+        thread::park();
+    }
+}
@@ -0,0 +1,66 @@
+use caramelo::{expect, Matcher};
+use http::Uri;
+
+use crate::{
+    journal::JournalEntry,
+    matchers::{client_cert, path, MatcherNode},
+    mock::{given, Request},
+    tls::ClientCertificate,
+};
+
+const CLIENT_CERT: &[u8] = include_bytes!("../../../certs/client.der");
+const SERVER_CERT: &[u8] = include_bytes!("../../../certs/server.der");
+
+fn request_with_certificate(der: &[u8]) -> Request {
+    Request::get(Uri::from_static("/api/users"))
+        .empty()
+        .unwrap()
+        .with_client_certificate(ClientCertificate::new(der.to_vec()))
+}
+
+#[test]
+fn test_client_certificate_fields() {
+    let client = ClientCertificate::new(CLIENT_CERT.to_vec());
+    assert_eq!(client.common_name(), Some("testuser".to_string()));
+    assert_eq!(
+        client.fingerprint(),
+        "84a6e2238563d27a9f6fb182b89cebd2fbecf9907635252e2aab6fb236274372"
+    );
+
+    let server = ClientCertificate::new(SERVER_CERT.to_vec());
+    assert_eq!(server.subject_alt_names(), vec!["localhost", "127.0.0.1"]);
+}
+
+#[test]
+fn test_client_cert_matcher() {
+    expect(request_with_certificate(CLIENT_CERT)).to_have(
+        client_cert()
+            .subject_cn("testuser")
+            .fingerprint("84:A6:E2:23:85:63:D2:7A:9F:6F:B1:82:B8:9C:EB:D2:FB:EC:F9:90:76:35:25:2E:2A:AB:6F:B2:36:27:43:72"),
+    );
+    assert!(client_cert()
+        .san("127.0.0.1")
+        .matches(&request_with_certificate(SERVER_CERT)));
+
+    let mismatch = client_cert()
+        .explain(
+            &Request::get(Uri::from_static("/api/users"))
+                .empty()
+                .unwrap(),
+        )
+        .unwrap();
+    assert_eq!(mismatch.actual(), "no client certificate was presented");
+}
+
+#[test]
+fn test_journal_records_client_certificate() {
+    let mock = given(path("/api/users"));
+    let entry = JournalEntry::new(request_with_certificate(CLIENT_CERT), &mock);
+
+    assert_eq!(
+        entry
+            .client_certificate()
+            .and_then(ClientCertificate::common_name),
+        Some("testuser".to_string())
+    );
+}
@@ -0,0 +1,81 @@
+use caramelo::{expect, Matcher};
+use http::Uri;
+
+use crate::{
+    matchers::{exact_xml_body_str, xpath, MatcherNode},
+    mock::Request,
+};
+
+fn xml_request(body: &'static str) -> Request {
+    Request::post(Uri::from_static("/soap"))
+        .header("content-type", "text/xml")
+        .body(body)
+        .unwrap()
+}
+
+#[test]
+fn test_exact_xml_body_ignores_prefixes_and_attribute_order() {
+    let request = xml_request(
+        r#"<?xml version="1.0"?>
+        <env:Envelope xmlns:env="http://schemas.xmlsoap.org/soap/envelope/">
+            <!-- request -->
+            <env:Body>
+                <GetUser lang="en" id="1"/>
+            </env:Body>
+        </env:Envelope>"#,
+    );
+
+    expect(request).to_have(exact_xml_body_str(
+        r#"<soap:Envelope xmlns:soap="http://schemas.xmlsoap.org/soap/envelope/"><soap:Body><GetUser id="1" lang="en"/></soap:Body></soap:Envelope>"#,
+    ));
+}
+
+#[test]
+fn test_exact_xml_body_reports_paths() {
+    let request = xml_request(
+        r#"<order id="2"><item>apple</item><item>pear</item><note>fast</note></order>"#,
+    );
+
+    let mismatch =
+        exact_xml_body_str(r#"<order id="1"><item>apple</item><item>plum</item></order>"#)
+            .explain(&request)
+            .unwrap();
+
+    let leaves = mismatch
+        .leaves()
+        .iter()
+        .map(|leaf| leaf.to_string())
+        .collect::<Vec<_>>();
+
+    assert_eq!(
+        leaves,
+        vec![
+            "/order/@id: expected \"1\", got \"2\"",
+            "/order/item[2]/text(): expected \"plum\", got \"pear\"",
+            "/order/note: unexpected element note",
+        ]
+    );
+}
+
+#[test]
+fn test_xpath_predicates() {
+    let request = xml_request(
+        r#"<order><item sku="A1">apple</item><item sku="B2">pear</item><total> 2 </total></order>"#,
+    );
+
+    assert!(xpath("//total")
+        .equals("2")
+        .matches(&request));
+    assert!(xpath("//item/@sku")
+        .matches(r"^[A-Z]\d$")
+        .matches(&request));
+    assert!(xpath("//item")
+        .count(2)
+        .matches(&request));
+    assert!(!xpath("//item")
+        .count(3)
+        .matches(&request));
+    assert!(!xpath("//customer")
+        .equals("John")
+        .matches(&request));
+}
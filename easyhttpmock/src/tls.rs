@@ -0,0 +1,248 @@
+use bytes::Bytes;
+
+/// Whether the server asks clients for a TLS certificate
+///
+/// # Examples
+///
+/// ```rust
+/// use easyhttpmock::tls::ClientAuth;
+///
+/// assert_eq!(ClientAuth::default(), ClientAuth::None);
+/// ```
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum ClientAuth {
+    /// Client certificates are not requested
+    #[default]
+    None,
+    /// Client certificates are requested, clients without one are still accepted
+    Optional,
+    /// Client certificates are required, clients without one are rejected during the handshake
+    Required,
+}
+
+impl ClientAuth {
+    /// Returns whether client certificates are requested at all
+    pub fn is_enabled(&self) -> bool {
+        *self != ClientAuth::None
+    }
+}
+
+/// Certificate presented by the client during the TLS handshake
+///
+/// Transports record it by inserting it into the extensions of the request, it is then available
+/// on the [`Request`](crate::mock::Request) and in the journal.
+///
+/// # Examples
+///
+/// ```rust
+/// use easyhttpmock::tls::ClientCertificate;
+///
+/// let certificate = ClientCertificate::new(include_bytes!("../../certs/client.der").to_vec());
+/// assert!(!certificate.der().is_empty());
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ClientCertificate {
+    der: Bytes,
+}
+
+impl ClientCertificate {
+    /// Creates a client certificate from its DER encoding
+    ///
+    /// # Arguments
+    ///
+    /// * `der` - The DER encoded certificate
+    pub fn new(der: impl Into<Bytes>) -> Self {
+        Self { der: der.into() }
+    }
+
+    #[inline]
+    /// Get the DER encoding of the certificate
+    pub fn der(&self) -> &Bytes {
+        &self.der
+    }
+
+    /// Returns the common name of the certificate subject
+    ///
+    /// # Returns
+    ///
+    /// * `Option<String>` - The common name, `None` when the certificate cannot be parsed or has
+    ///   no common name
+    #[cfg(feature = "mtls")]
+    pub fn common_name(&self) -> Option<String> {
+        let (_, certificate) = x509_parser::parse_x509_certificate(&self.der).ok()?;
+        let common_name = certificate
+            .subject()
+            .iter_common_name()
+            .next()?
+            .as_str()
+            .ok()?
+            .to_string();
+
+        Some(common_name)
+    }
+
+    /// Returns the DNS names, IP addresses, emails and URIs of the subject alternative names
+    ///
+    /// # Returns
+    ///
+    /// * `Vec<String>` - The subject alternative names, empty when there are none
+    #[cfg(feature = "mtls")]
+    pub fn subject_alt_names(&self) -> Vec<String> {
+        use std::net::IpAddr;
+        use x509_parser::extensions::GeneralName;
+
+        let Ok((_, certificate)) = x509_parser::parse_x509_certificate(&self.der) else {
+            return Vec::new();
+        };
+        let Ok(Some(names)) = certificate.subject_alternative_name() else {
+            return Vec::new();
+        };
+
+        names
+            .value
+            .general_names
+            .iter()
+            .filter_map(|name| match name {
+                GeneralName::DNSName(name)
+                | GeneralName::RFC822Name(name)
+                | GeneralName::URI(name) => Some(name.to_string()),
+                GeneralName::IPAddress(octets) => match octets.len() {
+                    4 => <[u8; 4]>::try_from(*octets)
+                        .ok()
+                        .map(|octets| IpAddr::from(octets).to_string()),
+                    16 => <[u8; 16]>::try_from(*octets)
+                        .ok()
+                        .map(|octets| IpAddr::from(octets).to_string()),
+                    _ => None,
+                },
+                _ => None,
+            })
+            .collect()
+    }
+
+    /// Returns the SHA-256 fingerprint of the certificate, as lowercase hex without separators
+    #[cfg(feature = "mtls")]
+    pub fn fingerprint(&self) -> String {
+        use sha2::{Digest, Sha256};
+
+        Sha256::digest(&self.der)
+            .iter()
+            .map(|byte| format!("{:02x}", byte))
+            .collect()
+    }
+}
+
+/// Ephemeral CA and server certificate generated for a mock server
+///
+/// The server certificate is signed by the CA and valid for the given hostname and IP
+/// addresses, clients trust the mock server by adding the CA to their trust store.
+///
+/// # Examples
+///
+/// ```rust
+/// use easyhttpmock::tls::SelfSignedTls;
+/// use std::net::{IpAddr, Ipv4Addr};
+///
+/// let tls = SelfSignedTls::generate("localhost", &[IpAddr::V4(Ipv4Addr::LOCALHOST)]).unwrap();
+/// assert!(tls.ca_pem().starts_with("-----BEGIN CERTIFICATE-----"));
+/// ```
+#[cfg(feature = "self-signed")]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SelfSignedTls {
+    ca_der: Vec<u8>,
+    ca_pem: String,
+    cert_der: Vec<u8>,
+    key_der: Vec<u8>,
+}
+
+#[cfg(feature = "self-signed")]
+impl SelfSignedTls {
+    /// Generates a new CA and a server certificate signed by it
+    ///
+    /// # Arguments
+    ///
+    /// * `hostname` - The DNS name the server certificate is valid for
+    /// * `ip_addresses` - The IP addresses the server certificate is valid for
+    ///
+    /// # Returns
+    ///
+    /// * `Result<SelfSignedTls, ServerError>` - The generated certificates or an error
+    pub fn generate(
+        hostname: &str,
+        ip_addresses: &[std::net::IpAddr],
+    ) -> Result<Self, crate::errors::ServerError> {
+        use crate::errors::ServerError;
+        use rcgen::{
+            BasicConstraints, CertificateParams, DnType, ExtendedKeyUsagePurpose, IsCa, Issuer,
+            KeyPair, KeyUsagePurpose, SanType,
+        };
+
+        let error = |e: rcgen::Error| ServerError::Config(e.to_string());
+
+        let mut ca_params = CertificateParams::new(Vec::<String>::new()).map_err(error)?;
+        ca_params.is_ca = IsCa::Ca(BasicConstraints::Unconstrained);
+        ca_params
+            .distinguished_name
+            .push(DnType::CommonName, "EasyHttpMock CA");
+        ca_params.key_usages = vec![
+            KeyUsagePurpose::DigitalSignature,
+            KeyUsagePurpose::KeyCertSign,
+            KeyUsagePurpose::CrlSign,
+        ];
+        let ca_key = KeyPair::generate().map_err(error)?;
+        let ca = ca_params
+            .self_signed(&ca_key)
+            .map_err(error)?;
+        let issuer = Issuer::new(ca_params, ca_key);
+
+        let mut params = CertificateParams::new(vec![hostname.to_string()]).map_err(error)?;
+        params
+            .subject_alt_names
+            .extend(
+                ip_addresses
+                    .iter()
+                    .map(|ip_address| SanType::IpAddress(*ip_address)),
+            );
+        params
+            .distinguished_name
+            .push(DnType::CommonName, hostname);
+        params.use_authority_key_identifier_extension = true;
+        params.key_usages = vec![KeyUsagePurpose::DigitalSignature];
+        params.extended_key_usages = vec![ExtendedKeyUsagePurpose::ServerAuth];
+        let key = KeyPair::generate().map_err(error)?;
+        let cert = params
+            .signed_by(&key, &issuer)
+            .map_err(error)?;
+
+        Ok(Self {
+            ca_der: ca.der().to_vec(),
+            ca_pem: ca.pem(),
+            cert_der: cert.der().to_vec(),
+            key_der: key.serialize_der(),
+        })
+    }
+
+    #[inline]
+    /// Get the DER encoded CA certificate
+    pub fn ca_der(&self) -> &[u8] {
+        &self.ca_der
+    }
+
+    #[inline]
+    /// Get the PEM encoded CA certificate, to add to the trust store of the client
+    pub fn ca_pem(&self) -> &str {
+        &self.ca_pem
+    }
+
+    #[inline]
+    /// Get the DER encoded server certificate
+    pub fn cert_der(&self) -> &[u8] {
+        &self.cert_der
+    }
+
+    #[inline]
+    /// Get the DER encoded PKCS#8 private key of the server certificate
+    pub fn key_der(&self) -> &[u8] {
+        &self.key_der
+    }
+}